@@ -1,6 +1,7 @@
 use crate::model::{
-    ConfigFile, DetectedTool, DriftState, EditorState, EnvVar, InstalledTool, MiseProject,
-    MiseSetting, MiseTask, OutdatedTool, PruneCandidate, RegistryEntry,
+    ConfigFile, DriftFix, DriftReport, EditorState, FleetOp, FleetPlan, FleetResult, EnvVar, InstalledTool, MiseProject,
+    MiseSetting, MiseTask, OutdatedTool, PruneCandidate, RegistryEntry, TrustEntry, TrustOp,
+    WizardPackage,
 };
 
 #[derive(Debug, Clone)]
@@ -33,17 +34,26 @@ pub enum Action {
     EnvLoaded(Vec<EnvVar>),
     SettingsLoaded(Vec<MiseSetting>),
     PruneLoaded(Vec<PruneCandidate>),
+    /// A result of `App::start_fetch`, tagged with the fetch generation it belongs to.
+    Fetched(u64, Box<Action>),
+    TrustEntriesLoaded(Vec<TrustEntry>),
     ToolInfoLoaded(String),
-    ProjectsLoaded(Vec<MiseProject>),
+    /// A project found by the scan with the given generation, streamed before it finishes.
+    ProjectScanned(u64, MiseProject),
+    ProjectsLoaded(u64, Vec<MiseProject>),
 
     // Drift indicator
     CheckDrift,
-    DriftChecked(DriftState),
+    DriftChecked(DriftReport),
     JumpToDriftProject,
+    OpenDriftBreakdown,
+    OpenChangeDir,
+    DriftApply(DriftFix),
 
     // Bootstrap Wizard
-    WizardDetected(Vec<DetectedTool>),
+    WizardDetected(Vec<WizardPackage>),
     WizardToggleTool,
+    WizardToggleMonorepo,
     WizardToggleAgentFiles,
     WizardNextStep,
     WizardPrevStep,
@@ -51,6 +61,8 @@ pub enum Action {
 
     // Editor (popup-based editing of config rows)
     EditorStatesLoaded(Vec<EditorState>),
+    /// Keys defined by a config's `_.file` dotenv files: (config path, files, keys).
+    DotenvKeysLoaded(String, String, Vec<String>),
     EditorConfirmEdit,
     EditorCancelEdit,
     EditorNextField,
@@ -59,6 +71,19 @@ pub enum Action {
     EditorWrite,
     EditorWriteComplete(String),
     EditorInput(char),
+    EditorNewline,
+    EditorUndo,
+    /// Result of checking `versions` of `tool` against `mise ls-remote`; `missing`
+    /// lists the ones with no matching remote version.
+    EditorRemoteChecked {
+        tool: String,
+        versions: Vec<String>,
+        missing: Vec<String>,
+    },
+    EditorMergeExternal,
+    EditorReloadExternal,
+    EditorRedo,
+    EditorPrevField,
     EditorBackspace,
 
     // Operations
@@ -71,14 +96,30 @@ pub enum Action {
     RunTask,
     UseTool,
     PruneTool,
+    PruneToggle,
+    PruneToggleAll,
+    PruneKeepMore,
+    PruneKeepLess,
     Refresh,
     TrustConfig,
+    OpenTrustManager,
+    TrustToggle,
+    TrustToggleAll,
+    TrustApply(TrustOp),
     ShowToolDetail,
     InstallProjectTools { path: String },
     UpdateProjectPins { path: String },
+    OpenFleetPlan(FleetOp),
+    FleetPlanned(FleetPlan),
+    FleetDone(FleetOp, Vec<FleetResult>),
+    RegenerateLock,
     CycleSortOrder,
     OpenScanConfig,
     SaveScanConfig,
+    ToggleFavorite,
+    CycleProjectGroup,
+    OpenProjectTags,
+    OpenExportReport,
 
     // Mouse / popup search
     MouseClick { x: u16, y: u16 },
@@ -1,13 +1,16 @@
 use crate::action::Action;
 use crate::mise;
+use crate::report::{self, ReportFormat};
 use crate::model::{
-    ConfigFile, DriftState, EditorEnvRow, EditorRowStatus, EditorState, EditorTaskRow,
-    EditorToolRow, EnvVar, InstalledTool, MiseProject, MiseSetting, MiseTask,
-    OutdatedTool, RegistryEntry, WizardState, WizardStep,
+    ConfigFile, DriftFix, DriftIssue, DriftReport, DriftState, EditorEnvRow, FleetOp, FleetPlan,
+    FleetResult, EditorRowStatus, EditorState, EditorTaskRow,
+    EditorToolRow, EnvRowKind, EnvVar, InstalledTool, MiseProject, MiseSetting, MiseTask,
+    OutdatedTool, PruneCandidate, RegistryEntry, ToolUsage, TrustEntry, TrustOp, TrustState, WizardState,
+    WizardStep,
 };
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tokio::sync::mpsc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -92,12 +95,70 @@ pub enum Popup {
     Progress {
         message: String,
     },
+    /// Uninstall confirmation for a version that scanned projects or configs still need.
+    /// The user must type the tool name before Enter is accepted.
+    UninstallGuard {
+        tool: String,
+        version: String,
+        /// Requirements that would become Missing after the uninstall.
+        impacted: Vec<ToolUsage>,
+        /// Text typed so far; must equal `tool` to confirm.
+        typed: String,
+    },
     ToolDetail {
         tool_name: String,
         info: String,
+        /// Installed versions of this tool paired with the projects/configs that use them.
+        used_by: Vec<(String, Vec<ToolUsage>)>,
         scroll: usize,
     },
     Help,
+    /// Per-tool breakdown of `App::drift` with install / trust / upgrade fixes.
+    DriftBreakdown,
+    /// Consolidated preview of a fleet-wide Projects operation; Enter runs it.
+    FleetPlan {
+        plan: FleetPlan,
+        scroll: usize,
+    },
+    /// Per-project outcome of a fleet run.
+    FleetResults {
+        op: FleetOp,
+        results: Vec<FleetResult>,
+        scroll: usize,
+    },
+    /// Format picker for exporting the Projects list as a health report.
+    ExportReport {
+        selected: usize,
+    },
+    /// Comma-separated tag editor for one project.
+    ProjectTags {
+        path: String,
+        input: String,
+    },
+    /// Path prompt for switching the working directory.
+    ChangeDir {
+        input: String,
+        /// Why the last submitted path was rejected.
+        error: Option<String>,
+    },
+    Prune {
+        /// Candidates reported by `mise prune --dry-run`, each with a selection checkbox.
+        candidates: Vec<PruneCandidate>,
+        /// Currently highlighted candidate.
+        selected: usize,
+        /// "Keep latest N per tool" policy applied to the checkboxes.
+        keep_latest: usize,
+    },
+    /// Bootstrap template picker; row 0 is "no template", row N is `wizard.templates[N - 1]`.
+    TemplatePicker {
+        selected: usize,
+    },
+    TrustManager {
+        /// Every config path in mise's trust store plus known-but-untrusted configs.
+        entries: Vec<TrustEntry>,
+        /// Currently highlighted entry.
+        selected: usize,
+    },
     ScanConfig {
         /// Working copy of scan dirs being edited.
         dirs: Vec<String>,
@@ -110,13 +171,24 @@ pub enum Popup {
         /// Working copy of max_depth being edited.
         max_depth: usize,
     },
+    EditorConflict {
+        /// Dirty config files that were modified on disk since they were loaded.
+        paths: Vec<String>,
+    },
+    EditorDiff {
+        /// Unified diff of every dirty config file against its on-disk contents.
+        diff: String,
+        scroll: usize,
+    },
     Editor {
         config_idx: usize,
         row_idx: usize,
         tab: Tab,
-        field0: String,
-        field1: String,
+        /// Form field values; tools and env use `[name, value]`, tasks use
+        /// the layout of `EditorTaskRow::form_fields`.
+        fields: Vec<String>,
         active_field: usize,
+        /// True when the row was just added; cancelling removes it again.
         is_new: bool,
     },
 }
@@ -124,7 +196,6 @@ pub enum Popup {
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     Uninstall { tool: String, version: String },
-    Prune,
     TrustConfig { path: String },
     RunTask { task: String },
 }
@@ -150,9 +221,15 @@ pub struct App {
     pub env_vars: Vec<EnvVar>,
     pub settings: Vec<MiseSetting>,
     pub projects: Vec<MiseProject>,
+    /// Generation of the latest project scan; results from older scans are dropped.
+    projects_scan: u64,
+    /// Generation of the latest `start_fetch`; results from older fetches are dropped.
+    fetch_gen: u64,
 
     // Cross-reference
     pub outdated_map: HashMap<String, OutdatedTool>,
+    /// Reverse index `tool@version` → projects/configs requiring it (rebuilt on tools/projects load).
+    pub usage_index: HashMap<String, Vec<ToolUsage>>,
 
     // Load states
     pub tools_state: LoadState,
@@ -169,6 +246,8 @@ pub struct App {
     pub tools_selected: usize,
     pub registry_selected: usize,
     pub config_selected: usize,
+    /// First visible line of the Config tab's file preview.
+    pub config_preview_scroll: usize,
     pub doctor_scroll: usize,
     pub sidebar_selected: usize,
     pub outdated_selected: usize,
@@ -178,6 +257,8 @@ pub struct App {
     pub projects_selected: usize,
     pub projects_drill_selected: usize,
     pub projects_drill_active: bool,
+    /// Only show projects in this group; None shows every project.
+    pub projects_group_filter: Option<String>,
 
     // Search
     pub search_active: bool,
@@ -217,8 +298,12 @@ pub struct App {
     // Spinner
     pub spinner_frame: usize,
 
+    /// Directory every fetch runs in; changed with `C` (see [`mise::work_dir`]).
+    pub work_dir: std::path::PathBuf,
+
     // Drift indicator state
-    pub drift_state: DriftState,
+    /// Latest CWD drift check; `state` drives the header indicator.
+    pub drift: DriftReport,
 
     // Bootstrap wizard state (lives on App, rendered as tab content)
     pub wizard: WizardState,
@@ -226,6 +311,15 @@ pub struct App {
     // Editor state (loaded per config file at startup)
     pub editor_states: Vec<EditorState>,
     pub editor_states_loaded: bool,
+    /// Snapshots of `editor_states` taken before each edit, newest last.
+    pub editor_undo: Vec<Vec<EditorState>>,
+    /// Snapshots popped by undo, replayed by redo.
+    pub editor_redo: Vec<Vec<EditorState>>,
+    /// `tool@version` specs in the editor that `mise ls-remote` has no match for.
+    pub editor_remote_missing: HashSet<String>,
+    /// Keys defined by `_.file` dotenv files, keyed by (config path, files);
+    /// `None` while the files are still being read.
+    pub env_dotenv_keys: HashMap<(String, String), Option<Vec<String>>>,
 
     // Action channel for async operations
     pub action_tx: mpsc::UnboundedSender<Action>,
@@ -247,8 +341,11 @@ impl App {
             env_vars: Vec::new(),
             settings: Vec::new(),
             projects: Vec::new(),
+            projects_scan: 0,
+            fetch_gen: 0,
 
             outdated_map: HashMap::new(),
+            usage_index: HashMap::new(),
 
             tools_state: LoadState::Loading,
             registry_state: LoadState::Loading,
@@ -263,6 +360,7 @@ impl App {
             tools_selected: 0,
             registry_selected: 0,
             config_selected: 0,
+            config_preview_scroll: 0,
             doctor_scroll: 0,
             sidebar_selected: 0,
             outdated_selected: 0,
@@ -272,6 +370,7 @@ impl App {
             projects_selected: 0,
             projects_drill_selected: 0,
             projects_drill_active: false,
+            projects_group_filter: None,
 
             search_active: false,
             search_query: String::new(),
@@ -300,92 +399,111 @@ impl App {
             pending_use_global: false,
             status_message: None,
             spinner_frame: 0,
-            drift_state: DriftState::Checking,
+            work_dir: mise::work_dir(),
+            drift: DriftReport::default(),
             wizard: WizardState {
-                target_dir: std::env::current_dir()
-                    .unwrap_or_else(|_| std::path::PathBuf::from("."))
-                    .to_string_lossy()
-                    .to_string(),
+                target_dir: mise::work_dir().to_string_lossy().to_string(),
                 step: WizardStep::Idle,
                 tools: Vec::new(),
+                tasks: Vec::new(),
                 selected: 0,
                 preview_content: String::new(),
                 write_agent_files: false,
                 preview_scroll: 0,
+                packages: Vec::new(),
+                monorepo: false,
+                merge: Vec::new(),
+                outputs: Vec::new(),
+                agent_outputs: Vec::new(),
+                preview_error: None,
+                templates: Vec::new(),
+                template: None,
             },
             editor_states: Vec::new(),
             editor_states_loaded: false,
+            editor_undo: Vec::new(),
+            editor_redo: Vec::new(),
+            editor_remote_missing: HashSet::new(),
+            env_dotenv_keys: HashMap::new(),
             action_tx,
         }
     }
 
-    pub fn start_fetch(&self) {
+    /// Fetch everything that depends on the working directory. Results are tagged
+    /// with a new generation so ones still in flight from an earlier fetch (e.g.
+    /// for the previous directory) are dropped.
+    pub fn start_fetch(&mut self) {
+        self.fetch_gen += 1;
+        let generation = self.fetch_gen;
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(tools) = mise::fetch_tools().await {
-                let _ = tx.send(Action::ToolsLoaded(tools));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::ToolsLoaded(tools))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(registry) = mise::fetch_registry().await {
-                let _ = tx.send(Action::RegistryLoaded(registry));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::RegistryLoaded(registry))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(configs) = mise::fetch_config().await {
-                let _ = tx.send(Action::ConfigLoaded(configs));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::ConfigLoaded(configs))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(lines) = mise::fetch_doctor().await {
-                let _ = tx.send(Action::DoctorLoaded(lines));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::DoctorLoaded(lines))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(outdated) = mise::fetch_outdated().await {
-                let _ = tx.send(Action::OutdatedLoaded(outdated));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::OutdatedLoaded(outdated))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(tasks) = mise::fetch_tasks().await {
-                let _ = tx.send(Action::TasksLoaded(tasks));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::TasksLoaded(tasks))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(env) = mise::fetch_env().await {
-                let _ = tx.send(Action::EnvLoaded(env));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::EnvLoaded(env))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             if let Ok(settings) = mise::fetch_settings().await {
-                let _ = tx.send(Action::SettingsLoaded(settings));
+                let _ = tx.send(Action::Fetched(generation, Box::new(Action::SettingsLoaded(settings))));
             }
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
-            let state = mise::check_cwd_drift().await.unwrap_or(DriftState::NoConfig);
-            let _ = tx.send(Action::DriftChecked(state));
+            let report = mise::check_cwd_drift().await.unwrap_or_else(|_| DriftReport {
+                state: DriftState::NoConfig,
+                ..Default::default()
+            });
+            let _ = tx.send(Action::Fetched(generation, Box::new(Action::DriftChecked(report))));
         });
 
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
             let states = mise::fetch_editor_states().await;
-            let _ = tx.send(Action::EditorStatesLoaded(states));
+            let _ = tx.send(Action::Fetched(generation, Box::new(Action::EditorStatesLoaded(states))));
         });
 
         // Projects are scanned in the ToolsLoaded handler once the tools list is populated.
@@ -393,26 +511,52 @@ impl App {
 
     pub fn handle_action(&mut self, action: Action) {
         // Editor popup intercept — when popup editor is open, block all other actions
-        if let Some(Popup::Editor { ref mut field0, ref mut field1, ref mut active_field, .. }) = self.popup {
+        if let Some(Popup::Editor { ref mut fields, ref mut active_field, tab, .. }) = self.popup {
             match &action {
                 Action::EditorInput(c) => {
-                    if *active_field == 0 {
-                        field0.push(*c);
-                    } else {
-                        field1.push(*c);
+                    if let Some(field) = fields.get_mut(*active_field) {
+                        field.push(*c);
+                    }
+                    // Typing a directive key (`_.python.venv`) switches the env form
+                    if tab == Tab::Environment && *active_field == 0 {
+                        let len = EnvRowKind::from_key(&fields[0]).form_labels().len();
+                        fields.resize(len, String::new());
+                    }
+                    return;
+                }
+                Action::EditorNewline => {
+                    // Task Run, Depends, Env and Sources, tool Options and directive
+                    // lists are multi-line
+                    let multi_line = match tab {
+                        Tab::Tasks => matches!(*active_field, 1 | 3 | 5 | 6),
+                        Tab::Tools => *active_field == 2,
+                        Tab::Environment => {
+                            *active_field == 1 && EnvRowKind::from_key(&fields[0]).is_list()
+                        }
+                        _ => false,
+                    };
+                    if multi_line {
+                        fields[*active_field].push('\n');
                     }
                     return;
                 }
                 Action::EditorBackspace => {
-                    if *active_field == 0 {
-                        field0.pop();
-                    } else {
-                        field1.pop();
+                    if let Some(field) = fields.get_mut(*active_field) {
+                        field.pop();
+                    }
+                    if tab == Tab::Environment && *active_field == 0 {
+                        let len = EnvRowKind::from_key(&fields[0]).form_labels().len();
+                        fields.resize(len, String::new());
                     }
                     return;
                 }
                 Action::EditorNextField => {
-                    *active_field = if *active_field == 0 { 1 } else { 0 };
+                    *active_field = (*active_field + 1) % fields.len().max(1);
+                    return;
+                }
+                Action::EditorPrevField => {
+                    let len = fields.len().max(1);
+                    *active_field = (*active_field + len - 1) % len;
                     return;
                 }
                 Action::EditorConfirmEdit => {
@@ -420,7 +564,14 @@ impl App {
                     return;
                 }
                 Action::EditorCancelEdit => {
+                    let cancelled_new = matches!(self.popup, Some(Popup::Editor { is_new: true, .. }));
                     self.popup = None;
+                    // Discard the placeholder row created by add
+                    if cancelled_new {
+                        if let Some(snapshot) = self.editor_undo.pop() {
+                            self.editor_states = snapshot;
+                        }
+                    }
                     return;
                 }
                 _ => return, // block everything else while popup editor is open
@@ -510,7 +661,7 @@ impl App {
                 }
                 Action::MoveDown => {
                     if wizard.step == WizardStep::Review {
-                        if wizard.selected + 1 < wizard.tools.len() {
+                        if wizard.selected + 1 < wizard.tools.len() + wizard.tasks.len() {
                             wizard.selected += 1;
                         }
                     } else if wizard.step == WizardStep::Preview {
@@ -520,8 +671,11 @@ impl App {
                 }
                 Action::WizardToggleTool => {
                     if wizard.step == WizardStep::Review {
+                        let tool_count = wizard.tools.len();
                         if let Some(tool) = wizard.tools.get_mut(wizard.selected) {
                             tool.enabled = !tool.enabled;
+                        } else if let Some(task) = wizard.tasks.get_mut(wizard.selected - tool_count) {
+                            task.enabled = !task.enabled;
                         }
                     }
                     return;
@@ -532,6 +686,15 @@ impl App {
                     }
                     return;
                 }
+                Action::WizardToggleMonorepo => {
+                    if wizard.step == WizardStep::Review && wizard.packages.len() > 1 {
+                        wizard.monorepo = !wizard.monorepo;
+                        wizard.tools = wizard_proposal(wizard);
+                        wizard.tasks = wizard_tasks(&wizard.packages, wizard.monorepo);
+                        wizard.selected = 0;
+                    }
+                    return;
+                }
                 Action::WizardNextStep | Action::Confirm => {
                     match wizard.step {
                         WizardStep::Idle => {} // handled below in main match
@@ -539,35 +702,45 @@ impl App {
                             // do nothing — waiting for WizardDetected
                         }
                         WizardStep::Review => {
-                            let preview = generate_mise_toml_preview(&wizard.tools);
-                            wizard.preview_content = preview;
+                            build_wizard_preview(wizard);
                             wizard.step = WizardStep::Preview;
                             wizard.preview_scroll = 0;
                         }
                         WizardStep::Preview => {
+                            if let Some(e) = &wizard.preview_error {
+                                self.status_message = Some((e.clone(), 30));
+                                return;
+                            }
                             let target = wizard.target_dir.clone();
-                            let tools: Vec<_> = wizard.tools.iter()
-                                .filter(|t| t.enabled)
-                                .map(|t| (t.name.clone(), t.version.clone()))
-                                .collect();
-                            let agent_files = wizard.write_agent_files;
+                            let outputs = wizard.outputs.clone();
+                            let agent_outputs = wizard.agent_outputs.clone();
                             wizard.step = WizardStep::Writing;
                             let tx = self.action_tx.clone();
                             tokio::spawn(async move {
-                                match mise::write_mise_toml(&target, &tools).await {
-                                    Ok(()) => {}
-                                    Err(e) => {
+                                for (path, content) in &outputs {
+                                    if let Err(e) = mise::write_config_file(path, content).await {
                                         let _ = tx.send(Action::OperationFailed(e));
                                         return;
                                     }
                                 }
-                                if agent_files {
-                                    let _ = mise::write_agent_files_for(&target).await;
+                                for (path, content) in &agent_outputs {
+                                    if let Err(e) = mise::write_config_file(path, content).await {
+                                        let _ = tx.send(Action::OperationFailed(e));
+                                        return;
+                                    }
                                 }
-                                match mise::install_project_tools(&target).await {
-                                    Ok(msg) => { let _ = tx.send(Action::WizardCompleted(msg)); }
-                                    Err(e)  => { let _ = tx.send(Action::OperationFailed(e)); }
+                                for (path, _) in &outputs {
+                                    let dir = path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+                                    if let Err(e) = mise::install_project_tools(&dir).await {
+                                        let _ = tx.send(Action::OperationFailed(e));
+                                        return;
+                                    }
                                 }
+                                let msg = match outputs.len() {
+                                    1 => format!("Installed tools in {target}"),
+                                    n => format!("Wrote {n} configs and installed tools in {target}"),
+                                };
+                                let _ = tx.send(Action::WizardCompleted(msg));
                             });
                         }
                         WizardStep::Writing => {}
@@ -575,17 +748,16 @@ impl App {
                     return;
                 }
                 Action::WizardPrevStep => {
-                    match wizard.step {
-                        WizardStep::Preview => {
-                            wizard.step = WizardStep::Review;
-                        }
-                        _ => {}
+                    if wizard.step == WizardStep::Preview {
+                        wizard.step = WizardStep::Review;
                     }
                     return;
                 }
                 Action::CancelPopup => {
                     self.wizard.step = WizardStep::Idle;
                     self.wizard.tools.clear();
+                    self.wizard.packages.clear();
+                    self.wizard.tasks.clear();
                     self.wizard.selected = 0;
                     self.wizard.preview_content.clear();
                     self.wizard.preview_scroll = 0;
@@ -608,6 +780,15 @@ impl App {
             }
             Action::MoveUp => self.move_selection(-1),
             Action::MoveDown => self.move_selection(1),
+            // On the Config tab, paging scrolls the file preview
+            Action::PageUp | Action::PageDown if self.tab == Tab::Config && self.popup.is_none() => {
+                let delta = if matches!(action, Action::PageUp) { -10 } else { 10 };
+                let total = self
+                    .visible_configs()
+                    .get(self.config_selected)
+                    .map_or(0, |c| c.contents.lines().count());
+                Self::adjust_scroll(&mut self.config_preview_scroll, delta, total);
+            }
             Action::PageUp => self.move_selection(-10),
             Action::PageDown => self.move_selection(10),
             Action::NextTab => self.cycle_tab(1),
@@ -643,16 +824,11 @@ impl App {
             Action::ToolsLoaded(tools) => {
                 self.tools = tools;
                 self.tools_state = LoadState::Loaded;
+                self.usage_index = mise::build_usage_index(&self.tools, &self.projects);
+                self.refresh_config_locks();
                 self.update_filtered_tools();
                 // Re-scan projects now that we have a populated tools list.
-                // scan_projects at startup used an empty snapshot; this corrects it.
-                let tx = self.action_tx.clone();
-                let tools_snapshot = self.tools.clone();
-                tokio::spawn(async move {
-                    let config = crate::config::MisetuiConfig::load();
-                    let projects = crate::mise::scan_projects(&config, &tools_snapshot);
-                    let _ = tx.send(Action::ProjectsLoaded(projects));
-                });
+                self.start_project_scan();
             }
             Action::RegistryLoaded(registry) => {
                 self.registry = registry;
@@ -662,6 +838,7 @@ impl App {
             Action::ConfigLoaded(configs) => {
                 self.configs = configs;
                 self.config_state = LoadState::Loaded;
+                self.refresh_config_locks();
                 self.update_filtered_configs();
             }
             Action::DoctorLoaded(lines) => {
@@ -686,42 +863,36 @@ impl App {
             Action::EnvLoaded(env) => {
                 self.env_vars = env;
                 self.env_state = LoadState::Loaded;
-                self.update_filtered_env();
+                self.sync_env_directives();
             }
             Action::SettingsLoaded(settings) => {
                 self.settings = settings;
                 self.settings_state = LoadState::Loaded;
                 self.update_filtered_settings();
             }
-            Action::PruneLoaded(candidates) => {
+            Action::PruneLoaded(mut candidates) => {
                 if candidates.is_empty() {
                     self.popup = None;
                     self.status_message =
                         Some(("No unused tool versions to prune".to_string(), 20));
                 } else {
-                    let count = candidates.len();
-                    let names: Vec<String> = candidates
-                        .iter()
-                        .take(5)
-                        .map(|c| {
-                            if c.version.is_empty() {
-                                c.tool.clone()
-                            } else {
-                                format!("{}@{}", c.tool, c.version)
-                            }
-                        })
-                        .collect();
-                    let mut msg = format!("Prune {count} versions? ({}", names.join(", "));
-                    if count > 5 {
-                        msg.push_str(", ...");
-                    }
-                    msg.push(')');
-                    self.popup = Some(Popup::Confirm {
-                        message: msg,
-                        action_on_confirm: ConfirmAction::Prune,
+                    let keep_latest = crate::config::MisetuiConfig::load().prune_keep_latest;
+                    apply_prune_policy(&mut candidates, keep_latest);
+                    self.popup = Some(Popup::Prune {
+                        candidates,
+                        selected: 0,
+                        keep_latest,
                     });
                 }
             }
+            Action::TrustEntriesLoaded(entries) => {
+                if entries.is_empty() {
+                    self.popup = None;
+                    self.status_message = Some(("No config files known to mise".to_string(), 20));
+                } else {
+                    self.popup = Some(Popup::TrustManager { entries, selected: 0 });
+                }
+            }
             Action::ToolInfoLoaded(info) => {
                 if let Some(Popup::Progress { message }) = &self.popup {
                     let tool_name = message
@@ -737,16 +908,52 @@ impl App {
                     } else {
                         info
                     };
+                    let mut versions: Vec<&str> = self
+                        .tools
+                        .iter()
+                        .filter(|t| t.name == tool_name && t.installed)
+                        .map(|t| t.version.as_str())
+                        .collect();
+                    versions.sort_by(|a, b| mise::compare_versions(b, a));
+                    versions.dedup();
+                    let used_by = versions
+                        .into_iter()
+                        .map(|v| (v.to_string(), self.tool_usage(&tool_name, v).to_vec()))
+                        .collect();
                     self.popup = Some(Popup::ToolDetail {
                         tool_name,
                         info: pretty,
+                        used_by,
                         scroll: 0,
                     });
                 }
             }
-            Action::ProjectsLoaded(projects) => {
+            Action::Fetched(generation, action) => {
+                if generation == self.fetch_gen {
+                    self.handle_action(*action);
+                }
+            }
+            Action::ProjectScanned(scan, project) => {
+                if scan != self.projects_scan {
+                    return;
+                }
+                // Update in place so a rescan doesn't reshuffle the list under the cursor
+                match self.projects.iter().position(|p| p.path == project.path) {
+                    Some(i) => self.projects[i] = project,
+                    None => {
+                        let at = self.projects.partition_point(|p| p.name <= project.name);
+                        self.projects.insert(at, project);
+                    }
+                }
+                self.update_filtered_projects();
+            }
+            Action::ProjectsLoaded(scan, projects) => {
+                if scan != self.projects_scan {
+                    return;
+                }
                 self.projects = projects;
                 self.projects_state = LoadState::Loaded;
+                self.usage_index = mise::build_usage_index(&self.tools, &self.projects);
                 self.update_filtered_projects();
             }
             Action::VersionsLoaded(versions) => {
@@ -831,6 +1038,9 @@ impl App {
                             }
                         });
                     }
+                } else if self.tab == Tab::Projects {
+                    // U on Projects tab upgrades pins across the filtered projects
+                    self.handle_action(Action::OpenFleetPlan(FleetOp::UpgradePins));
                 } else if self.tab == Tab::Outdated {
                     // U on Outdated tab upgrades all
                     self.handle_action(Action::UpgradeAll);
@@ -846,13 +1056,24 @@ impl App {
                     if let Some(tool) = tools.get(self.tools_selected) {
                         let name = tool.name.clone();
                         let version = tool.version.clone();
-                        self.popup = Some(Popup::Confirm {
-                            message: format!("Uninstall {name}@{version}?"),
-                            action_on_confirm: ConfirmAction::Uninstall {
+                        let impacted =
+                            mise::uninstall_impact(&name, &version, &self.tools, &self.projects);
+                        if impacted.is_empty() {
+                            self.popup = Some(Popup::Confirm {
+                                message: format!("Uninstall {name}@{version}? (unused)"),
+                                action_on_confirm: ConfirmAction::Uninstall {
+                                    tool: name,
+                                    version,
+                                },
+                            });
+                        } else {
+                            self.popup = Some(Popup::UninstallGuard {
                                 tool: name,
                                 version,
-                            },
-                        });
+                                impacted,
+                                typed: String::new(),
+                            });
+                        }
                     }
                 }
             }
@@ -956,8 +1177,9 @@ impl App {
                     message: "Checking for unused versions...".to_string(),
                 });
                 let tx = self.action_tx.clone();
+                let projects = self.projects.clone();
                 tokio::spawn(async move {
-                    match mise::prune_dry_run().await {
+                    match mise::prune_dry_run(&projects).await {
                         Ok(candidates) => {
                             let _ = tx.send(Action::PruneLoaded(candidates));
                         }
@@ -968,10 +1190,106 @@ impl App {
                 });
             }
 
+            Action::PruneToggle => {
+                if let Some(Popup::Prune { candidates, selected, .. }) = &mut self.popup {
+                    if let Some(c) = candidates.get_mut(*selected) {
+                        c.selected = !c.selected;
+                    }
+                }
+            }
+            Action::PruneToggleAll => {
+                if let Some(Popup::Prune { candidates, .. }) = &mut self.popup {
+                    let select = !candidates.iter().all(|c| c.selected);
+                    for c in candidates.iter_mut() {
+                        c.selected = select;
+                    }
+                }
+            }
+            Action::PruneKeepMore | Action::PruneKeepLess => {
+                if let Some(Popup::Prune { candidates, keep_latest, .. }) = &mut self.popup {
+                    if matches!(action, Action::PruneKeepMore) {
+                        *keep_latest += 1;
+                    } else if *keep_latest > 0 {
+                        *keep_latest -= 1;
+                    }
+                    apply_prune_policy(candidates, *keep_latest);
+                }
+            }
+
+            Action::OpenTrustManager => {
+                if self.popup.is_some() {
+                    return;
+                }
+                self.popup = Some(Popup::Progress {
+                    message: "Reading trust store...".to_string(),
+                });
+                let mut known: Vec<String> = self.configs.iter().map(|c| c.path.clone()).collect();
+                known.extend(self.projects.iter().map(|p| p.config_path.clone()));
+                let tx = self.action_tx.clone();
+                tokio::spawn(async move {
+                    match mise::fetch_trust_entries(known).await {
+                        Ok(entries) => {
+                            let _ = tx.send(Action::TrustEntriesLoaded(entries));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(Action::OperationFailed(e));
+                        }
+                    }
+                });
+            }
+            Action::TrustToggle => {
+                if let Some(Popup::TrustManager { entries, selected }) = &mut self.popup {
+                    if let Some(e) = entries.get_mut(*selected) {
+                        e.selected = !e.selected;
+                    }
+                }
+            }
+            Action::TrustToggleAll => {
+                if let Some(Popup::TrustManager { entries, .. }) = &mut self.popup {
+                    let select = !entries.iter().all(|e| e.selected);
+                    for e in entries.iter_mut() {
+                        e.selected = select;
+                    }
+                }
+            }
+            Action::TrustApply(op) => {
+                let Some(Popup::TrustManager { entries, selected }) = &self.popup else {
+                    return;
+                };
+                // Act on checked entries, or the highlighted one when nothing is checked.
+                let mut targets: Vec<TrustEntry> =
+                    entries.iter().filter(|e| e.selected).cloned().collect();
+                if targets.is_empty() {
+                    targets.extend(entries.get(*selected).cloned());
+                }
+                if targets.is_empty() {
+                    return;
+                }
+                self.popup = Some(Popup::Progress {
+                    message: format!("Updating trust for {} config(s)...", targets.len()),
+                });
+                let tx = self.action_tx.clone();
+                tokio::spawn(async move {
+                    match mise::apply_trust_op(targets, op).await {
+                        Ok(msg) => {
+                            let _ = tx.send(Action::OperationComplete(msg));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(Action::OperationFailed(e));
+                        }
+                    }
+                });
+            }
+
             Action::TrustConfig => {
                 if self.popup.is_some() {
                     return;
                 }
+                // `t` on the idle Bootstrap tab picks a template instead
+                if self.tab == Tab::Bootstrap && self.wizard.step == WizardStep::Idle {
+                    self.open_template_picker();
+                    return;
+                }
                 if self.tab == Tab::Config {
                     let configs = self.visible_configs_vec();
                     if let Some(cfg) = configs.get(self.config_selected) {
@@ -1038,6 +1356,148 @@ impl App {
                 });
             }
 
+            Action::RegenerateLock => {
+                if self.popup.is_some() {
+                    return;
+                }
+                // Projects: the selected project; Config: the selected file's directory
+                let path = match self.tab {
+                    Tab::Projects => self
+                        .filtered_projects
+                        .get(self.projects_selected)
+                        .map(|&idx| self.projects[idx].path.clone()),
+                    Tab::Config => self
+                        .visible_configs_vec()
+                        .get(self.config_selected)
+                        .and_then(|cfg| std::path::Path::new(&cfg.path).parent())
+                        .map(|dir| dir.to_string_lossy().to_string()),
+                    _ => None,
+                };
+                let Some(path) = path else {
+                    return;
+                };
+                self.popup = Some(Popup::Progress {
+                    message: format!("Regenerating lockfile in {path}..."),
+                });
+                let tx = self.action_tx.clone();
+                tokio::spawn(async move {
+                    match mise::regenerate_lock(&path).await {
+                        Ok(msg) => { let _ = tx.send(Action::OperationComplete(msg)); }
+                        Err(e)  => { let _ = tx.send(Action::OperationFailed(e)); }
+                    }
+                });
+            }
+
+            Action::OpenFleetPlan(op) => {
+                if self.tab != Tab::Projects || self.popup.is_some() {
+                    return;
+                }
+                let projects: Vec<&MiseProject> = self
+                    .filtered_projects
+                    .iter()
+                    .filter_map(|&idx| self.projects.get(idx))
+                    .collect();
+                if projects.is_empty() {
+                    return;
+                }
+                match op {
+                    FleetOp::InstallMissing => {
+                        let plan = mise::plan_fleet_install(&projects);
+                        self.popup = Some(Popup::FleetPlan { plan, scroll: 0 });
+                    }
+                    FleetOp::UpgradePins => {
+                        let targets: Vec<(String, String)> = projects
+                            .iter()
+                            .map(|p| (p.name.clone(), p.path.clone()))
+                            .collect();
+                        self.popup = Some(Popup::Progress {
+                            message: format!("Planning upgrades for {} project(s)...", targets.len()),
+                        });
+                        let tx = self.action_tx.clone();
+                        tokio::spawn(async move {
+                            let plan = mise::plan_fleet_upgrade(targets).await;
+                            let _ = tx.send(Action::FleetPlanned(plan));
+                        });
+                    }
+                }
+            }
+            Action::FleetPlanned(plan) => {
+                if matches!(self.popup, Some(Popup::Progress { .. })) {
+                    self.popup = Some(Popup::FleetPlan { plan, scroll: 0 });
+                }
+            }
+            Action::FleetDone(op, results) => {
+                let failed = results.iter().filter(|r| !r.ok).count();
+                self.status_message = Some((
+                    format!("{}: {} ok, {failed} failed", op.label(), results.len() - failed),
+                    20,
+                ));
+                self.popup = Some(Popup::FleetResults { op, results, scroll: 0 });
+                // Refresh data so project health reflects the new installs
+                self.start_fetch();
+            }
+
+            Action::ToggleFavorite => {
+                if self.tab != Tab::Projects || self.popup.is_some() {
+                    return;
+                }
+                if let Some(path) = self.selected_project_path() {
+                    self.update_project_config(&path, |config, path| {
+                        if config.toggle_favorite(path) {
+                            format!("Pinned {}", path.display())
+                        } else {
+                            format!("Unpinned {}", path.display())
+                        }
+                    });
+                }
+            }
+            Action::CycleProjectGroup => {
+                if self.tab != Tab::Projects || self.popup.is_some() {
+                    return;
+                }
+                let groups: std::collections::BTreeSet<&str> =
+                    self.projects.iter().filter_map(|p| p.group.as_deref()).collect();
+                // All → each group in name order → All
+                let next = match &self.projects_group_filter {
+                    None => groups.first().copied(),
+                    Some(current) => groups.range::<str, _>((
+                        std::ops::Bound::Excluded(current.as_str()),
+                        std::ops::Bound::Unbounded,
+                    ))
+                    .next()
+                    .copied(),
+                };
+                if next.is_none() && groups.is_empty() {
+                    self.status_message =
+                        Some(("No project groups — add [groups] to config.toml".to_string(), 20));
+                }
+                self.projects_group_filter = next.map(str::to_string);
+                self.projects_selected = 0;
+                self.update_filtered_projects();
+            }
+            Action::OpenExportReport => {
+                if self.tab != Tab::Projects || self.popup.is_some() {
+                    return;
+                }
+                if self.filtered_projects.is_empty() {
+                    self.status_message = Some(("No projects to export".to_string(), 15));
+                    return;
+                }
+                self.popup = Some(Popup::ExportReport { selected: 0 });
+            }
+            Action::OpenProjectTags => {
+                if self.tab != Tab::Projects || self.popup.is_some() {
+                    return;
+                }
+                if let Some(&idx) = self.filtered_projects.get(self.projects_selected) {
+                    let project = &self.projects[idx];
+                    self.popup = Some(Popup::ProjectTags {
+                        path: project.path.clone(),
+                        input: project.tags.join(", "),
+                    });
+                }
+            }
+
             Action::OpenScanConfig => {
                 if self.tab != Tab::Projects || self.popup.is_some() {
                     return;
@@ -1055,9 +1515,12 @@ impl App {
                 });
             }
 
-            Action::WizardDetected(tools) => {
+            Action::WizardDetected(packages) => {
                 if self.tab == Tab::Bootstrap {
-                    self.wizard.tools = tools;
+                    self.wizard.monorepo = packages.len() > 1;
+                    self.wizard.tasks = wizard_tasks(&packages, self.wizard.monorepo);
+                    self.wizard.packages = packages;
+                    self.wizard.tools = wizard_proposal(&self.wizard);
                     self.wizard.step = WizardStep::Review;
                     self.wizard.selected = 0;
                 }
@@ -1065,6 +1528,8 @@ impl App {
             Action::WizardCompleted(msg) => {
                 self.wizard.step = WizardStep::Idle;
                 self.wizard.tools.clear();
+                self.wizard.packages.clear();
+                self.wizard.tasks.clear();
                 self.wizard.selected = 0;
                 self.wizard.preview_content.clear();
                 self.wizard.preview_scroll = 0;
@@ -1074,16 +1539,26 @@ impl App {
             // These are handled by the intercept block; arms here prevent non-exhaustive match errors
             Action::WizardToggleTool
             | Action::WizardToggleAgentFiles
+            | Action::WizardToggleMonorepo
             | Action::WizardNextStep
             | Action::WizardPrevStep => {}
 
             Action::EditorStatesLoaded(states) => {
                 self.editor_states = states;
                 self.editor_states_loaded = true;
+                self.editor_undo.clear();
+                self.editor_redo.clear();
+                // Dotenv files may have changed since they were last read
+                self.env_dotenv_keys.clear();
+                self.sync_env_directives();
+            }
+            Action::DotenvKeysLoaded(config_path, files, keys) => {
+                self.env_dotenv_keys.insert((config_path, files), Some(keys));
+                self.sync_env_directives();
             }
             // Editor popup actions handled by intercept block above or methods below
             Action::EditorConfirmEdit | Action::EditorCancelEdit
-            | Action::EditorNextField
+            | Action::EditorNextField | Action::EditorPrevField | Action::EditorNewline
             | Action::EditorInput(_) | Action::EditorBackspace => {}
             Action::EditorAddRow => {
                 self.handle_editor_add_row();
@@ -1094,6 +1569,31 @@ impl App {
             Action::EditorWrite => {
                 self.handle_editor_write();
             }
+            Action::EditorRemoteChecked { tool, versions, missing } => {
+                for v in &versions {
+                    self.editor_remote_missing.remove(&format!("{tool}@{v}"));
+                }
+                if !missing.is_empty() {
+                    self.status_message = Some((
+                        format!("Warning: no remote {tool} version matches {}", missing.join(", ")),
+                        30,
+                    ));
+                }
+                self.editor_remote_missing
+                    .extend(missing.iter().map(|v| format!("{tool}@{v}")));
+            }
+            Action::EditorMergeExternal => self.resolve_editor_conflict(true),
+            Action::EditorReloadExternal => self.resolve_editor_conflict(false),
+            Action::EditorUndo => {
+                if self.popup.is_none() {
+                    self.step_editor_history(true);
+                }
+            }
+            Action::EditorRedo => {
+                if self.popup.is_none() {
+                    self.step_editor_history(false);
+                }
+            }
             Action::EditorWriteComplete(msg) => {
                 self.popup = None;
                 self.status_message = Some((msg, 20));
@@ -1105,24 +1605,22 @@ impl App {
                 if let Some(Popup::ScanConfig { dirs, max_depth, .. }) = &self.popup {
                     let dirs_clone: Vec<_> = dirs.iter()
                         .filter(|d| !d.trim().is_empty())
-                        .map(|d| std::path::PathBuf::from(d))
+                        .map(std::path::PathBuf::from)
                         .collect();
                     let max_depth = *max_depth;
-                    let config = crate::config::MisetuiConfig {
-                        scan_dirs: dirs_clone,
-                        max_depth,
-                    };
-                    match config.save() {
+                    let saved = crate::config::MisetuiConfig::try_load().and_then(|loaded| {
+                        crate::config::MisetuiConfig {
+                            scan_dirs: dirs_clone,
+                            max_depth,
+                            ..loaded
+                        }
+                        .save()
+                    });
+                    match saved {
                         Ok(()) => {
                             self.popup = None;
                             // Rescan with updated config
-                            let tx = self.action_tx.clone();
-                            let tools_snapshot = self.tools.clone();
-                            tokio::spawn(async move {
-                                let cfg = crate::config::MisetuiConfig::load();
-                                let projects = crate::mise::scan_projects(&cfg, &tools_snapshot);
-                                let _ = tx.send(Action::ProjectsLoaded(projects));
-                            });
+                            self.start_project_scan();
                         }
                         Err(e) => {
                             self.popup = Some(Popup::Progress {
@@ -1143,12 +1641,13 @@ impl App {
                     self.sort_ascending = true;
                     // Advance column
                     let max_col = match self.tab {
-                        Tab::Tools => 3,
+                        Tab::Tools => 4,
                         Tab::Registry => 1,
                         Tab::Outdated => 3,
                         Tab::Tasks => 2,
                         Tab::Environment => 3,
                         Tab::Settings => 2,
+                        Tab::Projects => 8,
                         _ => 0,
                     };
                     if max_col > 0 {
@@ -1183,13 +1682,22 @@ impl App {
                         self.sort_column = 0;
                         self.sort_ascending = true;
                     }
+                } else if y < 3 && crate::ui::header::drift_indicator_columns(self).contains(&x) {
+                    self.handle_action(Action::OpenDriftBreakdown);
                 } else {
                     self.focus = Focus::Content;
                 }
             }
 
             Action::PopupSearchInput(c) => {
-                if let Some(Popup::VersionPicker {
+                if let Some(Popup::UninstallGuard { typed, .. }) = &mut self.popup {
+                    typed.push(c);
+                } else if let Some(Popup::ChangeDir { input, error }) = &mut self.popup {
+                    input.push(c);
+                    *error = None;
+                } else if let Some(Popup::ProjectTags { input, .. }) = &mut self.popup {
+                    input.push(c);
+                } else if let Some(Popup::VersionPicker {
                     ref mut search_query,
                     ref mut filtered_versions,
                     ref mut selected,
@@ -1205,7 +1713,14 @@ impl App {
             }
 
             Action::PopupSearchBackspace => {
-                if let Some(Popup::VersionPicker {
+                if let Some(Popup::UninstallGuard { typed, .. }) = &mut self.popup {
+                    typed.pop();
+                } else if let Some(Popup::ChangeDir { input, error }) = &mut self.popup {
+                    input.pop();
+                    *error = None;
+                } else if let Some(Popup::ProjectTags { input, .. }) = &mut self.popup {
+                    input.pop();
+                } else if let Some(Popup::VersionPicker {
                     ref mut search_query,
                     ref mut filtered_versions,
                     ref mut selected,
@@ -1279,12 +1794,15 @@ impl App {
                             action_on_confirm, ..
                         } => match action_on_confirm {
                             ConfirmAction::Uninstall { tool, version } => {
+                                self.spawn_uninstall(tool, version);
+                            }
+                            ConfirmAction::TrustConfig { path } => {
                                 self.popup = Some(Popup::Progress {
-                                    message: format!("Uninstalling {tool}@{version}..."),
+                                    message: format!("Trusting {path}..."),
                                 });
                                 let tx = self.action_tx.clone();
                                 tokio::spawn(async move {
-                                    match mise::uninstall_tool(&tool, &version).await {
+                                    match mise::trust_config(&path).await {
                                         Ok(msg) => {
                                             let _ = tx.send(Action::OperationComplete(msg));
                                         }
@@ -1294,13 +1812,13 @@ impl App {
                                     }
                                 });
                             }
-                            ConfirmAction::Prune => {
+                            ConfirmAction::RunTask { task } => {
                                 self.popup = Some(Popup::Progress {
-                                    message: "Pruning unused versions...".to_string(),
+                                    message: format!("Running task '{task}'..."),
                                 });
                                 let tx = self.action_tx.clone();
                                 tokio::spawn(async move {
-                                    match mise::prune().await {
+                                    match mise::run_task(&task).await {
                                         Ok(msg) => {
                                             let _ = tx.send(Action::OperationComplete(msg));
                                         }
@@ -1310,13 +1828,24 @@ impl App {
                                     }
                                 });
                             }
-                            ConfirmAction::TrustConfig { path } => {
+                        },
+                        Popup::Prune { candidates, keep_latest, selected } => {
+                            let specs: Vec<String> = candidates
+                                .iter()
+                                .filter(|c| c.selected)
+                                .map(|c| c.spec())
+                                .collect();
+                            if specs.is_empty() {
+                                self.popup = Some(Popup::Prune { candidates, keep_latest, selected });
+                                self.status_message =
+                                    Some(("No versions selected for pruning".to_string(), 15));
+                            } else {
                                 self.popup = Some(Popup::Progress {
-                                    message: format!("Trusting {path}..."),
+                                    message: format!("Uninstalling {} version(s)...", specs.len()),
                                 });
                                 let tx = self.action_tx.clone();
                                 tokio::spawn(async move {
-                                    match mise::trust_config(&path).await {
+                                    match mise::uninstall_versions(&specs).await {
                                         Ok(msg) => {
                                             let _ = tx.send(Action::OperationComplete(msg));
                                         }
@@ -1326,30 +1855,102 @@ impl App {
                                     }
                                 });
                             }
-                            ConfirmAction::RunTask { task } => {
+                        }
+                        Popup::UninstallGuard { tool, version, impacted, typed } => {
+                            if typed.trim() == tool {
+                                self.spawn_uninstall(tool, version);
+                            } else {
+                                self.status_message =
+                                    Some((format!("Type '{tool}' to confirm the uninstall"), 15));
+                                self.popup = Some(Popup::UninstallGuard { tool, version, impacted, typed });
+                            }
+                        }
+                        Popup::Help => {}
+                        Popup::DriftBreakdown => {}
+                        Popup::FleetResults { .. } => {}
+                        Popup::FleetPlan { plan, .. } => {
+                            let runnable = plan.targets.iter().filter(|t| t.error.is_none()).count();
+                            if runnable == 0 {
+                                self.status_message =
+                                    Some(("Nothing to do in the filtered projects".to_string(), 20));
+                            } else {
                                 self.popup = Some(Popup::Progress {
-                                    message: format!("Running task '{task}'..."),
+                                    message: format!(
+                                        "{} in {runnable} project(s)...",
+                                        plan.op.label()
+                                    ),
                                 });
                                 let tx = self.action_tx.clone();
                                 tokio::spawn(async move {
-                                    match mise::run_task(&task).await {
-                                        Ok(msg) => {
-                                            let _ = tx.send(Action::OperationComplete(msg));
-                                        }
-                                        Err(e) => {
-                                            let _ = tx.send(Action::OperationFailed(e));
-                                        }
-                                    }
+                                    let op = plan.op;
+                                    let results = mise::run_fleet(plan).await;
+                                    let _ = tx.send(Action::FleetDone(op, results));
                                 });
                             }
+                        }
+                        Popup::ChangeDir { input, .. } => match resolve_dir(&input) {
+                            Ok(dir) => self.change_dir(dir),
+                            Err(e) => {
+                                self.popup = Some(Popup::ChangeDir { input, error: Some(e) });
+                            }
                         },
-                        Popup::Help => {}
+                        Popup::ExportReport { selected } => {
+                            let format = ReportFormat::ALL[selected];
+                            let projects = self.visible_projects();
+                            let written = report::reports_dir()
+                                .ok_or_else(|| "Could not determine data directory".to_string())
+                                .and_then(|dir| report::write(&projects, format, &dir));
+                            self.status_message = Some(
+                                match written {
+                                    Ok(path) => (
+                                        format!(
+                                            "Exported {} project(s) to {}",
+                                            projects.len(),
+                                            path.display()
+                                        ),
+                                        30,
+                                    ),
+                                    Err(e) => (format!("Error: {e}"), 30),
+                                },
+                            );
+                        }
+                        Popup::ProjectTags { path, input } => {
+                            let tags: Vec<String> = input
+                                .split(',')
+                                .map(|t| t.trim().trim_start_matches('#').to_string())
+                                .filter(|t| !t.is_empty())
+                                .collect();
+                            self.update_project_config(&path, |config, path| {
+                                config.set_tags(path, tags.clone());
+                                format!("Tags saved for {}", path.display())
+                            });
+                        }
                         Popup::ToolDetail { .. } => {}
                         Popup::Progress { .. } => {
                             self.popup = Some(Popup::Progress {
                                 message: "Working...".to_string(),
                             });
                         }
+                        Popup::EditorDiff { .. } => {
+                            self.spawn_editor_write();
+                        }
+                        popup @ Popup::EditorConflict { .. } => {
+                            // Requires an explicit choice: m (merge) or r (reload)
+                            self.popup = Some(popup);
+                        }
+                        popup @ Popup::TrustManager { .. } => {
+                            // Trust operations use t/u/i; Enter keeps the popup open
+                            self.popup = Some(popup);
+                        }
+                        Popup::TemplatePicker { selected } => {
+                            self.wizard.template =
+                                selected.checked_sub(1).and_then(|i| self.wizard.templates.get(i).cloned());
+                            let msg = match &self.wizard.template {
+                                Some(t) => format!("Template '{}' will be applied", t.name),
+                                None => "No template".to_string(),
+                            };
+                            self.status_message = Some((msg, 15));
+                        }
                         Popup::ScanConfig { .. } => {
                             // ScanConfig confirm is handled by the intercept block above
                         }
@@ -1409,6 +2010,8 @@ impl App {
                 if self.wizard.step == WizardStep::Writing {
                     self.wizard.step = WizardStep::Idle;
                     self.wizard.tools.clear();
+                    self.wizard.packages.clear();
+                    self.wizard.tasks.clear();
                     self.wizard.selected = 0;
                     self.wizard.preview_content.clear();
                     self.wizard.preview_scroll = 0;
@@ -1431,17 +2034,97 @@ impl App {
                 }
             }
 
-            Action::DriftChecked(state) => {
-                self.drift_state = state;
+            Action::DriftChecked(report) => {
+                self.drift = report;
             }
             Action::CheckDrift => {
-                self.drift_state = DriftState::Checking;
+                self.drift.state = DriftState::Checking;
                 let tx = self.action_tx.clone();
                 tokio::spawn(async move {
-                    let state = mise::check_cwd_drift().await.unwrap_or(DriftState::NoConfig);
-                    let _ = tx.send(Action::DriftChecked(state));
+                    let report = mise::check_cwd_drift().await.unwrap_or_else(|_| DriftReport {
+                        state: DriftState::NoConfig,
+                        ..Default::default()
+                    });
+                    let _ = tx.send(Action::DriftChecked(report));
+                });
+            }
+            Action::OpenChangeDir => {
+                if self.popup.is_some() {
+                    return;
+                }
+                if self.has_unsaved_editor_changes() {
+                    self.status_message = Some((
+                        "Write or undo config edits before changing directory".to_string(),
+                        20,
+                    ));
+                    return;
+                }
+                // On Projects, start from the selected project; elsewhere from the current dir
+                let start = match self.tab {
+                    Tab::Projects => self
+                        .filtered_projects
+                        .get(self.projects_selected)
+                        .map(|&idx| self.projects[idx].path.clone()),
+                    _ => None,
+                };
+                self.popup = Some(Popup::ChangeDir {
+                    input: start.unwrap_or_else(|| self.work_dir.to_string_lossy().to_string()),
+                    error: None,
                 });
             }
+            Action::OpenDriftBreakdown => {
+                if self.popup.is_none() && self.drift.state != DriftState::Checking {
+                    self.popup = Some(Popup::DriftBreakdown);
+                }
+            }
+            Action::DriftApply(fix) => {
+                if !matches!(self.popup, Some(Popup::DriftBreakdown)) {
+                    return;
+                }
+                let dir = self.drift.dir.clone();
+                match fix {
+                    DriftFix::InstallMissing
+                        if self.drift.has(DriftIssue::Missing)
+                            || self.drift.has(DriftIssue::WrongVersion) =>
+                    {
+                        self.popup = None;
+                        self.handle_action(Action::InstallProjectTools { path: dir });
+                    }
+                    DriftFix::UpgradePins if self.drift.has(DriftIssue::Outdated) => {
+                        self.popup = None;
+                        self.handle_action(Action::UpdateProjectPins { path: dir });
+                    }
+                    DriftFix::Trust if !self.drift.untrusted.is_empty() => {
+                        let targets: Vec<TrustEntry> = self
+                            .drift
+                            .untrusted
+                            .iter()
+                            .map(|path| TrustEntry {
+                                path: path.clone(),
+                                state: TrustState::Untrusted,
+                                exists: true,
+                                record: None,
+                                selected: true,
+                            })
+                            .collect();
+                        self.popup = Some(Popup::Progress {
+                            message: format!("Trusting {} config(s)...", targets.len()),
+                        });
+                        let tx = self.action_tx.clone();
+                        tokio::spawn(async move {
+                            match mise::apply_trust_op(targets, TrustOp::Trust).await {
+                                Ok(msg) => {
+                                    let _ = tx.send(Action::OperationComplete(msg));
+                                }
+                                Err(e) => {
+                                    let _ = tx.send(Action::OperationFailed(e));
+                                }
+                            }
+                        });
+                    }
+                    _ => {}
+                }
+            }
             Action::JumpToDriftProject => {
                 self.tab = Tab::Projects;
                 self.sidebar_selected = Tab::Projects.index();
@@ -1451,6 +2134,23 @@ impl App {
         }
     }
 
+    fn spawn_uninstall(&mut self, tool: String, version: String) {
+        self.popup = Some(Popup::Progress {
+            message: format!("Uninstalling {tool}@{version}..."),
+        });
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            match mise::uninstall_tool(&tool, &version).await {
+                Ok(msg) => {
+                    let _ = tx.send(Action::OperationComplete(msg));
+                }
+                Err(e) => {
+                    let _ = tx.send(Action::OperationFailed(e));
+                }
+            }
+        });
+    }
+
     fn start_editor_popup(&mut self) {
         if !self.editor_states_loaded || self.popup.is_some() {
             return;
@@ -1465,7 +2165,7 @@ impl App {
                         let row = &self.editor_states[ci].tools[ri];
                         self.popup = Some(Popup::Editor {
                             config_idx: ci, row_idx: ri, tab: Tab::Tools,
-                            field0: row.name.clone(), field1: row.version.clone(),
+                            fields: row.form_fields(),
                             active_field: 1, is_new: false,
                         });
                     }
@@ -1480,7 +2180,7 @@ impl App {
                         let row = &self.editor_states[ci].env_vars[ri];
                         self.popup = Some(Popup::Editor {
                             config_idx: ci, row_idx: ri, tab: Tab::Environment,
-                            field0: row.key.clone(), field1: row.value.clone(),
+                            fields: row.form_fields(),
                             active_field: 1, is_new: false,
                         });
                     }
@@ -1495,7 +2195,7 @@ impl App {
                         let row = &self.editor_states[ci].tasks[ri];
                         self.popup = Some(Popup::Editor {
                             config_idx: ci, row_idx: ri, tab: Tab::Tasks,
-                            field0: row.name.clone(), field1: row.command.clone(),
+                            fields: row.form_fields(),
                             active_field: 1, is_new: false,
                         });
                     }
@@ -1545,72 +2245,194 @@ impl App {
     }
 
     fn handle_editor_confirm_edit(&mut self) {
-        let (config_idx, row_idx, tab, field0, field1) = match self.popup.take() {
-            Some(Popup::Editor { config_idx, row_idx, tab, field0, field1, .. }) => {
-                (config_idx, row_idx, tab, field0, field1)
+        let (config_idx, row_idx, tab, fields, is_new) = match self.popup.take() {
+            Some(Popup::Editor { config_idx, row_idx, tab, fields, active_field, is_new }) => {
+                // Options under a header naming no version would be lost; keep the form open
+                if tab == Tab::Tools {
+                    let check = self.editor_states.get(config_idx)
+                        .and_then(|state| state.tools.get(row_idx))
+                        .map(|row| row.clone().apply_form_fields(&fields));
+                    if let Some(Err(e)) = check {
+                        self.status_message = Some((format!("Error: {e}"), 20));
+                        self.popup = Some(Popup::Editor { config_idx, row_idx, tab, fields, active_field, is_new });
+                        return;
+                    }
+                }
+                (config_idx, row_idx, tab, fields, is_new)
             }
             other => { self.popup = other; return; }
         };
+        // New rows were already snapshotted by handle_editor_add_row
+        if !is_new {
+            self.push_editor_undo();
+        }
         let state = &mut self.editor_states[config_idx];
-        match tab {
-            Tab::Tools => {
-                if let Some(row) = state.tools.get_mut(row_idx) {
-                    if row.name != field0 {
-                        row.name = field0;
-                        if row.status == EditorRowStatus::Unchanged {
-                            row.status = EditorRowStatus::Modified;
-                        }
-                        state.dirty = true;
-                    }
-                    if row.version != field1 {
-                        row.version = field1;
-                        if row.status == EditorRowStatus::Unchanged {
-                            row.status = EditorRowStatus::Modified;
-                        }
-                        state.dirty = true;
-                    }
-                }
-            }
-            Tab::Environment => {
-                if let Some(row) = state.env_vars.get_mut(row_idx) {
-                    if row.key != field0 {
-                        row.key = field0;
-                        if row.status == EditorRowStatus::Unchanged {
-                            row.status = EditorRowStatus::Modified;
-                        }
-                        state.dirty = true;
-                    }
-                    if row.value != field1 {
-                        row.value = field1;
-                        if row.status == EditorRowStatus::Unchanged {
-                            row.status = EditorRowStatus::Modified;
-                        }
-                        state.dirty = true;
-                    }
-                }
+        let changed = match tab {
+            Tab::Tools => state
+                .tools
+                .get_mut(row_idx)
+                .and_then(|row| row.apply_form_fields(&fields).unwrap_or(false).then_some(&mut row.status)),
+            Tab::Environment => state
+                .env_vars
+                .get_mut(row_idx)
+                .and_then(|row| row.apply_form_fields(&fields).then_some(&mut row.status)),
+            Tab::Tasks => state
+                .tasks
+                .get_mut(row_idx)
+                .and_then(|row| row.apply_form_fields(&fields).then_some(&mut row.status)),
+            _ => None,
+        };
+        if let Some(status) = changed {
+            if *status == EditorRowStatus::Unchanged {
+                *status = EditorRowStatus::Modified;
             }
-            Tab::Tasks => {
-                if let Some(row) = state.tasks.get_mut(row_idx) {
-                    if row.name != field0 {
-                        row.name = field0;
-                        if row.status == EditorRowStatus::Unchanged {
-                            row.status = EditorRowStatus::Modified;
-                        }
-                        state.dirty = true;
-                    }
-                    if row.command != field1 {
-                        row.command = field1;
-                        if row.status == EditorRowStatus::Unchanged {
-                            row.status = EditorRowStatus::Modified;
-                        }
-                        state.dirty = true;
-                    }
+            state.dirty = true;
+            if tab == Tab::Tools {
+                if let Some(row) = state.tools.get(row_idx).cloned() {
+                    self.spawn_remote_version_check(row.name, row.versions);
                 }
             }
-            _ => {}
+        } else if !is_new {
+            // Nothing changed — drop the snapshot taken above
+            self.editor_undo.pop();
         }
     }
 
+    /// Check concrete version specs (starting with a digit) against `mise ls-remote`
+    /// in the background; unknown tools or network failures are silently skipped.
+    fn spawn_remote_version_check(&self, tool: String, versions: Vec<String>) {
+        let versions: Vec<String> = versions
+            .into_iter()
+            .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+            .collect();
+        if tool.is_empty() || versions.is_empty() {
+            return;
+        }
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let Ok(remote) = mise::fetch_remote_versions(&tool).await else {
+                return;
+            };
+            let missing = versions
+                .iter()
+                .filter(|v| !remote.iter().any(|r| mise::version_satisfies(r, v)))
+                .cloned()
+                .collect();
+            let _ = tx.send(Action::EditorRemoteChecked { tool, versions, missing });
+        });
+    }
+
+    /// Load bootstrap templates and open the picker, preselecting the current one.
+    fn open_template_picker(&mut self) {
+        self.wizard.templates = crate::config::load_templates();
+        if self.wizard.templates.is_empty() {
+            let dir = crate::config::templates_dir()
+                .map(|d| d.display().to_string())
+                .unwrap_or_else(|| "~/.config/misetui/templates".to_string());
+            self.status_message = Some((format!("No templates found in {dir}"), 20));
+            return;
+        }
+        let selected = self
+            .wizard
+            .template
+            .as_ref()
+            .and_then(|t| self.wizard.templates.iter().position(|c| c.name == t.name))
+            .map_or(0, |i| i + 1);
+        self.popup = Some(Popup::TemplatePicker { selected });
+    }
+
+    /// Replace the synthetic Environment-tab rows for `[env]` directives with the
+    /// ones currently on disk, so they can be selected and edited like variables.
+    fn sync_env_directives(&mut self) {
+        self.env_vars.retain(|v| !v.directive);
+        let rows: Vec<(String, EditorEnvRow)> = self
+            .editor_states
+            .iter()
+            .flat_map(|state| {
+                state
+                    .env_vars
+                    .iter()
+                    .filter(|r| r.kind != EnvRowKind::Var && r.status != EditorRowStatus::Added)
+                    .map(|r| (state.file_path.clone(), r.clone()))
+            })
+            .collect();
+        for (file_path, row) in rows {
+            let produces = if row.kind == EnvRowKind::File {
+                self.dotenv_keys(&file_path, &row)
+            } else {
+                mise::env_directive_outputs(&row)
+            };
+            let value = if row.kind.is_list() {
+                row.list_entries().join(", ")
+            } else {
+                row.value.clone()
+            };
+            self.env_vars.push(EnvVar {
+                name: row.original_key.clone().unwrap_or_else(|| row.key.clone()),
+                value,
+                source: file_path,
+                tool: format!("→ {}", produces.join(", ")),
+                directive: true,
+            });
+        }
+        self.update_filtered_env();
+    }
+
+    /// Cached keys of a `_.file` row, reading its dotenv files in the background
+    /// the first time they are seen.
+    fn dotenv_keys(&mut self, config_path: &str, row: &EditorEnvRow) -> Vec<String> {
+        let key = (config_path.to_string(), row.value.clone());
+        if let Some(cached) = self.env_dotenv_keys.get(&key) {
+            return cached.clone().unwrap_or_else(|| vec!["…".to_string()]);
+        }
+        self.env_dotenv_keys.insert(key.clone(), None);
+        let tx = self.action_tx.clone();
+        let files = row.list_entries();
+        tokio::spawn(async move {
+            let keys = mise::dotenv_keys(&key.0, &files).await;
+            let _ = tx.send(Action::DotenvKeysLoaded(key.0, key.1, keys));
+        });
+        vec!["…".to_string()]
+    }
+
+    /// Problems in pending editor rows, across every config file.
+    pub fn editor_problems(&self) -> Vec<crate::validate::EditorProblem> {
+        self.editor_states
+            .iter()
+            .filter(|s| s.dirty)
+            .flat_map(|s| {
+                crate::validate::validate_state(s, &self.registry, &self.editor_remote_missing)
+            })
+            .collect()
+    }
+
+    /// Record the current editor rows so the next mutation can be undone.
+    fn push_editor_undo(&mut self) {
+        const MAX_HISTORY: usize = 100;
+        self.editor_undo.push(self.editor_states.clone());
+        if self.editor_undo.len() > MAX_HISTORY {
+            self.editor_undo.remove(0);
+        }
+        self.editor_redo.clear();
+    }
+
+    /// Undo (or redo) the last editor mutation by swapping in a snapshot.
+    fn step_editor_history(&mut self, undo: bool) {
+        let (from, to) = if undo {
+            (&mut self.editor_undo, &mut self.editor_redo)
+        } else {
+            (&mut self.editor_redo, &mut self.editor_undo)
+        };
+        let Some(snapshot) = from.pop() else {
+            let msg = if undo { "Nothing to undo" } else { "Nothing to redo" };
+            self.status_message = Some((msg.to_string(), 15));
+            return;
+        };
+        to.push(std::mem::replace(&mut self.editor_states, snapshot));
+        let msg = if undo { "Undid last edit" } else { "Redid last edit" };
+        self.status_message = Some((msg.to_string(), 15));
+    }
+
     fn handle_editor_add_row(&mut self) {
         if !self.editor_states_loaded || self.popup.is_some() {
             return;
@@ -1633,43 +2455,44 @@ impl App {
             None => return,
         };
         let tab = self.tab;
+        if matches!(tab, Tab::Tools | Tab::Environment | Tab::Tasks) {
+            self.push_editor_undo();
+        }
         match self.tab {
             Tab::Tools => {
-                self.editor_states[config_idx].tools.push(EditorToolRow {
-                    name: String::new(), version: "latest".to_string(),
-                    status: EditorRowStatus::Added, original_name: None,
-                });
+                let row = EditorToolRow {
+                    versions: vec!["latest".to_string()],
+                    status: EditorRowStatus::Added,
+                    ..Default::default()
+                };
+                let fields = row.form_fields();
+                self.editor_states[config_idx].tools.push(row);
                 let row_idx = self.editor_states[config_idx].tools.len() - 1;
                 self.editor_states[config_idx].dirty = true;
                 self.popup = Some(Popup::Editor {
-                    config_idx, row_idx, tab,
-                    field0: String::new(), field1: "latest".to_string(),
+                    config_idx, row_idx, tab, fields,
                     active_field: 0, is_new: true,
                 });
             }
             Tab::Environment => {
-                self.editor_states[config_idx].env_vars.push(EditorEnvRow {
-                    key: String::new(), value: String::new(),
-                    status: EditorRowStatus::Added, original_key: None,
-                });
+                let row = EditorEnvRow { status: EditorRowStatus::Added, ..Default::default() };
+                let fields = row.form_fields();
+                self.editor_states[config_idx].env_vars.push(row);
                 let row_idx = self.editor_states[config_idx].env_vars.len() - 1;
                 self.editor_states[config_idx].dirty = true;
                 self.popup = Some(Popup::Editor {
-                    config_idx, row_idx, tab,
-                    field0: String::new(), field1: String::new(),
+                    config_idx, row_idx, tab, fields,
                     active_field: 0, is_new: true,
                 });
             }
             Tab::Tasks => {
-                self.editor_states[config_idx].tasks.push(EditorTaskRow {
-                    name: String::new(), command: String::new(),
-                    status: EditorRowStatus::Added, original_name: None,
-                });
+                let row = EditorTaskRow { status: EditorRowStatus::Added, ..Default::default() };
+                let fields = row.form_fields();
+                self.editor_states[config_idx].tasks.push(row);
                 let row_idx = self.editor_states[config_idx].tasks.len() - 1;
                 self.editor_states[config_idx].dirty = true;
                 self.popup = Some(Popup::Editor {
-                    config_idx, row_idx, tab,
-                    field0: String::new(), field1: String::new(),
+                    config_idx, row_idx, tab, fields,
                     active_field: 0, is_new: true,
                 });
             }
@@ -1688,6 +2511,7 @@ impl App {
                     let source = tool.source.clone();
                     let name = tool.name.clone();
                     if let Some((ci, ri)) = self.find_editor_tool(&source, &name) {
+                        self.push_editor_undo();
                         let row = &mut self.editor_states[ci].tools[ri];
                         if row.status == EditorRowStatus::Added {
                             self.editor_states[ci].tools.remove(ri);
@@ -1704,6 +2528,7 @@ impl App {
                     let source = var.source.clone();
                     let key = var.name.clone();
                     if let Some((ci, ri)) = self.find_editor_env(&source, &key) {
+                        self.push_editor_undo();
                         let row = &mut self.editor_states[ci].env_vars[ri];
                         if row.status == EditorRowStatus::Added {
                             self.editor_states[ci].env_vars.remove(ri);
@@ -1720,6 +2545,7 @@ impl App {
                     let source = task.source.clone();
                     let name = task.name.clone();
                     if let Some((ci, ri)) = self.find_editor_task(&source, &name) {
+                        self.push_editor_undo();
                         let row = &mut self.editor_states[ci].tasks[ri];
                         if row.status == EditorRowStatus::Added {
                             self.editor_states[ci].tasks.remove(ri);
@@ -1734,18 +2560,110 @@ impl App {
         }
     }
 
+    /// Show a unified diff of every dirty config before anything is written.
     fn handle_editor_write(&mut self) {
         if !self.editor_states_loaded || self.popup.is_some() {
             return;
         }
-        let dirty_states: Vec<EditorState> = self.editor_states.iter()
-            .filter(|s| s.dirty)
-            .cloned()
+        let blocking: Vec<_> = self.editor_problems().into_iter().filter(|p| p.blocking).collect();
+        if let Some(first) = blocking.first() {
+            self.status_message = Some((
+                format!(
+                    "Fix {} problem(s) before writing — {}: {}",
+                    blocking.len(),
+                    first.location,
+                    first.message
+                ),
+                30,
+            ));
+            return;
+        }
+        let paths: Vec<String> = self.editor_states.iter()
+            .filter(|s| s.dirty && crate::mise::changed_on_disk(s))
+            .map(|s| s.file_path.clone())
             .collect();
-        if dirty_states.is_empty() {
+        if !paths.is_empty() {
+            self.popup = Some(Popup::EditorConflict { paths });
+            return;
+        }
+        let mut diff = String::new();
+        for state in self.editor_states.iter().filter(|s| s.dirty) {
+            match crate::mise::render_editor_changes(state) {
+                Ok(new) => {
+                    diff.push_str(
+                        &similar::TextDiff::from_lines(&state.raw_document, &new)
+                            .unified_diff()
+                            .context_radius(3)
+                            .header(&state.file_path, &state.file_path)
+                            .to_string(),
+                    );
+                }
+                Err(e) => {
+                    self.status_message = Some((format!("Error: {e}"), 20));
+                    return;
+                }
+            }
+        }
+        if diff.is_empty() {
             self.status_message = Some(("No unsaved changes".to_string(), 15));
             return;
         }
+        self.popup = Some(Popup::EditorDiff { diff, scroll: 0 });
+    }
+
+    /// Resolve an external-modification conflict by merging on-disk changes with
+    /// pending edits (`merge`) or discarding pending edits and reloading.
+    fn resolve_editor_conflict(&mut self, merge: bool) {
+        let Some(Popup::EditorConflict { paths }) = self.popup.take() else {
+            return;
+        };
+        // Resolve every file before touching any, so a failure leaves all as they were
+        let mut conflicts = Vec::new();
+        let mut resolved = Vec::new();
+        for (i, state) in self.editor_states.iter().enumerate() {
+            if !paths.contains(&state.file_path) {
+                continue;
+            }
+            let result = if merge {
+                crate::mise::merge_external_changes(state).map(|(merged, c)| {
+                    conflicts.extend(c);
+                    merged
+                })
+            } else {
+                crate::mise::reload_editor_state(state)
+            };
+            match result {
+                Ok(new_state) => resolved.push((i, new_state)),
+                Err(e) => {
+                    self.status_message = Some((format!("Error: {e}"), 20));
+                    self.popup = Some(Popup::EditorConflict { paths });
+                    return;
+                }
+            }
+        }
+        self.push_editor_undo();
+        for (i, new_state) in resolved {
+            self.editor_states[i] = new_state;
+        }
+        if !merge {
+            self.status_message = Some((format!("Reloaded {} file(s) from disk", paths.len()), 20));
+            return;
+        }
+        self.status_message = Some(if conflicts.is_empty() {
+            ("Merged external changes".to_string(), 20)
+        } else {
+            (format!("Merged; kept your edits for {}", conflicts.join(", ")), 30)
+        });
+        // Let the user review the merged result before anything is written
+        self.handle_editor_write();
+    }
+
+    /// Write every dirty config file (after the diff preview is confirmed).
+    fn spawn_editor_write(&mut self) {
+        let dirty_states: Vec<EditorState> = self.editor_states.iter()
+            .filter(|s| s.dirty)
+            .cloned()
+            .collect();
         let count = dirty_states.len();
         self.popup = Some(Popup::Progress {
             message: format!("Writing {} config file(s)...", count),
@@ -1769,7 +2687,7 @@ impl App {
             if state.file_path == source {
                 for row in &state.tools {
                     if row.name == name && row.status != EditorRowStatus::Unchanged {
-                        let ver = if row.status == EditorRowStatus::Modified { Some(row.version.clone()) } else { None };
+                        let ver = if row.status == EditorRowStatus::Modified { Some(row.version_display()) } else { None };
                         return Some((row.status, ver));
                     }
                 }
@@ -1827,13 +2745,62 @@ impl App {
             .collect()
     }
 
+    /// Projects and config files whose requirements resolve to `tool@version`.
+    pub fn tool_usage(&self, tool: &str, version: &str) -> &[ToolUsage] {
+        self.usage_index
+            .get(&format!("{tool}@{version}"))
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Re-target every fetch at `dir`: mise commands, the Bootstrap target and the
+    /// drift check (main re-registers the drift watcher when `work_dir` changes).
+    fn change_dir(&mut self, dir: std::path::PathBuf) {
+        mise::set_work_dir(dir.clone());
+        self.wizard.target_dir = dir.to_string_lossy().to_string();
+        self.status_message = Some((format!("Working directory: {}", dir.display()), 20));
+        self.work_dir = dir;
+
+        // Everything except the registry and the project scan depends on the directory
+        self.tools_state = LoadState::Loading;
+        self.config_state = LoadState::Loading;
+        self.doctor_state = LoadState::Loading;
+        self.outdated_state = LoadState::Loading;
+        self.tasks_state = LoadState::Loading;
+        self.env_state = LoadState::Loading;
+        self.settings_state = LoadState::Loading;
+        self.editor_states.clear();
+        self.editor_states_loaded = false;
+        self.editor_undo.clear();
+        self.editor_redo.clear();
+        self.drift = DriftReport::default();
+        self.tools_selected = 0;
+        self.config_selected = 0;
+        self.config_preview_scroll = 0;
+        self.doctor_scroll = 0;
+        self.outdated_selected = 0;
+        self.tasks_selected = 0;
+        self.env_selected = 0;
+        self.settings_selected = 0;
+        self.start_fetch();
+    }
+
     pub fn has_unsaved_editor_changes(&self) -> bool {
         self.editor_states.iter().any(|s| s.dirty)
     }
 
     fn move_selection(&mut self, delta: i32) {
+        let template_rows = self.wizard.templates.len() + 1;
         if let Some(popup) = &mut self.popup {
             match popup {
+                Popup::TemplatePicker { selected } => {
+                    Self::adjust_selection(selected, delta, template_rows);
+                    return;
+                }
+                Popup::ExportReport { selected } => {
+                    Self::adjust_selection(selected, delta, ReportFormat::ALL.len());
+                    return;
+                }
                 Popup::VersionPicker {
                     selected,
                     filtered_versions,
@@ -1842,11 +2809,33 @@ impl App {
                     Self::adjust_selection(selected, delta, filtered_versions.len());
                     return;
                 }
-                Popup::ToolDetail { scroll, info, .. } => {
-                    let lines = info.lines().count();
+                Popup::ToolDetail { scroll, info, used_by, .. } => {
+                    let usage_lines: usize = used_by.iter().map(|(_, u)| u.len().max(1)).sum();
+                    let lines = info.lines().count() + usage_lines + 3;
+                    Self::adjust_scroll(scroll, delta, lines);
+                    return;
+                }
+                Popup::Prune { selected, candidates, .. } => {
+                    Self::adjust_selection(selected, delta, candidates.len());
+                    return;
+                }
+                Popup::TrustManager { selected, entries } => {
+                    Self::adjust_selection(selected, delta, entries.len());
+                    return;
+                }
+                Popup::EditorDiff { scroll, diff } => {
+                    Self::adjust_scroll(scroll, delta, diff.lines().count());
+                    return;
+                }
+                Popup::FleetPlan { scroll, plan } => {
+                    let lines = plan.installs.len() + plan.targets.len() + 4;
                     Self::adjust_scroll(scroll, delta, lines);
                     return;
                 }
+                Popup::FleetResults { scroll, results, .. } => {
+                    Self::adjust_scroll(scroll, delta, results.len());
+                    return;
+                }
                 _ => return,
             }
         }
@@ -1874,7 +2863,11 @@ impl App {
             }
             Tab::Config => {
                 let len = self.filtered_configs.len();
+                let before = self.config_selected;
                 Self::adjust_selection(&mut self.config_selected, delta, len);
+                if self.config_selected != before {
+                    self.config_preview_scroll = 0;
+                }
             }
             Tab::Doctor => {
                 let total = self.filtered_doctor.len();
@@ -1956,7 +2949,10 @@ impl App {
             Tab::Bootstrap => {}
             Tab::Tools => self.tools_selected = 0,
             Tab::Registry => self.registry_selected = 0,
-            Tab::Config => self.config_selected = 0,
+            Tab::Config => {
+                self.config_selected = 0;
+                self.config_preview_scroll = 0;
+            }
             Tab::Doctor => self.doctor_scroll = 0,
             Tab::Outdated => self.outdated_selected = 0,
             Tab::Tasks => self.tasks_selected = 0,
@@ -1995,7 +2991,7 @@ impl App {
                 Some((best_score, i, hl))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.registry_hl = scored.iter().map(|(_, _, hl)| hl.clone()).collect();
         self.filtered_registry = scored.into_iter().map(|(_, i, _)| i).collect();
     }
@@ -2032,12 +3028,21 @@ impl App {
                 Some((best_score, i, hl))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.tools_hl = scored.iter().map(|(_, _, hl)| hl.clone()).collect();
         self.filtered_tools = scored.into_iter().map(|(_, i, _)| i).collect();
     }
 
+    /// Recompute each config's lock rows against the installed tools. Runs when either
+    /// the configs or the tools finish loading, whichever comes last wins.
+    fn refresh_config_locks(&mut self) {
+        for cfg in &mut self.configs {
+            cfg.lock_rows = mise::config_lock_rows(cfg, &self.tools);
+        }
+    }
+
     fn update_filtered_configs(&mut self) {
+        self.config_preview_scroll = 0;
         if self.search_query.is_empty() {
             self.filtered_configs = (0..self.configs.len()).collect();
             return;
@@ -2059,7 +3064,7 @@ impl App {
                 Some((score, i))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.filtered_configs = scored.into_iter().map(|(_, i)| i).collect();
     }
 
@@ -2079,7 +3084,7 @@ impl App {
                 Some((score, i))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.filtered_doctor = scored.into_iter().map(|(_, i)| i).collect();
     }
 
@@ -2106,7 +3111,7 @@ impl App {
                 Some((best_score, i, hl))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.outdated_hl = scored.iter().map(|(_, _, hl)| hl.clone()).collect();
         self.filtered_outdated = scored.into_iter().map(|(_, i, _)| i).collect();
     }
@@ -2132,7 +3137,7 @@ impl App {
                 Some((best_score, i, hl))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.tasks_hl = scored.iter().map(|(_, _, hl)| hl.clone()).collect();
         self.filtered_tasks = scored.into_iter().map(|(_, i, _)| i).collect();
     }
@@ -2160,7 +3165,7 @@ impl App {
                 Some((best_score, i, hl))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.env_hl = scored.iter().map(|(_, _, hl)| hl.clone()).collect();
         self.filtered_env = scored.into_iter().map(|(_, i, _)| i).collect();
     }
@@ -2186,37 +3191,133 @@ impl App {
                 Some((best_score, i, hl))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.settings_hl = scored.iter().map(|(_, _, hl)| hl.clone()).collect();
         self.filtered_settings = scored.into_iter().map(|(_, i, _)| i).collect();
     }
 
+    fn selected_project_path(&self) -> Option<String> {
+        self.filtered_projects
+            .get(self.projects_selected)
+            .map(|&idx| self.projects[idx].path.clone())
+    }
+
+    /// Apply `edit` to misetui's config for the project at `path`, save it and
+    /// refresh groups, favorites and tags without rescanning. `edit` returns the
+    /// status message.
+    fn update_project_config(
+        &mut self,
+        path: &str,
+        edit: impl FnOnce(&mut crate::config::MisetuiConfig, &std::path::Path) -> String,
+    ) {
+        // A config.toml that fails to parse must not be replaced by defaults
+        let mut config = match crate::config::MisetuiConfig::try_load() {
+            Ok(config) => config,
+            Err(e) => {
+                self.status_message = Some((format!("Error: {e}"), 20));
+                return;
+            }
+        };
+        let msg = edit(&mut config, std::path::Path::new(path));
+        if let Err(e) = config.save_project_meta() {
+            self.status_message = Some((format!("Error: {e}"), 20));
+            return;
+        }
+        for project in &mut self.projects {
+            project.apply_config(&config);
+        }
+        self.update_filtered_projects();
+        // Follow the project to its new position
+        if let Some(pos) = self
+            .filtered_projects
+            .iter()
+            .position(|&i| self.projects[i].path == path)
+        {
+            self.projects_selected = pos;
+        }
+        self.status_message = Some((msg, 15));
+    }
+
+    /// Rescan `scan_dirs` on a blocking thread, streaming projects in as they are found.
+    fn start_project_scan(&mut self) {
+        self.projects_scan += 1;
+        self.projects_state = LoadState::Loading;
+        let config = crate::config::MisetuiConfig::load();
+        let invalid = mise::invalid_excludes(&config.exclude);
+        if !invalid.is_empty() {
+            self.status_message = Some((
+                format!("Ignoring invalid exclude pattern(s): {}", invalid.join(", ")),
+                30,
+            ));
+        }
+        let scan = self.projects_scan;
+        let tx = self.action_tx.clone();
+        let tools_snapshot = self.tools.clone();
+        tokio::task::spawn_blocking(move || {
+            let on_found = |project: &MiseProject| {
+                let _ = tx.send(Action::ProjectScanned(scan, project.clone()));
+            };
+            let projects = mise::scan_projects(&config, &tools_snapshot, &on_found);
+            let _ = tx.send(Action::ProjectsLoaded(scan, projects));
+        });
+    }
+
     fn update_filtered_projects(&mut self) {
+        let group = self.projects_group_filter.as_deref();
+        let in_group = |proj: &MiseProject| group.is_none() || proj.group.as_deref() == group;
         if !self.search_active || self.search_query.is_empty() {
-            self.filtered_projects = (0..self.projects.len()).collect();
+            let mut filtered: Vec<usize> = (0..self.projects.len())
+                .filter(|&i| in_group(&self.projects[i]))
+                .collect();
+            // Keep each section contiguous so the group headers read top to bottom
+            filtered.sort_by(|&a, &b| self.projects[a].section().cmp(&self.projects[b].section()));
+            self.filtered_projects = filtered;
             self.projects_hl = self.filtered_projects.iter().map(|_| Vec::new()).collect();
             return;
         }
         let matcher = SkimMatcherV2::default();
         let query = &self.search_query.clone();
+        // `#tag` searches tags only
+        if let Some(tag_query) = query.strip_prefix('#') {
+            let mut scored: Vec<(i64, usize)> = self
+                .projects
+                .iter()
+                .enumerate()
+                .filter(|(_, proj)| in_group(proj))
+                .filter_map(|(i, proj)| {
+                    let best = proj
+                        .tags
+                        .iter()
+                        .filter_map(|t| matcher.fuzzy_match(t, tag_query))
+                        .max()?;
+                    Some((best, i))
+                })
+                .collect();
+            scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+            self.filtered_projects = scored.iter().map(|(_, i)| *i).collect();
+            self.projects_hl = self.filtered_projects.iter().map(|_| Vec::new()).collect();
+            return;
+        }
         let mut scored: Vec<(i64, usize, Vec<usize>)> = self
             .projects
             .iter()
             .enumerate()
+            .filter(|(_, proj)| in_group(proj))
             .filter_map(|(i, proj)| {
                 let name_score = matcher.fuzzy_indices(&proj.name, query);
                 let path_score = matcher.fuzzy_indices(&proj.path, query);
-                // Use the better score of name vs path; highlight indices from name match
-                match (name_score, path_score) {
-                    (Some((ns, ni)), Some((ps, _))) if ns >= ps => Some((ns, i, ni)),
+                let tag_score = proj.tags.iter().filter_map(|t| matcher.fuzzy_match(t, query)).max();
+                // Use the best score of name, path and tags; highlight indices from name match
+                let other = path_score.map(|(ps, _)| ps).max(tag_score);
+                match (name_score, other) {
+                    (Some((ns, ni)), Some(os)) if ns >= os => Some((ns, i, ni)),
                     (Some((ns, ni)), None) => Some((ns, i, ni)),
-                    (None, Some((ps, _pi))) => Some((ps, i, Vec::new())),
-                    (Some((ns, ni)), Some(_)) => Some((ns, i, ni)),
+                    (_, Some(os)) => Some((os, i, Vec::new())),
                     _ => None,
                 }
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         self.filtered_projects = scored.iter().map(|(_, i, _)| *i).collect();
         self.projects_hl = scored.into_iter().map(|(_, _, hl)| hl).collect();
     }
@@ -2240,6 +3341,14 @@ impl App {
                         0 => ta.name.to_lowercase().cmp(&tb.name.to_lowercase()),
                         1 => ta.version.cmp(&tb.version),
                         2 => ta.active.cmp(&tb.active),
+                        3 => {
+                            let used = |t: &InstalledTool| {
+                                self.usage_index
+                                    .get(&format!("{}@{}", t.name, t.version))
+                                    .map_or(0, |u| u.len())
+                            };
+                            used(ta).cmp(&used(tb))
+                        }
                         _ => ta.name.to_lowercase().cmp(&tb.name.to_lowercase()),
                     };
                     if asc { cmp } else { cmp.reverse() }
@@ -2310,6 +3419,40 @@ impl App {
                     if asc { cmp } else { cmp.reverse() }
                 });
             }
+            Tab::Projects => {
+                self.filtered_projects.sort_by(|&a, &b| {
+                    let pa = &self.projects[a];
+                    let pb = &self.projects[b];
+                    // Favorites and groups stay together, like the Tools tab's sources
+                    let section_cmp = pa.section().cmp(&pb.section());
+                    if section_cmp != std::cmp::Ordering::Equal {
+                        return section_cmp;
+                    }
+                    // Unknown ages sort as oldest so recently touched repos lead.
+                    let age = |secs: Option<u64>| secs.unwrap_or(u64::MAX);
+                    let cmp = match col {
+                        0 => pa.name.to_lowercase().cmp(&pb.name.to_lowercase()),
+                        1 => pa.path.cmp(&pb.path),
+                        2 => pa.tool_count.cmp(&pb.tool_count),
+                        3 => pa.health.rank().cmp(&pb.health.rank()),
+                        4 => pb.lock_issues().cmp(&pa.lock_issues()),
+                        5 => {
+                            let branch = |p: &MiseProject| {
+                                p.git.as_ref().map(|g| g.branch.to_lowercase())
+                            };
+                            branch(pa).cmp(&branch(pb))
+                        }
+                        6 => {
+                            let dirty = |p: &MiseProject| p.git.as_ref().map(|g| !g.config_dirty);
+                            dirty(pa).cmp(&dirty(pb))
+                        }
+                        7 => age(pa.git.as_ref().and_then(|g| g.commit_age_secs))
+                            .cmp(&age(pb.git.as_ref().and_then(|g| g.commit_age_secs))),
+                        _ => age(pa.config_age_secs).cmp(&age(pb.config_age_secs)),
+                    };
+                    if asc { cmp } else { cmp.reverse() }
+                });
+            }
             _ => {}
         }
     }
@@ -2415,7 +3558,7 @@ impl App {
                 Some((score, i))
             })
             .collect();
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
         scored.into_iter().map(|(_, i)| i).collect()
     }
 
@@ -2436,16 +3579,198 @@ impl App {
     }
 }
 
-/// Build the .mise.toml content string from a list of enabled tools.
-/// Called by the wizard intercept block to populate preview_content.
-fn generate_mise_toml_preview(tools: &[crate::model::DetectedTool]) -> String {
-    let mut lines = vec!["[tools]".to_string()];
+/// Check every prune candidate that is not protected by the "keep latest N" policy
+/// or by a scanned project that still requires it.
+fn apply_prune_policy(candidates: &mut [PruneCandidate], keep_latest: usize) {
+    for c in candidates.iter_mut() {
+        c.selected = !c.is_protected(keep_latest);
+    }
+}
+
+/// Expand a leading `~` and resolve `input` to an existing directory.
+fn resolve_dir(input: &str) -> Result<std::path::PathBuf, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter a directory path".to_string());
+    }
+    let path = match input.strip_prefix('~') {
+        Some(rest) => dirs::home_dir()
+            .ok_or_else(|| "Could not determine home directory".to_string())?
+            .join(rest.trim_start_matches('/')),
+        None => std::path::PathBuf::from(input),
+    };
+    let path = if path.is_relative() {
+        mise::work_dir().join(path)
+    } else {
+        path
+    };
+    match path.canonicalize() {
+        Ok(dir) if dir.is_dir() => Ok(dir),
+        Ok(_) => Err(format!("Not a directory: {}", path.display())),
+        Err(e) => Err(format!("{}: {e}", path.display())),
+    }
+}
+
+/// Tools the wizard proposes: the root's own detections, or in monorepo mode a
+/// root config of shared tools plus per-package overrides. A chosen template's
+/// tools join the root config unless detection found a real pin for them.
+fn wizard_proposal(wizard: &WizardState) -> Vec<crate::model::DetectedTool> {
+    let mut tools = if wizard.monorepo {
+        crate::detect::plan_monorepo(&wizard.packages)
+    } else {
+        wizard.packages.first().map(|p| p.tools.clone()).unwrap_or_default()
+    };
+    if let Some(template) = &wizard.template {
+        let rendered = template.render(&wizard.target_dir);
+        for (name, version) in mise::template_tools(&rendered) {
+            let existing = tools.iter().position(|t| t.dir.is_empty() && t.name == name);
+            if existing.is_some_and(|i| !crate::detect::is_floating(&tools[i].version)) {
+                continue;
+            }
+            let tool = crate::model::DetectedTool {
+                name,
+                version,
+                source: format!("template: {}", template.name),
+                enabled: true,
+                ..Default::default()
+            };
+            match existing {
+                Some(i) => tools[i] = tool,
+                None => tools.push(tool),
+            }
+        }
+        tools.sort_by(|a, b| a.dir.cmp(&b.dir).then_with(|| a.name.cmp(&b.name)));
+    }
+    tools
+}
+
+/// Tasks the wizard proposes: the root's imports, or in monorepo mode every
+/// package's imports, each going into that package's config.
+fn wizard_tasks(packages: &[crate::model::WizardPackage], monorepo: bool) -> Vec<crate::model::DetectedTask> {
+    let take = if monorepo { packages.len() } else { 1 };
+    packages.iter().take(take).flat_map(|p| p.tasks.clone()).collect()
+}
+
+/// Enabled wizard tools and tasks destined for one config file.
+struct WizardConfig {
+    /// Package directory relative to the wizard target; empty for the root.
+    dir: String,
+    tools: Vec<(String, String)>,
+    tasks: Vec<crate::model::DetectedTask>,
+}
+
+/// Group enabled wizard tools and tasks per config file to write, root first.
+fn wizard_configs(
+    tools: &[crate::model::DetectedTool],
+    tasks: &[crate::model::DetectedTask],
+) -> Vec<WizardConfig> {
+    let mut configs: Vec<WizardConfig> = Vec::new();
+    fn config_for<'a>(configs: &'a mut Vec<WizardConfig>, dir: &str) -> &'a mut WizardConfig {
+        match configs.iter().position(|c| c.dir == dir) {
+            Some(i) => &mut configs[i],
+            None => {
+                configs.push(WizardConfig { dir: dir.to_string(), tools: Vec::new(), tasks: Vec::new() });
+                configs.last_mut().expect("just pushed")
+            }
+        }
+    }
     for tool in tools.iter().filter(|t| t.enabled) {
-        if tool.version.is_empty() || tool.version == "latest" {
-            lines.push(format!("{} = \"latest\"", tool.name));
-        } else {
-            lines.push(format!("{} = \"{}\"", tool.name, tool.version));
+        config_for(&mut configs, &tool.dir).tools.push((tool.name.clone(), tool.version.clone()));
+    }
+    for task in tasks.iter().filter(|t| t.enabled) {
+        config_for(&mut configs, &task.dir).tasks.push(task.clone());
+    }
+    configs.sort_by(|a, b| a.dir.cmp(&b.dir));
+    configs
+}
+
+/// Build the configs the wizard will write for its enabled tools and tasks,
+/// merged into any existing config (with the chosen template layered under the
+/// root one). Called by the wizard intercept block to populate preview_content,
+/// the merge summary and the exact outputs. Monorepo proposals and existing
+/// configs get one labelled section per file.
+fn build_wizard_preview(wizard: &mut WizardState) {
+    let target = wizard.target_dir.as_str();
+    let mut configs = wizard_configs(&wizard.tools, &wizard.tasks);
+    if configs.is_empty() || (wizard.template.is_some() && !configs[0].dir.is_empty()) {
+        configs.insert(0, WizardConfig { dir: String::new(), tools: Vec::new(), tasks: Vec::new() });
+    }
+    let multiple = configs.len() > 1 || configs.iter().any(|c| !c.dir.is_empty());
+    let skipped: Vec<String> = wizard
+        .tools
+        .iter()
+        .filter(|t| !t.enabled && t.dir.is_empty())
+        .map(|t| t.name.clone())
+        .collect();
+
+    let mut sections = Vec::new();
+    wizard.merge.clear();
+    wizard.outputs.clear();
+    wizard.preview_error = None;
+    for config in &configs {
+        let path = mise::wizard_config_path(&std::path::Path::new(target).join(&config.dir));
+        let label = path
+            .strip_prefix(target)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string_lossy().to_string());
+        let existing = std::fs::read_to_string(&path).ok();
+        let base = match (&wizard.template, config.dir.is_empty()) {
+            (Some(t), true) => mise::apply_template(
+                existing.as_deref().unwrap_or(""),
+                &t.render(target),
+                &skipped,
+                &label,
+            ),
+            _ => Ok(existing.clone().unwrap_or_default()),
+        };
+        let merged = base.and_then(|base| mise::merge_wizard_config(&base, &config.tools, &config.tasks, &label));
+        let content = match merged {
+            Ok((content, entries)) => {
+                wizard.merge.extend(entries);
+                wizard.outputs.push((path.clone(), content.clone()));
+                content
+            }
+            Err(e) => {
+                let text = format!("# ✗ {e}\n");
+                wizard.preview_error.get_or_insert(e);
+                text
+            }
+        };
+        let header = match (&existing, multiple) {
+            (Some(_), _) => Some(format!("# ── {label} (merged into existing) ──")),
+            (None, true) => Some(format!("# ── {label} ──")),
+            (None, false) => None,
+        };
+        sections.push(match header {
+            Some(h) => format!("{h}\n{content}"),
+            None => content,
+        });
+    }
+
+    wizard.agent_outputs.clear();
+    let root = std::path::Path::new(target);
+    let root_output = wizard.outputs.iter().find(|(p, _)| p.parent() == Some(root));
+    if let (true, Some((path, content))) = (wizard.write_agent_files, root_output) {
+        let config_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let packages: Vec<String> = configs.iter().filter(|c| !c.dir.is_empty()).map(|c| c.dir.clone()).collect();
+        let section = mise::render_agent_section(&config_name, content, &packages);
+        let outputs = match mise::agent_file_outputs(target, &section) {
+            Ok(outputs) => outputs,
+            Err(e) => {
+                sections.push(format!("# ✗ {e}\n"));
+                wizard.preview_error.get_or_insert(e);
+                Vec::new()
+            }
+        };
+        for (path, content) in outputs {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            sections.push(if path.exists() {
+                format!("# ── {name} (mise section inserted or updated, rest kept) ──\n{section}")
+            } else {
+                format!("# ── {name} (new) ──\n{content}")
+            });
+            wizard.agent_outputs.push((path, content));
         }
     }
-    lines.join("\n") + "\n"
+    wizard.preview_content = sections.join("\n");
 }
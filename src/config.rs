@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MisetuiConfig {
@@ -7,6 +8,27 @@ pub struct MisetuiConfig {
     pub scan_dirs: Vec<PathBuf>,
     #[serde(default = "default_max_depth")]
     pub max_depth: usize,
+    /// Per scan dir overrides of `max_depth`, keyed by the directory (`~` allowed).
+    #[serde(default)]
+    pub dir_depth: BTreeMap<String, usize>,
+    /// Gitignore-style globs, relative to each scan dir, for directories the scan skips.
+    #[serde(default = "default_exclude")]
+    pub exclude: Vec<String>,
+    /// Skip directories ignored by `.gitignore`, `.ignore` and git's exclude files.
+    #[serde(default = "default_true")]
+    pub respect_gitignore: bool,
+    /// Number of newest installed versions per tool the prune view keeps by default.
+    #[serde(default)]
+    pub prune_keep_latest: usize,
+    /// Named project groups, each a list of directories whose projects belong to it.
+    #[serde(default)]
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// Project paths pinned to the top of the Projects tab.
+    #[serde(default)]
+    pub favorites: Vec<String>,
+    /// Free-form tags per project path.
+    #[serde(default)]
+    pub tags: BTreeMap<String, Vec<String>>,
 }
 
 fn default_scan_dirs() -> Vec<PathBuf> {
@@ -25,11 +47,29 @@ fn default_max_depth() -> usize {
     3
 }
 
+fn default_exclude() -> Vec<String> {
+    ["node_modules", "target", "vendor"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_true() -> bool {
+    true
+}
+
 impl Default for MisetuiConfig {
     fn default() -> Self {
         Self {
             scan_dirs: default_scan_dirs(),
             max_depth: default_max_depth(),
+            dir_depth: BTreeMap::new(),
+            exclude: default_exclude(),
+            respect_gitignore: true,
+            prune_keep_latest: 0,
+            groups: BTreeMap::new(),
+            favorites: Vec::new(),
+            tags: BTreeMap::new(),
         }
     }
 }
@@ -37,24 +77,140 @@ impl Default for MisetuiConfig {
 impl MisetuiConfig {
     /// Load from ~/.config/misetui/config.toml; returns defaults if file absent or parse fails.
     pub fn load() -> Self {
-        let config_path = dirs::config_dir().map(|d| d.join("misetui").join("config.toml"));
+        Self::try_load().unwrap_or_default()
+    }
 
-        let Some(path) = config_path else {
-            return Self::default();
+    /// Load from ~/.config/misetui/config.toml; defaults if the file is absent, an error
+    /// if it exists but cannot be read or parsed. Use this before saving anything.
+    pub fn try_load() -> Result<Self, String> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
         };
+        match std::fs::read_to_string(&path) {
+            // The full error is a multi-line snippet; keep the location and message
+            Ok(contents) => toml::from_str(&contents).map_err(|e| {
+                let location = e.to_string().lines().next().unwrap_or_default().to_string();
+                format!("Invalid {}: {location}: {}", path.display(), e.message())
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Scan depth for `scan_dir`: its `dir_depth` entry, else `max_depth`.
+    pub fn depth_for(&self, scan_dir: &Path) -> usize {
+        self.dir_depth
+            .iter()
+            .find(|(dir, _)| expand_home(dir) == scan_dir)
+            .map_or(self.max_depth, |(_, depth)| *depth)
+    }
+
+    /// Group for the project at `path`: the one listing the closest enclosing directory.
+    pub fn group_for(&self, path: &Path) -> Option<String> {
+        self.groups
+            .iter()
+            .flat_map(|(name, dirs)| dirs.iter().map(move |dir| (name, expand_home(dir))))
+            .filter(|(_, dir)| path.starts_with(dir))
+            .max_by_key(|(_, dir)| dir.components().count())
+            .map(|(name, _)| name.clone())
+    }
+
+    pub fn is_favorite(&self, path: &Path) -> bool {
+        self.favorites.iter().any(|f| expand_home(f) == path)
+    }
 
-        let Ok(contents) = std::fs::read_to_string(&path) else {
-            return Self::default();
+    pub fn tags_for(&self, path: &Path) -> Vec<String> {
+        self.tags
+            .iter()
+            .find(|(p, _)| expand_home(p) == path)
+            .map(|(_, tags)| tags.clone())
+            .unwrap_or_default()
+    }
+
+    /// Pin or unpin `path`; returns whether it is now a favorite.
+    pub fn toggle_favorite(&mut self, path: &Path) -> bool {
+        let before = self.favorites.len();
+        self.favorites.retain(|f| expand_home(f) != path);
+        if self.favorites.len() < before {
+            return false;
+        }
+        self.favorites.push(path.to_string_lossy().to_string());
+        true
+    }
+
+    /// Replace the tags of `path`; an empty list removes its entry.
+    pub fn set_tags(&mut self, path: &Path, tags: Vec<String>) {
+        self.tags.retain(|p, _| expand_home(p) != path);
+        if !tags.is_empty() {
+            self.tags.insert(path.to_string_lossy().to_string(), tags);
+        }
+    }
+
+    /// Write `favorites` and `tags` into config.toml, keeping every other key and
+    /// comment in the file as it is.
+    pub fn save_project_meta(&self) -> Result<(), String> {
+        use toml_edit::{value, Array, DocumentMut, Item, Table};
+
+        let config_path =
+            config_path().ok_or_else(|| "Could not determine config directory".to_string())?;
+        let existing = match std::fs::read_to_string(&config_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Failed to read {}: {e}", config_path.display())),
         };
+        let mut doc: DocumentMut = existing
+            .parse()
+            .map_err(|e| format!("Invalid {}: {e}", config_path.display()))?;
 
-        toml::from_str(&contents).unwrap_or_default()
+        let strings = |list: &[String]| value(list.iter().map(String::as_str).collect::<Array>());
+        let stored = |item: Option<&Item>| -> Option<Vec<String>> {
+            let array = item?.as_array()?;
+            Some(array.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        };
+
+        // Only rewrite values that changed, so their formatting survives
+        if stored(doc.get("favorites")).as_deref() != Some(self.favorites.as_slice()) {
+            if self.favorites.is_empty() {
+                doc.remove("favorites");
+            } else {
+                doc.insert("favorites", strings(&self.favorites));
+            }
+        }
+        if self.tags.is_empty() {
+            doc.remove("tags");
+        } else {
+            let tags = doc
+                .entry("tags")
+                .or_insert_with(|| Item::Table(Table::new()))
+                .as_table_like_mut()
+                .ok_or_else(|| format!("tags in {} is not a table", config_path.display()))?;
+            let stale: Vec<String> = tags
+                .iter()
+                .map(|(k, _)| k.to_string())
+                .filter(|k| !self.tags.contains_key(k))
+                .collect();
+            for key in stale {
+                tags.remove(&key);
+            }
+            for (path, list) in &self.tags {
+                if stored(tags.get(path)).as_ref() != Some(list) {
+                    tags.insert(path, strings(list));
+                }
+            }
+        }
+
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config dir: {e}"))?;
+        }
+        std::fs::write(&config_path, doc.to_string())
+            .map_err(|e| format!("Failed to write config: {e}"))
     }
 
     /// Save to ~/.config/misetui/config.toml. Creates directories if needed.
     pub fn save(&self) -> Result<(), String> {
-        let config_path = dirs::config_dir()
-            .map(|d| d.join("misetui").join("config.toml"))
-            .ok_or_else(|| "Could not determine config directory".to_string())?;
+        let config_path =
+            config_path().ok_or_else(|| "Could not determine config directory".to_string())?;
 
         if let Some(parent) = config_path.parent() {
             std::fs::create_dir_all(parent)
@@ -70,3 +226,90 @@ impl MisetuiConfig {
         Ok(())
     }
 }
+
+/// ~/.config/misetui/config.toml
+fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("misetui").join("config.toml"))
+}
+
+/// Expand a leading `~` to the home directory.
+fn expand_home(dir: &str) -> PathBuf {
+    match (dir.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(dir),
+    }
+}
+
+/// A named bootstrap template: a partial mise.toml (tools, env, tasks, settings)
+/// stored as `~/.config/misetui/templates/<name>.toml`.
+#[derive(Debug, Clone)]
+pub struct BootstrapTemplate {
+    /// File stem, e.g. `service` for `service.toml`.
+    pub name: String,
+    /// First line of a leading `#` comment block, if any.
+    pub description: String,
+    pub contents: String,
+}
+
+impl BootstrapTemplate {
+    /// Substitute `{{project_name}}` (the directory's name) and `{{project_dir}}`
+    /// (its full path) for the wizard target `dir`. Placeholders sit inside quoted
+    /// strings, so the values are escaped as TOML basic-string content.
+    pub fn render(&self, dir: &str) -> String {
+        let name = std::path::Path::new(dir)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.contents
+            .replace("{{project_name}}", &toml_escape(&name))
+            .replace("{{project_dir}}", &toml_escape(dir))
+    }
+}
+
+/// Escape `s` for use between the quotes of a TOML basic string.
+fn toml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// ~/.config/misetui/templates
+pub fn templates_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("misetui").join("templates"))
+}
+
+/// Load every `*.toml` template, sorted by name. Missing or unreadable files are skipped.
+pub fn load_templates() -> Vec<BootstrapTemplate> {
+    let Some(entries) = templates_dir().and_then(|d| std::fs::read_dir(d).ok()) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<BootstrapTemplate> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| {
+            let contents = std::fs::read_to_string(&path).ok()?;
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            let description = contents
+                .lines()
+                .next()
+                .and_then(|l| l.strip_prefix('#'))
+                .map(|d| d.trim().to_string())
+                .unwrap_or_default();
+            Some(BootstrapTemplate {
+                name,
+                description,
+                contents,
+            })
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
@@ -0,0 +1,910 @@
+//! Toolchain detectors used by the Bootstrap wizard.
+//!
+//! Each detector inspects one family of project files and reports the tools it
+//! implies, reading real version constraints where the file carries one.
+
+use crate::model::{DetectedTask, DetectedTool, WizardPackage};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A tool suggested by a detector.
+#[derive(Debug, Clone)]
+struct Detection {
+    tool: &'static str,
+    version: String,
+    source: String,
+    /// True when `version` came from the project itself rather than a fallback
+    /// like `lts` or `latest`. Pinned detections win over unpinned ones.
+    pinned: bool,
+}
+
+impl Detection {
+    fn pinned(tool: &'static str, version: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            tool,
+            version: version.into(),
+            source: source.into(),
+            pinned: true,
+        }
+    }
+
+    fn fallback(tool: &'static str, version: &str, source: impl Into<String>) -> Self {
+        Self {
+            tool,
+            version: version.to_string(),
+            source: source.into(),
+            pinned: false,
+        }
+    }
+}
+
+type Detector = fn(&Path) -> Vec<Detection>;
+
+/// Detectors in priority order: when two pinned detections name the same tool,
+/// the earlier detector wins.
+const DETECTORS: &[Detector] = &[
+    detect_node,
+    detect_package_manager,
+    detect_bun,
+    detect_deno,
+    detect_rust,
+    detect_go,
+    detect_python,
+    detect_ruby,
+    detect_php,
+    detect_terraform,
+    detect_dotnet,
+    detect_sdkman,
+    detect_java,
+    detect_elixir,
+];
+
+/// Run every detector against `dir` on top of the `.tool-versions` entries in
+/// `legacy`, which are the lowest priority: any detector that reads a real pin
+/// for the same tool replaces them.
+pub fn detect(dir: &Path, legacy: Vec<DetectedTool>) -> Vec<DetectedTool> {
+    let mut tools: HashMap<String, (DetectedTool, bool)> = legacy
+        .into_iter()
+        .map(|t| {
+            let pinned = t.version != "latest";
+            (t.name.clone(), (t, pinned))
+        })
+        .collect();
+
+    for detector in DETECTORS {
+        for d in detector(dir) {
+            let replace = match tools.get(d.tool) {
+                None => true,
+                // A later unpinned hint never overrides an earlier suggestion
+                Some((existing, pinned)) => {
+                    d.pinned && (!pinned || existing.source == ".tool-versions")
+                }
+            };
+            if replace {
+                let tool = DetectedTool {
+                    name: d.tool.to_string(),
+                    version: d.version,
+                    source: d.source,
+                    enabled: true,
+                    ..Default::default()
+                };
+                tools.insert(d.tool.to_string(), (tool, d.pinned));
+            }
+        }
+    }
+    tools.into_values().map(|(t, _)| t).collect()
+}
+
+/// How many directory levels below the wizard target monorepo detection walks.
+const PACKAGE_DEPTH: usize = 3;
+
+/// Directories never treated as (or searched for) packages.
+const SKIP_DIRS: [&str; 8] = [
+    "node_modules", "target", "vendor", "dist", "build", "venv", "__pycache__", "_build",
+];
+
+/// Detect tools in `root` and in every package directory below it. The root
+/// always comes first, even when it has no indicators of its own; packages
+/// follow in path order.
+pub fn detect_packages(root: &Path) -> Vec<WizardPackage> {
+    let mut packages = vec![WizardPackage {
+        dir: String::new(),
+        tools: detect_dir(root, ""),
+        tasks: detect_tasks(root, ""),
+    }];
+    collect_packages(root, root, 0, &mut packages);
+    packages[1..].sort_by(|a, b| a.dir.cmp(&b.dir));
+    packages
+}
+
+fn collect_packages(root: &Path, dir: &Path, depth: usize, packages: &mut Vec<WizardPackage>) {
+    if depth >= PACKAGE_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !path.is_dir() || name.starts_with('.') || SKIP_DIRS.contains(&name.as_ref()) {
+            continue;
+        }
+        let rel = path
+            .strip_prefix(root)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let tools = detect_dir(&path, &rel);
+        if !tools.is_empty() {
+            let tasks = detect_tasks(&path, &rel);
+            packages.push(WizardPackage {
+                dir: rel,
+                tools,
+                tasks,
+            });
+        }
+        collect_packages(root, &path, depth + 1, packages);
+    }
+}
+
+fn detect_dir(dir: &Path, rel: &str) -> Vec<DetectedTool> {
+    let legacy = crate::mise::migrate_legacy_pins(&dir.to_string_lossy());
+    let mut tools = detect(dir, legacy);
+    for t in &mut tools {
+        t.dir = rel.to_string();
+    }
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    tools
+}
+
+/// Versions that float rather than pin; they never force a per-package override.
+pub fn is_floating(version: &str) -> bool {
+    matches!(version, "" | "latest" | "lts" | "stable")
+}
+
+/// Propose configs for a monorepo: a tool used by the root or by two or more
+/// packages goes into the root config at the version most of them agree on, and
+/// packages pinning a different version get an override in their own config. A
+/// tool only one package uses stays in that package's config.
+pub fn plan_monorepo(packages: &[WizardPackage]) -> Vec<DetectedTool> {
+    let mut by_tool: Vec<(String, Vec<&DetectedTool>)> = Vec::new();
+    for tool in packages.iter().flat_map(|p| &p.tools) {
+        match by_tool.iter_mut().find(|(name, _)| *name == tool.name) {
+            Some((_, found)) => found.push(tool),
+            None => by_tool.push((tool.name.clone(), vec![tool])),
+        }
+    }
+    by_tool.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut plan = Vec::new();
+    for (_, found) in by_tool {
+        if let [only] = found.as_slice() {
+            if !only.dir.is_empty() {
+                plan.push((*only).clone());
+                continue;
+            }
+        }
+        // Most common pinned version; ties go to the earliest (root first)
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for t in found.iter().filter(|t| !is_floating(&t.version)) {
+            match counts.iter_mut().find(|(v, _)| *v == t.version) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((&t.version, 1)),
+            }
+        }
+        let shared = counts
+            .iter()
+            .rev()
+            .max_by_key(|(_, n)| *n)
+            .map(|(v, _)| *v)
+            .unwrap_or(&found[0].version);
+        let users = found
+            .iter()
+            .filter(|t| t.version == shared || is_floating(&t.version))
+            .count();
+        let base = found.iter().find(|t| t.version == shared).unwrap_or(&found[0]);
+        let mut root = (*base).clone();
+        root.dir = String::new();
+        if found.len() > 1 {
+            root.source = format!("shared by {users} of {} packages", found.len());
+        }
+        plan.push(root);
+        plan.extend(
+            found
+                .iter()
+                .filter(|t| t.version != shared && !is_floating(&t.version))
+                .map(|t| (*t).clone()),
+        );
+    }
+    // Root entries first, then each package's overrides in path order
+    plan.sort_by(|a, b| a.dir.cmp(&b.dir).then_with(|| a.name.cmp(&b.name)));
+    plan
+}
+
+// ── Helpers ─────────────────────────────────────────────────────────────────
+
+fn read(dir: &Path, file: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(file)).ok()
+}
+
+/// First non-empty, non-comment line of a pin file like `.nvmrc`.
+fn read_pin(dir: &Path, file: &str) -> Option<String> {
+    read(dir, file)?
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.trim_start_matches('v').to_string())
+}
+
+fn read_json(dir: &Path, file: &str) -> Option<serde_json::Value> {
+    serde_json::from_str(&read(dir, file)?).ok()
+}
+
+fn read_toml(dir: &Path, file: &str) -> Option<toml::Value> {
+    toml::from_str(&read(dir, file)?).ok()
+}
+
+/// Turn a version constraint (`>=18`, `^20.1.0`, `~> 1.15`, `3.x`,
+/// `>=3.9,<4`, `^18 || ^20`) into the version prefix mise should install.
+/// Alternatives pick the newest branch; ranges use their lower bound.
+fn constraint_to_prefix(constraint: &str) -> Option<String> {
+    constraint.split("||").filter_map(branch_prefix).max_by_key(|prefix| {
+        prefix.split('.').map(|p| p.parse::<u64>().unwrap_or(0)).collect::<Vec<_>>()
+    })
+}
+
+/// Prefix for one `||` alternative of a constraint.
+fn branch_prefix(branch: &str) -> Option<String> {
+    // Re-join operators written apart from their version: `~> 1.15`, `>= 18`
+    let mut comparators = Vec::new();
+    let mut pending = String::new();
+    for token in branch.split([',', ' ']).filter(|t| !t.is_empty()) {
+        pending.push_str(token);
+        if token.chars().any(|c| c.is_ascii_alphanumeric() || c == '*') {
+            comparators.push(std::mem::take(&mut pending));
+        }
+    }
+    let lower = comparators
+        .iter()
+        .find(|c| !c.starts_with('<') && !c.starts_with("!="))?;
+    let caret = lower.starts_with('^');
+    let version = lower.trim_start_matches(['>', '=', '^', '~', 'v']);
+    let parts: Vec<&str> = version
+        .split('.')
+        .take_while(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    // `^20.1.0` accepts any 20.x, so the major is the meaningful pin
+    let keep = if caret && parts[0] != "0" { 1 } else { parts.len() };
+    Some(parts[..keep].join("."))
+}
+
+// ── Detectors ───────────────────────────────────────────────────────────────
+
+fn detect_node(dir: &Path) -> Vec<Detection> {
+    for pin in [".nvmrc", ".node-version"] {
+        if let Some(v) = read_pin(dir, pin) {
+            let v = match v.as_str() {
+                "lts/*" | "node" => "lts".to_string(),
+                _ => v,
+            };
+            return vec![Detection::pinned("node", v, pin)];
+        }
+    }
+    let Some(pkg) = read_json(dir, "package.json") else {
+        return Vec::new();
+    };
+    match pkg["engines"]["node"].as_str().and_then(constraint_to_prefix) {
+        Some(v) => vec![Detection::pinned("node", v, "package.json (engines)")],
+        None => vec![Detection::fallback("node", "lts", "package.json")],
+    }
+}
+
+/// JavaScript package managers mise can install directly.
+const PACKAGE_MANAGERS: [&str; 3] = ["pnpm", "yarn", "bun"];
+
+/// `packageManager: "pnpm@9.1.0"` (corepack) plus `engines.pnpm` / `engines.yarn`.
+fn detect_package_manager(dir: &Path) -> Vec<Detection> {
+    let Some(pkg) = read_json(dir, "package.json") else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    if let Some((name, version)) = pkg["packageManager"]
+        .as_str()
+        .and_then(|pm| pm.split_once('@'))
+    {
+        // Strip a corepack integrity suffix: `pnpm@9.1.0+sha512.abc`
+        let version = version.split('+').next().unwrap_or(version);
+        if let Some(tool) = PACKAGE_MANAGERS.into_iter().find(|pm| *pm == name) {
+            found.push(Detection::pinned(tool, version, "package.json (packageManager)"));
+        }
+    }
+    for tool in PACKAGE_MANAGERS {
+        if found.iter().any(|d| d.tool == tool) {
+            continue;
+        }
+        if let Some(v) = pkg["engines"][tool].as_str().and_then(constraint_to_prefix) {
+            found.push(Detection::pinned(tool, v, "package.json (engines)"));
+        }
+    }
+    found
+}
+
+fn detect_bun(dir: &Path) -> Vec<Detection> {
+    ["bun.lockb", "bun.lock", "bunfig.toml"]
+        .into_iter()
+        .find(|f| dir.join(f).exists())
+        .map(|f| vec![Detection::fallback("bun", "latest", f)])
+        .unwrap_or_default()
+}
+
+fn detect_deno(dir: &Path) -> Vec<Detection> {
+    ["deno.json", "deno.jsonc"]
+        .into_iter()
+        .find(|f| dir.join(f).exists())
+        .map(|f| vec![Detection::fallback("deno", "latest", f)])
+        .unwrap_or_default()
+}
+
+/// `rust-toolchain.toml` channel, legacy `rust-toolchain`, then `rust-version` in Cargo.toml.
+fn detect_rust(dir: &Path) -> Vec<Detection> {
+    if let Some(channel) = read_toml(dir, "rust-toolchain.toml")
+        .and_then(|t| t.get("toolchain")?.get("channel")?.as_str().map(String::from))
+    {
+        return vec![Detection::pinned("rust", channel, "rust-toolchain.toml")];
+    }
+    if let Some(v) = read_pin(dir, "rust-toolchain") {
+        return vec![Detection::pinned("rust", v, "rust-toolchain")];
+    }
+    let Some(cargo) = read_toml(dir, "Cargo.toml") else {
+        return Vec::new();
+    };
+    let msrv = cargo
+        .get("package")
+        .or_else(|| cargo.get("workspace")?.get("package"))
+        .and_then(|p| p.get("rust-version")?.as_str().map(String::from));
+    match msrv {
+        Some(v) => vec![Detection::pinned("rust", v, "Cargo.toml (rust-version)")],
+        None => vec![Detection::fallback("rust", "stable", "Cargo.toml")],
+    }
+}
+
+/// The `toolchain` directive wins over `go`, which is only a minimum.
+fn detect_go(dir: &Path) -> Vec<Detection> {
+    let Some(gomod) = read(dir, "go.mod") else {
+        return Vec::new();
+    };
+    let directive = |name: &str| {
+        gomod.lines().find_map(|l| {
+            let rest = l.trim().strip_prefix(name)?.strip_prefix(' ')?;
+            Some(rest.trim().trim_start_matches("go").to_string())
+        })
+    };
+    if let Some(v) = directive("toolchain").filter(|v| !v.is_empty() && v != "default") {
+        return vec![Detection::pinned("go", v, "go.mod (toolchain)")];
+    }
+    match directive("go") {
+        Some(v) if !v.is_empty() => vec![Detection::pinned("go", v, "go.mod")],
+        _ => vec![Detection::fallback("go", "latest", "go.mod")],
+    }
+}
+
+fn detect_python(dir: &Path) -> Vec<Detection> {
+    if let Some(v) = read_pin(dir, ".python-version") {
+        return vec![Detection::pinned("python", v, ".python-version")];
+    }
+    if let Some(pyproject) = read_toml(dir, "pyproject.toml") {
+        let requires = pyproject
+            .get("project")
+            .and_then(|p| p.get("requires-python")?.as_str().map(String::from))
+            .or_else(|| {
+                let poetry = pyproject.get("tool")?.get("poetry")?;
+                poetry.get("dependencies")?.get("python")?.as_str().map(String::from)
+            });
+        return match requires.as_deref().and_then(constraint_to_prefix) {
+            Some(v) => vec![Detection::pinned("python", v, "pyproject.toml (requires-python)")],
+            None => vec![Detection::fallback("python", "latest", "pyproject.toml")],
+        };
+    }
+    if dir.join("requirements.txt").exists() {
+        return vec![Detection::fallback("python", "latest", "requirements.txt")];
+    }
+    Vec::new()
+}
+
+/// `.ruby-version`, then a `ruby "3.2.2"` line in the Gemfile.
+fn detect_ruby(dir: &Path) -> Vec<Detection> {
+    if let Some(v) = read_pin(dir, ".ruby-version") {
+        return vec![Detection::pinned("ruby", v, ".ruby-version")];
+    }
+    let Some(gemfile) = read(dir, "Gemfile") else {
+        return Vec::new();
+    };
+    let pinned = gemfile.lines().find_map(|l| {
+        let rest = l.trim().strip_prefix("ruby ")?;
+        constraint_to_prefix(rest.trim().trim_matches(['"', '\'']))
+    });
+    match pinned {
+        Some(v) => vec![Detection::pinned("ruby", v, "Gemfile")],
+        None => vec![Detection::fallback("ruby", "latest", "Gemfile")],
+    }
+}
+
+fn detect_php(dir: &Path) -> Vec<Detection> {
+    let Some(composer) = read_json(dir, "composer.json") else {
+        return Vec::new();
+    };
+    match composer["require"]["php"].as_str().and_then(constraint_to_prefix) {
+        Some(v) => vec![Detection::pinned("php", v, "composer.json (require)")],
+        None => vec![Detection::fallback("php", "latest", "composer.json")],
+    }
+}
+
+fn detect_terraform(dir: &Path) -> Vec<Detection> {
+    read_pin(dir, ".terraform-version")
+        .map(|v| vec![Detection::pinned("terraform", v, ".terraform-version")])
+        .unwrap_or_default()
+}
+
+/// `sdk.version` in `global.json`.
+fn detect_dotnet(dir: &Path) -> Vec<Detection> {
+    let Some(global) = read_json(dir, "global.json") else {
+        return Vec::new();
+    };
+    match global["sdk"]["version"].as_str() {
+        Some(v) => vec![Detection::pinned("dotnet", v, "global.json")],
+        None => vec![Detection::fallback("dotnet", "latest", "global.json")],
+    }
+}
+
+/// `.sdkmanrc` lines like `java=17.0.2-tem` or `gradle=8.5`.
+fn detect_sdkman(dir: &Path) -> Vec<Detection> {
+    let Some(rc) = read(dir, ".sdkmanrc") else {
+        return Vec::new();
+    };
+    rc.lines()
+        .filter(|l| !l.trim().starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .filter_map(|(candidate, version)| {
+            let version = version.trim();
+            let (tool, version) = match candidate.trim() {
+                "java" => ("java", sdkman_java_version(version)),
+                "gradle" => ("gradle", version.to_string()),
+                "maven" => ("maven", version.to_string()),
+                "kotlin" => ("kotlin", version.to_string()),
+                "scala" => ("scala", version.to_string()),
+                "sbt" => ("sbt", version.to_string()),
+                _ => return None,
+            };
+            Some(Detection::pinned(tool, version, ".sdkmanrc"))
+        })
+        .collect()
+}
+
+/// Translate an SDKMAN java identifier (`17.0.2-tem`) to mise's `vendor-version` form.
+fn sdkman_java_version(id: &str) -> String {
+    let Some((version, vendor)) = id.rsplit_once('-') else {
+        return id.to_string();
+    };
+    let vendor = match vendor {
+        "tem" => "temurin",
+        "zulu" => "zulu",
+        "amzn" => "corretto",
+        "librca" => "liberica",
+        "ms" => "microsoft",
+        "sem" => "semeru",
+        "graalce" => "graalvm-community",
+        _ => return version.to_string(),
+    };
+    format!("{vendor}-{version}")
+}
+
+/// `.java-version`, then the release level in `pom.xml` or a Gradle build.
+/// Maven and Gradle themselves are suggested when the project has no wrapper.
+fn detect_java(dir: &Path) -> Vec<Detection> {
+    let mut found = Vec::new();
+    if let Some(v) = read_pin(dir, ".java-version") {
+        found.push(Detection::pinned("java", v, ".java-version"));
+    }
+    if let Some(pom) = read(dir, "pom.xml") {
+        let release = [
+            "maven.compiler.release",
+            "java.version",
+            "maven.compiler.source",
+            "release",
+        ]
+        .iter()
+        .find_map(|tag| xml_tag(&pom, tag));
+        match release {
+            Some(v) => found.push(Detection::pinned("java", v, "pom.xml")),
+            None => found.push(Detection::fallback("java", "latest", "pom.xml")),
+        }
+        if !dir.join("mvnw").exists() {
+            found.push(Detection::fallback("maven", "latest", "pom.xml"));
+        }
+    }
+    let gradle = ["build.gradle", "build.gradle.kts"]
+        .into_iter()
+        .find_map(|f| read(dir, f).map(|s| (f, s)));
+    if let Some((file, build)) = gradle {
+        match gradle_java_version(&build) {
+            Some(v) => found.push(Detection::pinned("java", v, file)),
+            None => found.push(Detection::fallback("java", "latest", file)),
+        }
+        if !dir.join("gradlew").exists() {
+            found.push(Detection::fallback("gradle", "latest", file));
+        }
+    }
+    found
+}
+
+fn xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}>");
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find('<')? + start;
+    let value = xml[start..end].trim();
+    // Skip unresolved property references like `${java.version}`
+    (!value.is_empty() && !value.starts_with("${"))
+        .then(|| value.strip_prefix("1.").unwrap_or(value).to_string())
+}
+
+/// `JavaLanguageVersion.of(17)`, `JavaVersion.VERSION_17` or `sourceCompatibility = '17'`.
+fn gradle_java_version(build: &str) -> Option<String> {
+    let digits = |s: &str| -> Option<String> {
+        let v: String = s
+            .trim_start_matches(['(', '=', ' ', '\'', '"'])
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == '_')
+            .collect();
+        let v = v.replace('_', ".");
+        let v = v.strip_prefix("1.").map(String::from).unwrap_or(v);
+        (!v.is_empty()).then_some(v)
+    };
+    for marker in ["JavaLanguageVersion.of", "JavaVersion.VERSION_", "sourceCompatibility"] {
+        if let Some(i) = build.find(marker) {
+            if let Some(v) = digits(&build[i + marker.len()..]) {
+                return Some(v);
+            }
+        }
+    }
+    None
+}
+
+/// `elixir: "~> 1.15"` in the `project` keyword list of `mix.exs`.
+fn detect_elixir(dir: &Path) -> Vec<Detection> {
+    let Some(mix) = read(dir, "mix.exs") else {
+        return Vec::new();
+    };
+    let pinned = mix.lines().find_map(|l| {
+        let rest = &l[l.find("elixir:")? + "elixir:".len()..];
+        let quoted = rest.trim().strip_prefix('"')?;
+        constraint_to_prefix(&quoted[..quoted.find('"')?])
+    });
+    let mut found = vec![match pinned {
+        Some(v) => Detection::pinned("elixir", v, "mix.exs"),
+        None => Detection::fallback("elixir", "latest", "mix.exs"),
+    }];
+    found.push(Detection::fallback("erlang", "latest", "mix.exs"));
+    found
+}
+
+// ── Task importers ──────────────────────────────────────────────────────────
+
+/// A task found by an importer, before name collisions are resolved.
+struct ImportedTask {
+    /// Prefix used when the name collides with an earlier import (`make:build`).
+    prefix: &'static str,
+    name: String,
+    run: String,
+    description: String,
+    source: String,
+}
+
+type Importer = fn(&Path) -> Vec<ImportedTask>;
+
+/// Importers in priority order: on a name collision the earlier one keeps the
+/// plain name and later ones are prefixed.
+const IMPORTERS: &[Importer] = &[
+    import_package_scripts,
+    import_makefile,
+    import_justfile,
+    import_cargo_aliases,
+    import_pyproject_scripts,
+];
+
+/// Import existing scripts in `dir` as candidate mise tasks.
+pub fn detect_tasks(dir: &Path, rel: &str) -> Vec<DetectedTask> {
+    let mut tasks: Vec<DetectedTask> = Vec::new();
+    for importer in IMPORTERS {
+        for t in importer(dir) {
+            let name = if tasks.iter().any(|e| e.name == t.name) {
+                format!("{}:{}", t.prefix, t.name)
+            } else {
+                t.name
+            };
+            if tasks.iter().any(|e| e.name == name) {
+                continue;
+            }
+            tasks.push(DetectedTask {
+                name,
+                run: t.run,
+                description: t.description,
+                source: t.source,
+                enabled: true,
+                dir: rel.to_string(),
+            });
+        }
+    }
+    tasks
+}
+
+/// `scripts` in `package.json`, run through the package manager the lockfile
+/// implies. npm lifecycle hooks (`prepare`, `preinstall`, `prebuild`, ...) are skipped.
+fn import_package_scripts(dir: &Path) -> Vec<ImportedTask> {
+    let Some(pkg) = read_json(dir, "package.json") else {
+        return Vec::new();
+    };
+    let Some(scripts) = pkg["scripts"].as_object() else {
+        return Vec::new();
+    };
+    let manager = [
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+        ("bun.lockb", "bun"),
+        ("bun.lock", "bun"),
+    ]
+    .into_iter()
+    .find(|(lock, _)| dir.join(lock).exists())
+    .map(|(_, pm)| pm.to_string())
+    .or_else(|| {
+        let pm = pkg["packageManager"].as_str()?.split('@').next()?;
+        PACKAGE_MANAGERS.contains(&pm).then(|| pm.to_string())
+    })
+    .unwrap_or_else(|| "npm".to_string());
+
+    let lifecycle = ["install", "prepare", "prepublishOnly", "postinstall", "preinstall"];
+    let is_hook = |name: &str| {
+        lifecycle.contains(&name)
+            || ["pre", "post"].iter().any(|p| {
+                name.strip_prefix(p)
+                    .is_some_and(|base| scripts.contains_key(base))
+            })
+    };
+    scripts
+        .iter()
+        .filter(|(name, _)| !is_hook(name))
+        .filter_map(|(name, cmd)| {
+            Some(ImportedTask {
+                prefix: "npm",
+                name: name.clone(),
+                run: format!("{manager} run {name}"),
+                description: cmd.as_str()?.to_string(),
+                source: "package.json".to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Makefile targets. A `## text` suffix or a `# text` line just above the
+/// target becomes the description; pattern rules, special targets and
+/// variable assignments are skipped.
+fn import_makefile(dir: &Path) -> Vec<ImportedTask> {
+    let Some((file, make)) = ["Makefile", "makefile", "GNUmakefile"]
+        .into_iter()
+        .find_map(|f| read(dir, f).map(|s| (f, s)))
+    else {
+        return Vec::new();
+    };
+    let mut tasks = Vec::new();
+    let mut comment: Option<String> = None;
+    for line in make.lines() {
+        if let Some(c) = line.strip_prefix('#') {
+            comment = Some(c.trim_start_matches('#').trim().to_string());
+            continue;
+        }
+        let doc = comment.take();
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((head, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') || head.contains(['=', '%', '$', '(']) {
+            continue;
+        }
+        let inline = rest.split_once("##").map(|(_, d)| d.trim().to_string());
+        for target in head.split_whitespace().filter(|t| !t.starts_with('.')) {
+            tasks.push(ImportedTask {
+                prefix: "make",
+                name: target.to_string(),
+                run: format!("make {target}"),
+                description: inline.clone().or_else(|| doc.clone()).unwrap_or_default(),
+                source: file.to_string(),
+            });
+        }
+    }
+    tasks
+}
+
+/// justfile recipes with their `#` doc comments. Settings, aliases, variables
+/// and private (`_`-prefixed or `[private]`) recipes are skipped; a leading `@` is dropped.
+fn import_justfile(dir: &Path) -> Vec<ImportedTask> {
+    let Some((file, just)) = ["justfile", "Justfile", ".justfile"]
+        .into_iter()
+        .find_map(|f| read(dir, f).map(|s| (f, s)))
+    else {
+        return Vec::new();
+    };
+    let mut tasks = Vec::new();
+    let mut comment: Option<String> = None;
+    let mut private = false;
+    for line in just.lines() {
+        if let Some(c) = line.strip_prefix('#') {
+            comment = Some(c.trim().to_string());
+            continue;
+        }
+        // Attributes like `[group('ci')]` sit between a recipe's doc comment and its header
+        if let Some(attr) = line.strip_prefix('[') {
+            private |= attr
+                .trim_end()
+                .trim_end_matches(']')
+                .split(',')
+                .any(|a| a.trim() == "private");
+            continue;
+        }
+        let doc = comment.take();
+        let hidden = std::mem::take(&mut private);
+        if line.starts_with([' ', '\t']) || line.contains(":=") {
+            continue;
+        }
+        let Some((head, _)) = line.split_once(':') else {
+            continue;
+        };
+        // A leading `@` only silences the recipe's command echo
+        let Some(name) = head.split_whitespace().next().map(|n| n.trim_start_matches('@')) else {
+            continue;
+        };
+        let keyword = ["set", "alias", "export", "import", "mod"].contains(&name);
+        if keyword || name.starts_with('_') || hidden {
+            continue;
+        }
+        tasks.push(ImportedTask {
+            prefix: "just",
+            name: name.to_string(),
+            run: format!("just {name}"),
+            description: doc.unwrap_or_default(),
+            source: file.to_string(),
+        });
+    }
+    tasks
+}
+
+/// `[alias]` entries in `.cargo/config.toml`.
+fn import_cargo_aliases(dir: &Path) -> Vec<ImportedTask> {
+    let Some((file, config)) = [".cargo/config.toml", ".cargo/config"]
+        .into_iter()
+        .find_map(|f| read_toml(dir, f).map(|t| (f, t)))
+    else {
+        return Vec::new();
+    };
+    let Some(aliases) = config.get("alias").and_then(|a| a.as_table()) else {
+        return Vec::new();
+    };
+    aliases
+        .iter()
+        .map(|(name, expansion)| {
+            let expansion = match expansion {
+                toml::Value::Array(parts) => parts
+                    .iter()
+                    .filter_map(|p| p.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                other => other.as_str().unwrap_or_default().to_string(),
+            };
+            ImportedTask {
+                prefix: "cargo",
+                name: name.clone(),
+                run: format!("cargo {name}"),
+                description: format!("cargo {expansion}"),
+                source: file.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// `[project.scripts]` console scripts plus Poetry and PDM script tables.
+fn import_pyproject_scripts(dir: &Path) -> Vec<ImportedTask> {
+    let Some(pyproject) = read_toml(dir, "pyproject.toml") else {
+        return Vec::new();
+    };
+    let tool = pyproject.get("tool");
+    let tables = [
+        (pyproject.get("project").and_then(|p| p.get("scripts")), ""),
+        (tool.and_then(|t| t.get("poetry")?.get("scripts")), "poetry run "),
+        (tool.and_then(|t| t.get("pdm")?.get("scripts")), "pdm run "),
+    ];
+    let mut tasks = Vec::new();
+    for (table, runner) in tables {
+        let Some(table) = table.and_then(|t| t.as_table()) else {
+            continue;
+        };
+        for (name, target) in table {
+            let target = match target {
+                toml::Value::String(s) => s.clone(),
+                // PDM `{ cmd = "..." }` / `{ call = "..." }` forms
+                toml::Value::Table(t) => ["cmd", "call", "shell", "help"]
+                    .iter()
+                    .find_map(|k| t.get(*k)?.as_str().map(String::from))
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+            tasks.push(ImportedTask {
+                prefix: "py",
+                name: name.clone(),
+                run: format!("{runner}{name}"),
+                description: target,
+                source: "pyproject.toml".to_string(),
+            });
+        }
+    }
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constraint_exact_and_partial_versions() {
+        assert_eq!(constraint_to_prefix("18").as_deref(), Some("18"));
+        assert_eq!(constraint_to_prefix("3.12.7").as_deref(), Some("3.12.7"));
+        assert_eq!(constraint_to_prefix("v20.11.0").as_deref(), Some("20.11.0"));
+    }
+
+    #[test]
+    fn constraint_lower_bounds() {
+        assert_eq!(constraint_to_prefix(">=18").as_deref(), Some("18"));
+        assert_eq!(constraint_to_prefix(">= 18.2").as_deref(), Some("18.2"));
+        assert_eq!(constraint_to_prefix(">=3.9,<4").as_deref(), Some("3.9"));
+        assert_eq!(constraint_to_prefix("<4, >=3.9").as_deref(), Some("3.9"));
+        assert_eq!(constraint_to_prefix("==3.11.*").as_deref(), Some("3.11"));
+    }
+
+    #[test]
+    fn constraint_caret_keeps_major() {
+        assert_eq!(constraint_to_prefix("^20.1.0").as_deref(), Some("20"));
+        assert_eq!(constraint_to_prefix("^0.8.3").as_deref(), Some("0.8.3"));
+    }
+
+    #[test]
+    fn constraint_tilde_forms() {
+        assert_eq!(constraint_to_prefix("~> 1.15").as_deref(), Some("1.15"));
+        assert_eq!(constraint_to_prefix("~>1.15.2").as_deref(), Some("1.15.2"));
+        assert_eq!(constraint_to_prefix("~3.2.1").as_deref(), Some("3.2.1"));
+    }
+
+    #[test]
+    fn constraint_wildcards() {
+        assert_eq!(constraint_to_prefix("3.x").as_deref(), Some("3"));
+        assert_eq!(constraint_to_prefix("20.*").as_deref(), Some("20"));
+        assert_eq!(constraint_to_prefix("*"), None);
+    }
+
+    #[test]
+    fn constraint_alternatives_pick_newest_branch() {
+        assert_eq!(constraint_to_prefix("^18 || ^20").as_deref(), Some("20"));
+        assert_eq!(constraint_to_prefix("^20 || ^18").as_deref(), Some("20"));
+        assert_eq!(constraint_to_prefix(">=14 <15 || >=16.9").as_deref(), Some("16.9"));
+        assert_eq!(constraint_to_prefix("18.x || 9.x").as_deref(), Some("18"));
+    }
+
+    #[test]
+    fn constraint_without_lower_bound() {
+        assert_eq!(constraint_to_prefix("<4"), None);
+        assert_eq!(constraint_to_prefix(""), None);
+        assert_eq!(constraint_to_prefix("latest"), None);
+    }
+}
@@ -80,6 +80,7 @@ fn map_key(code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
     if modifiers.contains(KeyModifiers::CONTROL) {
         return match code {
             KeyCode::Char('c') | KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Char('n') => Some(Action::EditorNewline),
             _ => None,
         };
     }
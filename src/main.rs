@@ -1,16 +1,20 @@
 mod action;
 mod app;
 mod config;
+mod detect;
 mod event;
 mod mise;
 mod model;
+mod report;
 mod theme;
 mod tui;
 mod ui;
+mod validate;
 
 use action::Action;
 use app::{App, Popup, Tab};
-use model::WizardStep;
+use model::{DriftFix, FleetOp, TrustOp, WizardStep};
+use color_eyre::eyre::eyre;
 use color_eyre::Result;
 use event::EventHandler;
 use notify::{Config as NotifyConfig, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
@@ -19,11 +23,17 @@ use std::sync::mpsc as std_mpsc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return run_cli(&args).await;
+    }
+
     let mut terminal = tui::init()?;
 
     let (action_tx, mut action_rx) = mpsc::unbounded_channel::<Action>();
@@ -33,76 +43,9 @@ async fn main() -> Result<()> {
     // Start fetching data
     app.start_fetch();
 
-    // Filesystem watcher for drift indicator (DRFT-02)
-    // Watches .mise.toml in CWD and ~/.config/mise/config.toml.
-    // Uses a std::sync::mpsc channel bridged to tokio via Arc<Mutex<Receiver>>.
-    {
-        let watch_tx = action_tx.clone();
-        tokio::spawn(async move {
-            // Build list of paths to watch
-            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-            let mise_toml = cwd.join(".mise.toml");
-
-            let global_config =
-                dirs::config_dir().map(|p| p.join("mise").join("config.toml"));
-
-            // std channel — notify requires a std Sender
-            let (std_tx, std_rx) = std_mpsc::channel::<()>();
-            let std_rx = Arc::new(Mutex::new(std_rx));
-
-            let mut watcher = match RecommendedWatcher::new(
-                move |res: notify::Result<notify::Event>| {
-                    if let Ok(event) = res {
-                        match event.kind {
-                            EventKind::Create(_)
-                            | EventKind::Modify(_)
-                            | EventKind::Remove(_) => {
-                                let _ = std_tx.send(());
-                            }
-                            _ => {}
-                        }
-                    }
-                },
-                NotifyConfig::default()
-                    .with_poll_interval(Duration::from_millis(200)),
-            ) {
-                Ok(w) => w,
-                Err(_) => return, // Watcher unavailable — graceful degradation
-            };
-
-            // Watch .mise.toml (non-recursive; file may not exist yet — errors silently ignored)
-            let _ = watcher.watch(&mise_toml, RecursiveMode::NonRecursive);
-            if let Some(ref gc) = global_config {
-                let _ = watcher.watch(gc, RecursiveMode::NonRecursive);
-            }
-
-            // Debounce loop: coalesce burst writes into one CheckDrift every ~200ms
-            loop {
-                let std_rx_clone = Arc::clone(&std_rx);
-                let received = tokio::task::spawn_blocking(move || {
-                    let rx = std_rx_clone.lock().unwrap();
-                    rx.recv_timeout(Duration::from_millis(500))
-                })
-                .await;
-
-                match received {
-                    Ok(Ok(())) => {
-                        // Drain any additional events accumulated during debounce window
-                        tokio::time::sleep(Duration::from_millis(200)).await;
-                        {
-                            let rx = std_rx.lock().unwrap();
-                            while rx.try_recv().is_ok() {}
-                        }
-                        let _ = watch_tx.send(Action::CheckDrift);
-                    }
-                    Ok(Err(_)) => {
-                        // recv_timeout timed out — just loop (keep watching)
-                    }
-                    Err(_) => break, // spawn_blocking panicked — exit watcher
-                }
-            }
-        });
-    }
+    // Re-registered whenever the working directory changes
+    let mut watched_dir = app.work_dir.clone();
+    let mut watcher = spawn_drift_watcher(action_tx.clone(), watched_dir.clone());
 
     loop {
         // Render
@@ -117,6 +60,20 @@ async fn main() -> Result<()> {
                     remap_version_picker_action(event_action)
                 } else if is_scan_config_active(&app) {
                     remap_scan_config_action(event_action)
+                } else if is_uninstall_guard_active(&app) {
+                    remap_uninstall_guard_action(event_action)
+                } else if is_prune_active(&app) {
+                    remap_prune_action(event_action)
+                } else if is_editor_conflict_active(&app) {
+                    remap_editor_conflict_action(event_action)
+                } else if is_trust_manager_active(&app) {
+                    remap_trust_manager_action(event_action)
+                } else if is_change_dir_active(&app) || is_project_tags_active(&app) {
+                    remap_change_dir_action(event_action)
+                } else if is_drift_breakdown_active(&app) {
+                    remap_drift_breakdown_action(event_action)
+                } else if is_template_picker_active(&app) {
+                    remap_template_picker_action(event_action)
                 } else if is_wizard_active(&app) {
                     remap_wizard_action(event_action)
                 } else if app.search_active && app.popup.is_none() {
@@ -131,6 +88,12 @@ async fn main() -> Result<()> {
             }
         }
 
+        if app.work_dir != watched_dir {
+            watcher.abort();
+            watched_dir = app.work_dir.clone();
+            watcher = spawn_drift_watcher(action_tx.clone(), watched_dir.clone());
+        }
+
         if app.should_quit {
             break;
         }
@@ -140,6 +103,132 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+const USAGE: &str = "Usage: misetui [--export <md|html|json> [--output <file>]]
+
+Without arguments, starts the TUI.
+
+  --export <format>   Scan projects and print a health report (md, html or json)
+  -o, --output <file> Write the report to <file> instead of stdout
+  -h, --help          Show this help";
+
+/// Headless entry point for command-line flags.
+async fn run_cli(args: &[String]) -> Result<()> {
+    let mut format = None;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--export" => {
+                let name = args.next().ok_or_else(|| eyre!("--export needs a format\n\n{USAGE}"))?;
+                format = Some(report::ReportFormat::parse(name).map_err(|e| eyre!(e))?);
+            }
+            "-o" | "--output" => {
+                output = Some(PathBuf::from(
+                    args.next().ok_or_else(|| eyre!("{arg} needs a file\n\n{USAGE}"))?,
+                ));
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            other => return Err(eyre!("Unknown argument '{other}'\n\n{USAGE}")),
+        }
+    }
+    let Some(format) = format else {
+        return Err(eyre!("--output requires --export\n\n{USAGE}"));
+    };
+
+    let tools = mise::fetch_tools().await.map_err(|e| eyre!(e))?;
+    let mut projects = tokio::task::spawn_blocking(move || {
+        let config = config::MisetuiConfig::load();
+        mise::scan_projects(&config, &tools, &|_| {})
+    })
+    .await?;
+    // Same order as the Projects tab: favorites, then groups, then the rest
+    projects.sort_by(|a, b| a.section().cmp(&b.section()));
+    let projects: Vec<&model::MiseProject> = projects.iter().collect();
+    let report = report::render(&projects, format, report::now_secs());
+    match output {
+        Some(path) => {
+            std::fs::write(&path, report)
+                .map_err(|e| eyre!("Failed to write {}: {e}", path.display()))?;
+            eprintln!("Wrote {} project(s) to {}", projects.len(), path.display());
+        }
+        None => print!("{report}"),
+    }
+    Ok(())
+}
+
+/// Filesystem watcher for drift indicator (DRFT-02)
+/// Watches .mise.toml in `dir` and ~/.config/mise/config.toml.
+/// Uses a std::sync::mpsc channel bridged to tokio via Arc<Mutex<Receiver>>.
+/// Aborting the returned task drops the watcher.
+fn spawn_drift_watcher(watch_tx: mpsc::UnboundedSender<Action>, dir: PathBuf) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // Build list of paths to watch
+        let mise_toml = dir.join(".mise.toml");
+
+        let global_config =
+            dirs::config_dir().map(|p| p.join("mise").join("config.toml"));
+
+        // std channel — notify requires a std Sender
+        let (std_tx, std_rx) = std_mpsc::channel::<()>();
+        let std_rx = Arc::new(Mutex::new(std_rx));
+
+        let mut watcher = match RecommendedWatcher::new(
+            move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    match event.kind {
+                        EventKind::Create(_)
+                        | EventKind::Modify(_)
+                        | EventKind::Remove(_) => {
+                            let _ = std_tx.send(());
+                        }
+                        _ => {}
+                    }
+                }
+            },
+            NotifyConfig::default()
+                .with_poll_interval(Duration::from_millis(200)),
+        ) {
+            Ok(w) => w,
+            Err(_) => return, // Watcher unavailable — graceful degradation
+        };
+
+        // Watch .mise.toml (non-recursive; file may not exist yet — errors silently ignored)
+        let _ = watcher.watch(&mise_toml, RecursiveMode::NonRecursive);
+        if let Some(ref gc) = global_config {
+            let _ = watcher.watch(gc, RecursiveMode::NonRecursive);
+        }
+
+        // Debounce loop: coalesce burst writes into one CheckDrift every ~200ms
+        loop {
+            let std_rx_clone = Arc::clone(&std_rx);
+            let received = tokio::task::spawn_blocking(move || {
+                let rx = std_rx_clone.lock().unwrap();
+                rx.recv_timeout(Duration::from_millis(500))
+            })
+            .await;
+
+            match received {
+                Ok(Ok(())) => {
+                    // Drain any additional events accumulated during debounce window
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    {
+                        let rx = std_rx.lock().unwrap();
+                        while rx.try_recv().is_ok() {}
+                    }
+                    let _ = watch_tx.send(Action::CheckDrift);
+                }
+                Ok(Err(_)) => {
+                    // recv_timeout timed out — just loop (keep watching)
+                }
+                Err(_) => break, // spawn_blocking panicked — exit watcher
+            }
+        }
+    })
+}
+
 fn is_version_picker_active(app: &App) -> bool {
     matches!(app.popup, Some(Popup::VersionPicker { .. }))
 }
@@ -148,6 +237,38 @@ fn is_scan_config_active(app: &App) -> bool {
     matches!(app.popup, Some(Popup::ScanConfig { .. }))
 }
 
+fn is_uninstall_guard_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::UninstallGuard { .. }))
+}
+
+fn is_prune_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::Prune { .. }))
+}
+
+fn is_trust_manager_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::TrustManager { .. }))
+}
+
+fn is_change_dir_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::ChangeDir { .. }))
+}
+
+fn is_project_tags_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::ProjectTags { .. }))
+}
+
+fn is_drift_breakdown_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::DriftBreakdown))
+}
+
+fn is_template_picker_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::TemplatePicker { .. }))
+}
+
+fn is_editor_conflict_active(app: &App) -> bool {
+    matches!(app.popup, Some(Popup::EditorConflict { .. }))
+}
+
 fn is_wizard_active(app: &App) -> bool {
     app.tab == Tab::Bootstrap && app.wizard.step != WizardStep::Idle
 }
@@ -164,6 +285,7 @@ fn remap_wizard_action(action: Action) -> Action {
             'k' => Action::MoveUp,
             ' ' => Action::WizardToggleTool,
             'a' => Action::WizardToggleAgentFiles,
+            'm' => Action::WizardToggleMonorepo,
             'n' => Action::WizardNextStep,
             'p' => Action::WizardPrevStep,
             'q' | 'Q' => Action::CancelPopup,
@@ -176,6 +298,21 @@ fn remap_wizard_action(action: Action) -> Action {
     }
 }
 
+/// In template picker mode, j/k move and Enter chooses
+fn remap_template_picker_action(action: Action) -> Action {
+    match action {
+        Action::SearchInput(c) => match c {
+            'j' => Action::MoveDown,
+            'k' => Action::MoveUp,
+            'q' => Action::CancelPopup,
+            _ => Action::None,
+        },
+        Action::Confirm | Action::CancelPopup => action,
+        Action::MoveUp | Action::MoveDown | Action::PageUp | Action::PageDown => action,
+        _ => Action::None,
+    }
+}
+
 /// In scan config popup mode, route chars to popup navigation/editing actions
 fn remap_scan_config_action(action: Action) -> Action {
     match action {
@@ -193,6 +330,90 @@ fn remap_scan_config_action(action: Action) -> Action {
     }
 }
 
+/// In uninstall guard mode, every char types into the confirmation field
+fn remap_uninstall_guard_action(action: Action) -> Action {
+    match action {
+        Action::SearchInput(c) => Action::PopupSearchInput(c),
+        Action::SearchBackspace => Action::PopupSearchBackspace,
+        Action::Confirm | Action::CancelPopup => action,
+        _ => Action::None,
+    }
+}
+
+/// In prune popup mode, route chars to candidate selection and policy actions
+fn remap_prune_action(action: Action) -> Action {
+    match action {
+        Action::SearchInput(c) => match c {
+            'j' => Action::MoveDown,
+            'k' => Action::MoveUp,
+            ' ' => Action::PruneToggle,
+            'a' => Action::PruneToggleAll,
+            '+' => Action::PruneKeepMore,
+            '-' => Action::PruneKeepLess,
+            'q' | 'Q' => Action::CancelPopup,
+            _ => Action::None,
+        },
+        Action::Confirm | Action::CancelPopup => action,
+        Action::MoveUp | Action::MoveDown | Action::PageUp | Action::PageDown => action,
+        _ => Action::None,
+    }
+}
+
+/// In editor conflict mode, only merge, reload or cancel are accepted
+fn remap_editor_conflict_action(action: Action) -> Action {
+    match action {
+        Action::SearchInput('m') => Action::EditorMergeExternal,
+        Action::SearchInput('r') => Action::EditorReloadExternal,
+        Action::CancelPopup => action,
+        _ => Action::None,
+    }
+}
+
+/// In trust manager mode, route chars to selection and bulk trust actions
+fn remap_trust_manager_action(action: Action) -> Action {
+    match action {
+        Action::SearchInput(c) => match c {
+            'j' => Action::MoveDown,
+            'k' => Action::MoveUp,
+            ' ' => Action::TrustToggle,
+            'a' => Action::TrustToggleAll,
+            't' => Action::TrustApply(TrustOp::Trust),
+            'u' => Action::TrustApply(TrustOp::Untrust),
+            'i' => Action::TrustApply(TrustOp::Ignore),
+            'q' | 'Q' => Action::CancelPopup,
+            _ => Action::None,
+        },
+        Action::CancelPopup => action,
+        Action::MoveUp | Action::MoveDown | Action::PageUp | Action::PageDown => action,
+        _ => Action::None,
+    }
+}
+
+/// In the change-directory and project-tags prompts, chars are typed into the input
+fn remap_change_dir_action(action: Action) -> Action {
+    match action {
+        Action::SearchInput(c) => Action::PopupSearchInput(c),
+        Action::SearchBackspace => Action::PopupSearchBackspace,
+        Action::Confirm | Action::CancelPopup => action,
+        _ => Action::None,
+    }
+}
+
+/// In the drift breakdown, letters pick a fix
+fn remap_drift_breakdown_action(action: Action) -> Action {
+    match action {
+        Action::SearchInput(c) => match c {
+            'i' => Action::DriftApply(DriftFix::InstallMissing),
+            't' => Action::DriftApply(DriftFix::Trust),
+            'u' => Action::DriftApply(DriftFix::UpgradePins),
+            'q' | 'Q' | 'D' => Action::CancelPopup,
+            _ => Action::None,
+        },
+        Action::CancelPopup | Action::Confirm => action,
+        _ => Action::None,
+    }
+}
+
 /// In normal mode, map char inputs to their bound actions
 fn remap_normal_action(action: Action) -> Action {
     match action {
@@ -206,17 +427,29 @@ fn remap_normal_action(action: Action) -> Action {
             'i' => Action::InstallTool,
             'u' => Action::UpdateTool,
             'd' => Action::EditorDeleteRow,
+            'x' => Action::UninstallTool,
             'a' => Action::EditorAddRow,
             'v' => Action::ShowToolDetail,
             'w' => Action::EditorWrite,
+            'z' => Action::EditorUndo,
+            'Z' => Action::EditorRedo,
             '?' => Action::ShowHelp,
             'r' => Action::Refresh,
             'U' => Action::UseTool,
             'p' => Action::PruneTool,
             't' => Action::TrustConfig,
+            'T' => Action::OpenTrustManager,
             's' => Action::CycleSortOrder,
             'P' => Action::JumpToDriftProject,
+            'D' => Action::OpenDriftBreakdown,
+            'C' => Action::OpenChangeDir,
+            'I' => Action::OpenFleetPlan(FleetOp::InstallMissing),
+            'L' => Action::RegenerateLock,
             'c' => Action::OpenScanConfig,
+            'f' => Action::ToggleFavorite,
+            'g' => Action::CycleProjectGroup,
+            '#' => Action::OpenProjectTags,
+            'E' => Action::OpenExportReport,
             _ => Action::None, // unbound chars do nothing; use / to search
         },
         // Enter is handled contextually in app.rs (popup confirm, tool detail, run task)
@@ -249,6 +482,8 @@ fn remap_editor_popup_action(action: Action) -> Action {
         Action::Confirm => Action::EditorConfirmEdit,
         Action::CancelPopup => Action::EditorCancelEdit,
         Action::NextTab => Action::EditorNextField,
+        Action::PrevTab => Action::EditorPrevField,
+        Action::EditorNewline => action,
         _ => Action::None,
     }
 }
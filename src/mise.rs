@@ -1,17 +1,44 @@
 use crate::config::MisetuiConfig;
 use crate::model::{
-    ConfigFile, DetectedTool, DriftState, EditorEnvRow, EditorRowStatus, EditorState, EditorTab,
-    EditorTaskRow, EditorToolRow, EnvVar, EnvVarEntry, InstalledTool, InstalledToolVersion,
-    MiseProject, MiseSetting, MiseTask, OutdatedEntry, OutdatedTool, ProjectHealthStatus,
-    ProjectToolHealth, PruneCandidate, RegistryEntry,
+    ConfigFile, ConfigKind, DetectedTask, GitInfo, DetectedTool, DriftIssue, DriftReport, DriftState, DriftTool, EditorEnvRow, EditorRowStatus, EditorState, EditorTab,
+    EditorTaskRow, EditorToolRow, EnvRowKind, EnvVar, EnvVarEntry, FleetInstall, FleetOp, FleetPlan, FleetResult, FleetTarget, InstalledTool,
+    InstalledToolVersion, LockState, LockedTool, MiseProject, MiseSetting, MiseTask, OutdatedEntry, OutdatedTool, ProjectHealthStatus,
+    ProjectToolHealth, PruneCandidate, RegistryEntry, ToolUsage, TrustEntry, TrustOp, TrustState,
+    ToolMerge, WizardMergeEntry, WizardPackage,
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::sync::RwLock;
 use tokio::process::Command;
 
+/// Directory mise commands run in once the user switches away from the process CWD.
+static WORK_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// The working context for mise commands: the directory chosen with "change
+/// directory", else the process CWD.
+pub fn work_dir() -> PathBuf {
+    WORK_DIR
+        .read()
+        .ok()
+        .and_then(|dir| dir.clone())
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Global and system config files as mise itself lists them, refreshed by `fetch_config`.
+static GLOBAL_CONFIGS: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// Re-target every later mise command at `dir`.
+pub fn set_work_dir(dir: PathBuf) {
+    if let Ok(mut current) = WORK_DIR.write() {
+        *current = Some(dir);
+    }
+}
+
 async fn run_mise(args: &[&str]) -> Result<String, String> {
     let output = Command::new("mise")
         .args(args)
+        .current_dir(work_dir())
         .output()
         .await
         .map_err(|e| format!("Failed to run mise: {e}"))?;
@@ -39,15 +66,140 @@ pub async fn fetch_registry() -> Result<Vec<RegistryEntry>, String> {
 }
 
 pub async fn fetch_config() -> Result<Vec<ConfigFile>, String> {
-    let json = run_mise(&["config", "ls", "-J"]).await?;
-    let configs: Vec<ConfigFile> =
-        serde_json::from_str(&json).map_err(|e| format!("Parse error: {e}"))?;
+    let (json, globals) = tokio::join!(run_mise(&["config", "ls", "-J"]), list_global_configs());
+    if let (Some(globals), Ok(mut current)) = (globals, GLOBAL_CONFIGS.write()) {
+        *current = globals;
+    }
+    let mut configs: Vec<ConfigFile> =
+        serde_json::from_str(&json?).map_err(|e| format!("Parse error: {e}"))?;
+
+    let trusted = read_trust_dir("trusted-configs");
+    let ignored = read_trust_dir("ignored-configs");
+    for (i, cfg) in configs.iter_mut().enumerate() {
+        let path = std::path::Path::new(&cfg.path);
+        cfg.rank = i + 1;
+        cfg.kind = classify_config(path);
+        cfg.trust = trust_state_for(path, cfg.kind, &trusted, &ignored);
+        cfg.contents = tokio::fs::read_to_string(path).await.unwrap_or_default();
+        if let Some(lockfile) = lockfile_for(path) {
+            cfg.locked = read_lockfile(&lockfile);
+            cfg.lockfile = Some(lockfile.to_string_lossy().to_string());
+        }
+    }
     Ok(configs)
 }
 
+/// The configs mise loads from `/`, where no project config applies: the global
+/// config (wherever `MISE_GLOBAL_CONFIG_FILE`, `MISE_CONFIG_DIR` or the platform put
+/// it), its `conf.d` fragments and the system config. None if mise fails.
+async fn list_global_configs() -> Option<Vec<PathBuf>> {
+    let output = Command::new("mise")
+        .args(["config", "ls", "-J"])
+        .current_dir("/")
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let configs: Vec<ConfigFile> = serde_json::from_slice(&output.stdout).ok()?;
+    Some(configs.into_iter().map(|c| PathBuf::from(c.path)).collect())
+}
+
+/// Global config files from the last `fetch_config`.
+pub fn global_configs() -> Vec<PathBuf> {
+    GLOBAL_CONFIGS.read().map(|g| g.clone()).unwrap_or_default()
+}
+
+/// mise's state directory (`$MISE_STATE_DIR`, else `$XDG_STATE_HOME/mise`, else
+/// `~/.local/state/mise`), where trust and ignore records are kept.
+fn mise_state_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("MISE_STATE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_STATE_HOME") {
+        return Some(PathBuf::from(dir).join("mise"));
+    }
+    dirs::home_dir().map(|h| h.join(".local").join("state").join("mise"))
+}
+
+/// Config paths recorded in a mise state subdirectory. mise stores one symlink per
+/// config, named by a hash of the path and pointing at the canonical config file.
+fn read_trust_dir(name: &str) -> Vec<PathBuf> {
+    read_trust_records(name)
+        .into_iter()
+        .map(|(_, target)| target)
+        .collect()
+}
+
+/// `(record, target)` pairs for each symlink in a mise state subdirectory.
+fn read_trust_records(name: &str) -> Vec<(PathBuf, PathBuf)> {
+    let Some(dir) = mise_state_dir().map(|d| d.join(name)) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let record = e.path();
+            std::fs::read_link(&record).ok().map(|target| (record, target))
+        })
+        .collect()
+}
+
+/// Classify a config file path into its role in the mise hierarchy. Global
+/// configs are the ones mise lists outside any project (see `global_configs`).
+pub fn classify_config(path: &std::path::Path) -> ConfigKind {
+    let canonical = path.canonicalize().ok();
+    let is_global = global_configs()
+        .iter()
+        .any(|g| g == path || Some(g) == canonical.as_ref());
+    if is_global {
+        return ConfigKind::Global;
+    }
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if !file_name.ends_with(".toml") {
+        return ConfigKind::Legacy;
+    }
+    let stem = file_name.trim_start_matches('.').trim_end_matches(".toml");
+    let parts: Vec<&str> = stem.split('.').collect();
+    match parts.as_slice() {
+        [_, .., "local"] => ConfigKind::Local,
+        ["mise", _env] => ConfigKind::EnvSpecific,
+        ["config", _env] => ConfigKind::EnvSpecific,
+        _ => ConfigKind::Project,
+    }
+}
+
+/// Resolve the trust state of `path`: ignored records win, then trusted records;
+/// global configs are implicitly trusted by mise.
+fn trust_state_for(
+    path: &std::path::Path,
+    kind: ConfigKind,
+    trusted: &[PathBuf],
+    ignored: &[PathBuf],
+) -> TrustState {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let listed = |list: &[PathBuf]| list.iter().any(|p| p == path || *p == canonical);
+    if listed(ignored) {
+        TrustState::Ignored
+    } else if kind == ConfigKind::Global || listed(trusted) {
+        TrustState::Trusted
+    } else {
+        TrustState::Untrusted
+    }
+}
+
 pub async fn fetch_doctor() -> Result<Vec<String>, String> {
     let output = Command::new("mise")
         .args(["doctor"])
+        .current_dir(work_dir())
         .output()
         .await
         .map_err(|e| format!("Failed to run mise doctor: {e}"))?;
@@ -64,6 +216,12 @@ pub async fn fetch_versions(tool: &str) -> Result<Vec<String>, String> {
     Ok(versions.into_iter().take(50).collect())
 }
 
+/// Every remote version of `tool`, unlimited (used to validate editor version specs).
+pub async fn fetch_remote_versions(tool: &str) -> Result<Vec<String>, String> {
+    let out = run_mise(&["ls-remote", tool]).await?;
+    Ok(out.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect())
+}
+
 pub async fn install_tool(tool: &str, version: &str) -> Result<String, String> {
     let tool_ver = format!("{tool}@{version}");
     run_mise(&["install", &tool_ver]).await?;
@@ -130,46 +288,200 @@ pub async fn use_tool(tool: &str, version: &str) -> Result<String, String> {
     Ok(format!("Now using {tool_ver}"))
 }
 
-pub async fn prune_dry_run() -> Result<Vec<PruneCandidate>, String> {
+/// List versions `mise prune` would remove, enriched with size, last-used time and
+/// the reason each one is considered unused.
+///
+/// `projects` are the already-scanned Projects-tab entries: a candidate still required
+/// by one of them (but by no tracked config) is reported in `required_by` so the UI
+/// can keep it unchecked.
+pub async fn prune_dry_run(projects: &[MiseProject]) -> Result<Vec<PruneCandidate>, String> {
     let output = Command::new("mise")
         .args(["prune", "--dry-run"])
+        .current_dir(work_dir())
         .output()
         .await
         .map_err(|e| format!("Failed to run mise prune: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        return Err(format!("mise prune failed: {}", first_line(&stderr)));
+    }
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let candidates: Vec<PruneCandidate> = stdout
+    let installed: BTreeMap<String, Vec<InstalledToolVersion>> =
+        serde_json::from_str(&run_mise(&["ls", "-J"]).await?)
+            .map_err(|e| format!("Failed to parse mise ls output: {e}"))?;
+
+    // mise reports dry-run removals on stderr in recent releases and stdout in older ones.
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let mut specs: Vec<(String, String)> = text
         .lines()
-        .filter_map(|line| {
-            let line = line.trim();
-            if line.is_empty() {
-                return None;
-            }
-            // Parse lines like "node@18.0.0" or "node 18.0.0"
-            if let Some((tool, version)) = line.split_once('@') {
-                Some(PruneCandidate {
-                    tool: tool.trim().to_string(),
-                    version: version.trim().to_string(),
-                })
-            } else if let Some((tool, version)) = line.split_once(' ') {
-                Some(PruneCandidate {
-                    tool: tool.trim().to_string(),
-                    version: version.trim().to_string(),
-                })
-            } else {
-                Some(PruneCandidate {
-                    tool: line.to_string(),
-                    version: String::new(),
-                })
-            }
-        })
+        .filter_map(|line| parse_prune_line(line, &installed))
         .collect();
+    specs.sort();
+    specs.dedup();
+
+    let candidates = tokio::task::spawn_blocking({
+        let projects = projects.to_vec();
+        move || {
+            specs
+                .into_iter()
+                .map(|(tool, version)| build_prune_candidate(tool, version, &installed, &projects))
+                .collect()
+        }
+    })
+    .await
+    .map_err(|e| format!("Prune scan failed: {e}"))?;
     Ok(candidates)
 }
 
-pub async fn prune() -> Result<String, String> {
-    run_mise(&["prune", "-y"]).await?;
-    Ok("Pruned unused tool versions".to_string())
+/// Extract `tool@version` from a line of `mise prune --dry-run` output.
+/// Accepts `node@18.0.0`, `mise prune npm:@scope/pkg@1.2.3 (dry-run)` and `node 18.0.0`;
+/// only versions present in `installed` count, so warnings and other noise are ignored.
+fn parse_prune_line(
+    line: &str,
+    installed: &BTreeMap<String, Vec<InstalledToolVersion>>,
+) -> Option<(String, String)> {
+    let is_installed = |tool: &str, version: &str| {
+        installed
+            .get(tool)
+            .is_some_and(|vs| vs.iter().any(|v| v.installed && v.version == version))
+    };
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let at_spec = tokens.iter().find_map(|t| {
+        let (tool, version) = t.rsplit_once('@')?;
+        is_installed(tool, version).then(|| (tool.to_string(), version.to_string()))
+    });
+    if at_spec.is_some() {
+        return at_spec;
+    }
+    match tokens.as_slice() {
+        [tool, version] if is_installed(tool, version) => {
+            Some((tool.to_string(), version.to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn build_prune_candidate(
+    tool: String,
+    version: String,
+    installed: &BTreeMap<String, Vec<InstalledToolVersion>>,
+    projects: &[MiseProject],
+) -> PruneCandidate {
+    let versions = installed.get(&tool).map(|v| v.as_slice()).unwrap_or(&[]);
+
+    let mut sorted: Vec<&str> = versions
+        .iter()
+        .filter(|v| v.installed)
+        .map(|v| v.version.as_str())
+        .collect();
+    sorted.sort_by(|a, b| compare_versions(b, a));
+    let rank = sorted.iter().position(|v| *v == version).unwrap_or(sorted.len());
+
+    let install_path = versions
+        .iter()
+        .find(|v| v.version == version)
+        .and_then(|v| v.install_path.clone())
+        .unwrap_or_default();
+
+    let (size_bytes, last_used_secs) = if install_path.is_empty() {
+        (None, None)
+    } else {
+        let path = std::path::Path::new(&install_path);
+        (dir_size(path), last_used_secs(path))
+    };
+
+    // A requirement like "latest" or "20" resolves to the newest installed match only,
+    // the same way `build_usage_index` attributes usage
+    let required_by: Vec<String> = projects
+        .iter()
+        .filter(|p| {
+            p.tools.iter().any(|t| {
+                let resolved = sorted.iter().find(|v| version_satisfies(v, &t.required));
+                t.tool == tool && resolved == Some(&version.as_str())
+            })
+        })
+        .map(|p| p.name.clone())
+        .collect();
+
+    let mut reason = if required_by.is_empty() {
+        "not referenced by any tracked config".to_string()
+    } else {
+        format!("not in a tracked config, but required by {}", required_by.join(", "))
+    };
+    if let Some(newest) = sorted.first().filter(|v| **v != version) {
+        reason.push_str(&format!("; superseded by {newest}"));
+    }
+
+    PruneCandidate {
+        tool,
+        version,
+        size_bytes,
+        last_used_secs,
+        reason,
+        rank,
+        required_by,
+        selected: true,
+    }
+}
+
+/// Recursively sum file sizes below `path`. Symlinks are not followed.
+fn dir_size(path: &std::path::Path) -> Option<u64> {
+    let meta = std::fs::symlink_metadata(path).ok()?;
+    if !meta.is_dir() {
+        return Some(meta.len());
+    }
+    let mut total = 0;
+    for entry in std::fs::read_dir(path).ok()?.flatten() {
+        total += dir_size(&entry.path()).unwrap_or(0);
+    }
+    Some(total)
+}
+
+/// Seconds since `path` was last accessed (or modified, if atime is unavailable).
+fn last_used_secs(path: &std::path::Path) -> Option<u64> {
+    let meta = std::fs::metadata(path).ok()?;
+    let when = meta.accessed().or_else(|_| meta.modified()).ok()?;
+    age_secs(when)
+}
+
+/// True if installed `version` satisfies the requirement string `required`.
+/// "latest" always satisfies; otherwise an exact match or a dotted prefix match
+/// (e.g. required "3.12" is satisfied by "3.12.7").
+pub fn version_satisfies(version: &str, required: &str) -> bool {
+    required == "latest" || version == required || version.starts_with(&format!("{required}."))
+}
+
+/// Compare two version strings numerically segment by segment ("1.10.0" > "1.9.2").
+/// Non-numeric segments fall back to lexical comparison.
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let seg = |s: &str| -> Vec<String> {
+        s.split(['.', '-', '+'])
+            .map(|p| p.to_string())
+            .collect()
+    };
+    let (sa, sb) = (seg(a), seg(b));
+    for (x, y) in sa.iter().zip(sb.iter()) {
+        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(nx), Ok(ny)) => nx.cmp(&ny),
+            _ => x.cmp(y),
+        };
+        if ord != std::cmp::Ordering::Equal {
+            return ord;
+        }
+    }
+    sa.len().cmp(&sb.len())
+}
+
+/// Uninstall exactly the given `tool@version` specs in a single `mise uninstall` call.
+pub async fn uninstall_versions(specs: &[String]) -> Result<String, String> {
+    let mut args = vec!["uninstall"];
+    args.extend(specs.iter().map(|s| s.as_str()));
+    run_mise(&args).await?;
+    Ok(format!("Pruned {} version(s)", specs.len()))
 }
 
 pub async fn trust_config(path: &str) -> Result<String, String> {
@@ -177,43 +489,145 @@ pub async fn trust_config(path: &str) -> Result<String, String> {
     Ok(format!("Trusted {path}"))
 }
 
-#[allow(dead_code)]
 pub async fn untrust_config(path: &str) -> Result<String, String> {
     run_mise(&["trust", "--untrust", path]).await?;
     Ok(format!("Untrusted {path}"))
 }
 
+pub async fn ignore_config(path: &str) -> Result<String, String> {
+    run_mise(&["trust", "--ignore", path]).await?;
+    Ok(format!("Ignored {path}"))
+}
+
+/// List every config path in mise's trust store plus the given known config paths
+/// (from `mise config ls` and scanned projects) that have no trust record yet.
+pub async fn fetch_trust_entries(known: Vec<String>) -> Result<Vec<TrustEntry>, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut entries: Vec<TrustEntry> = Vec::new();
+        for (dir, state) in [
+            ("ignored-configs", TrustState::Ignored),
+            ("trusted-configs", TrustState::Trusted),
+        ] {
+            for (record, target) in read_trust_records(dir) {
+                let path = target.to_string_lossy().to_string();
+                if entries.iter().any(|e| e.path == path) {
+                    continue;
+                }
+                entries.push(TrustEntry {
+                    exists: target.exists(),
+                    path,
+                    state,
+                    record: Some(record),
+                    selected: false,
+                });
+            }
+        }
+
+        for path in known {
+            let p = std::path::Path::new(&path);
+            let canonical = p
+                .canonicalize()
+                .map(|c| c.to_string_lossy().to_string())
+                .unwrap_or_else(|_| path.clone());
+            if entries.iter().any(|e| e.path == path || e.path == canonical) {
+                continue;
+            }
+            let kind = classify_config(p);
+            entries.push(TrustEntry {
+                exists: p.exists(),
+                state: trust_state_for(p, kind, &[], &[]),
+                path,
+                record: None,
+                selected: false,
+            });
+        }
+
+        // Stale records first, then untrusted, then by path.
+        entries.sort_by(|a, b| {
+            a.exists
+                .cmp(&b.exists)
+                .then_with(|| trust_order(a.state).cmp(&trust_order(b.state)))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        entries
+    })
+    .await
+    .map_err(|e| format!("Trust scan failed: {e}"))
+}
+
+fn trust_order(state: TrustState) -> u8 {
+    match state {
+        TrustState::Untrusted => 0,
+        TrustState::Trusted => 1,
+        TrustState::Ignored => 2,
+    }
+}
+
+/// Apply `op` to each entry in turn, collecting per-path failures.
+///
+/// mise refuses to untrust a path that no longer exists, so stale records are
+/// removed directly from the state directory instead.
+pub async fn apply_trust_op(entries: Vec<TrustEntry>, op: TrustOp) -> Result<String, String> {
+    let mut failures = Vec::new();
+    for entry in &entries {
+        let result = match (op, &entry.record) {
+            (TrustOp::Untrust, Some(record)) if !entry.exists => std::fs::remove_file(record)
+                .map(|_| String::new())
+                .map_err(|e| e.to_string()),
+            (TrustOp::Trust, _) => trust_config(&entry.path).await,
+            (TrustOp::Untrust, _) => untrust_config(&entry.path).await,
+            (TrustOp::Ignore, _) => ignore_config(&entry.path).await,
+        };
+        if let Err(e) = result {
+            failures.push(format!("{}: {e}", entry.path));
+        }
+    }
+    if failures.is_empty() {
+        Ok(format!("{} {} config(s)", op.verb(), entries.len()))
+    } else {
+        Err(failures.join("; "))
+    }
+}
+
 pub async fn fetch_tool_info(tool: &str) -> Result<String, String> {
     // Returns raw JSON string for display in popup
     run_mise(&["tool", tool, "-J"]).await
 }
 
+/// Tool name → all installed versions (regardless of active state).
+fn installed_versions(tools: &[InstalledTool]) -> HashMap<&str, Vec<&str>> {
+    let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
+    for tool in tools {
+        map.entry(tool.name.as_str())
+            .or_default()
+            .push(tool.version.as_str());
+    }
+    map
+}
+
 /// Scan configured directories for .mise.toml files and compute project health.
 /// Cross-references against `installed_tools` (already loaded in-memory) to avoid
-/// extra mise subprocess calls.
+/// extra mise subprocess calls. Each scan dir is walked in parallel and `on_found`
+/// is called from the walker threads as projects turn up; blocking, so run it off
+/// the UI thread.
 pub fn scan_projects(
     config: &MisetuiConfig,
     installed_tools: &[crate::model::InstalledTool],
+    on_found: &(dyn Fn(&MiseProject) + Sync),
 ) -> Vec<MiseProject> {
     // Build a fast lookup: tool name → all installed versions (regardless of active state).
     // A tool can be installed but not active when the shell is not inside that project's
     // directory — we still want to report it as installed for the Projects health check.
-    let mut installed_map: std::collections::HashMap<&str, Vec<&str>> =
-        std::collections::HashMap::new();
-    for tool in installed_tools {
-        installed_map
-            .entry(tool.name.as_str())
-            .or_default()
-            .push(tool.version.as_str());
-    }
+    let installed_map = installed_versions(installed_tools);
 
-    let mut projects = Vec::new();
+    let found = std::sync::Mutex::new(Vec::new());
 
     for scan_root in &config.scan_dirs {
-        collect_projects(scan_root, 0, config.max_depth, &installed_map, &mut projects);
+        collect_projects(scan_root, config, &installed_map, on_found, &found);
     }
 
     // Deduplicate by path (a dir might appear in multiple scan roots)
+    let mut projects = found.into_inner().unwrap_or_else(|e| e.into_inner());
     projects.sort_by(|a, b| a.path.cmp(&b.path));
     projects.dedup_by(|a, b| a.path == b.path);
     projects.sort_by(|a, b| a.name.cmp(&b.name));
@@ -221,40 +635,73 @@ pub fn scan_projects(
 }
 
 fn collect_projects(
-    dir: &std::path::Path,
-    depth: usize,
-    max_depth: usize,
-    installed_map: &std::collections::HashMap<&str, Vec<&str>>,
-    projects: &mut Vec<MiseProject>,
+    root: &std::path::Path,
+    config: &MisetuiConfig,
+    installed_map: &HashMap<&str, Vec<&str>>,
+    on_found: &(dyn Fn(&MiseProject) + Sync),
+    found: &std::sync::Mutex<Vec<MiseProject>>,
 ) {
-    let config_path = dir.join(".mise.toml");
-    if config_path.exists() {
-        let project = parse_project(dir, &config_path, installed_map);
-        projects.push(project);
-        // Don't recurse into projects that already have a .mise.toml
-        return;
-    }
+    let gitignore = config.respect_gitignore;
+    let mut walker = ignore::WalkBuilder::new(root);
+    walker
+        .max_depth(Some(config.depth_for(root)))
+        // Symlinked projects are scanned; the walker reports loops instead of following them
+        .follow_links(true)
+        .hidden(true)
+        .ignore(gitignore)
+        .git_ignore(gitignore)
+        .git_exclude(gitignore)
+        .git_global(gitignore)
+        .parents(gitignore)
+        .overrides(exclude_overrides(root, &config.exclude))
+        .filter_entry(|entry| entry.file_type().is_some_and(|t| t.is_dir()));
+
+    walker.build_parallel().run(|| {
+        Box::new(|entry| {
+            let Ok(entry) = entry else {
+                return ignore::WalkState::Continue;
+            };
+            let dir = entry.path();
+            let config_path = dir.join(".mise.toml");
+            if !config_path.exists() {
+                return ignore::WalkState::Continue;
+            }
+            let mut project = parse_project(dir, &config_path, installed_map);
+            project.apply_config(config);
+            on_found(&project);
+            found
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(project);
+            // Don't recurse into projects that already have a .mise.toml
+            ignore::WalkState::Skip
+        })
+    });
+}
 
-    if depth >= max_depth {
-        return;
+/// Exclude matcher for one scan root. Invalid globs are dropped; see `invalid_excludes`.
+fn exclude_overrides(root: &std::path::Path, patterns: &[String]) -> ignore::overrides::Override {
+    let mut builder = ignore::overrides::OverrideBuilder::new(root);
+    for pattern in patterns {
+        // Override globs whitelist by default; a leading `!` makes them exclude instead
+        let _ = builder.add(&format!("!{pattern}"));
     }
+    builder
+        .build()
+        .unwrap_or_else(|_| ignore::overrides::Override::empty())
+}
 
-    let Ok(entries) = std::fs::read_dir(dir) else {
-        return;
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            // Skip hidden directories and common non-project dirs
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-            if name_str.starts_with('.') || name_str == "node_modules" || name_str == "target" {
-                continue;
-            }
-            collect_projects(&path, depth + 1, max_depth, installed_map, projects);
-        }
-    }
+/// Exclude patterns from misetui's config that are not valid globs.
+pub fn invalid_excludes(patterns: &[String]) -> Vec<String> {
+    patterns
+        .iter()
+        .filter(|pattern| {
+            ignore::overrides::OverrideBuilder::new("/")
+                .add(&format!("!{pattern}"))
+                .is_err()
+        })
+        .cloned()
+        .collect()
 }
 
 fn parse_project(
@@ -267,78 +714,35 @@ fn parse_project(
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| dir.to_string_lossy().to_string());
     let path = dir.to_string_lossy().to_string();
+    let config_path_str = config_path.to_string_lossy().to_string();
 
-    let Ok(contents) = std::fs::read_to_string(config_path) else {
+    let lockfile = lockfile_for(config_path);
+    let Some(tool_entries) = read_tool_requirements(config_path) else {
         return MiseProject {
             name,
             path,
+            config_path: config_path_str,
             tool_count: 0,
             health: ProjectHealthStatus::NoConfig,
             tools: Vec::new(),
+            lockfile: None,
+            config_age_secs: None,
+            git: None,
+            group: None,
+            favorite: false,
+            tags: Vec::new(),
         };
     };
-
-    // Parse [tools] table from .mise.toml using basic TOML parsing
-    let toml_val: toml::Value = match toml::from_str(&contents) {
-        Ok(v) => v,
-        Err(_) => {
-            return MiseProject {
-                name,
-                path,
-                tool_count: 0,
-                health: ProjectHealthStatus::NoConfig,
-                tools: Vec::new(),
-            };
-        }
-    };
-
-    let tool_entries: Vec<(String, String)> = toml_val
-        .get("tools")
-        .and_then(|t| t.as_table())
-        .map(|table| {
-            table
-                .iter()
-                .map(|(k, v)| {
-                    let version = match v {
-                        toml::Value::String(s) => s.clone(),
-                        toml::Value::Array(arr) => arr
-                            .first()
-                            .and_then(|x| x.as_str())
-                            .unwrap_or("?")
-                            .to_string(),
-                        other => other.to_string(),
-                    };
-                    (k.clone(), version)
-                })
-                .collect()
-        })
-        .unwrap_or_default();
+    let locked = lockfile.as_deref().map(read_lockfile);
 
     let mut tool_healths: Vec<ProjectToolHealth> = Vec::new();
     let mut worst = ProjectHealthStatus::Healthy;
 
     for (tool_name, required) in &tool_entries {
-        let status = match installed_map.get(tool_name.as_str()) {
-            None => ProjectHealthStatus::Missing,
-            Some(versions) => {
-                // Check if any installed version satisfies the requirement.
-                // "latest" always satisfies. Exact match satisfies. Fuzzy prefix satisfies
-                // (e.g. required "3.12" is satisfied by installed "3.12.12").
-                let satisfied = required == "latest"
-                    || versions.iter().any(|v| {
-                        *v == required.as_str()
-                            || v.starts_with(&format!("{}.", required))
-                    });
-                if satisfied {
-                    ProjectHealthStatus::Healthy
-                } else {
-                    ProjectHealthStatus::Outdated
-                }
-            }
-        };
+        let health = tool_health(tool_name, required, installed_map, locked.as_deref());
 
         // Update worst-case aggregate
-        match (&worst, &status) {
+        match (&worst, &health.status) {
             (_, ProjectHealthStatus::Missing) => worst = ProjectHealthStatus::Missing,
             (ProjectHealthStatus::Healthy, ProjectHealthStatus::Outdated) => {
                 worst = ProjectHealthStatus::Outdated
@@ -346,130 +750,628 @@ fn parse_project(
             _ => {}
         }
 
-        // Display the best-matching installed version (exact match first, else first available).
-        let installed = installed_map
-            .get(tool_name.as_str())
-            .and_then(|versions| {
-                versions
-                    .iter()
-                    .find(|v| {
-                        **v == required.as_str()
-                            || v.starts_with(&format!("{}.", required))
-                    })
-                    .or_else(|| versions.first())
-                    .copied()
-            })
-            .unwrap_or("")
-            .to_string();
-
-        tool_healths.push(ProjectToolHealth {
-            tool: tool_name.clone(),
-            required: required.clone(),
-            installed,
-            status,
-        });
+        tool_healths.push(health);
     }
 
     MiseProject {
         name,
         path,
+        config_path: config_path_str,
         tool_count: tool_entries.len(),
         health: worst,
         tools: tool_healths,
+        lockfile: lockfile.map(|p| p.to_string_lossy().to_string()),
+        config_age_secs: std::fs::metadata(config_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(age_secs),
+        git: probe_git(dir, config_path),
+        group: None,
+        favorite: false,
+        tags: Vec::new(),
     }
 }
 
-/// Check the health of the current working directory's mise tool requirements.
-///
-/// Uses `mise config ls --json` + `mise ls --current --json` (proper tool-status APIs).
-///
-/// - `DriftState::NoConfig`  — no local .mise.toml applies; only global config (or nothing)
-/// - `DriftState::Missing`   — one or more local-config tools are not installed
-/// - `DriftState::Untrusted` — a local .mise.toml exists but hasn't been trusted
-/// - `DriftState::Healthy`   — all local-config tools are installed
-pub async fn check_cwd_drift() -> Result<DriftState, String> {
-    let cwd = std::env::current_dir()
-        .map_err(|e| format!("Cannot determine CWD: {e}"))?;
+/// Seconds elapsed since `when`; None for times in the future.
+fn age_secs(when: std::time::SystemTime) -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(when)
+        .ok()
+        .map(|d| d.as_secs())
+}
 
-    // Step 1: Detect whether a local (non-global) config applies to this directory.
-    // `mise config ls --json` returns an array of config entries ordered by precedence.
-    let config_out = Command::new("mise")
-        .args(["config", "ls", "--json"])
-        .current_dir(&cwd)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run mise config ls: {e}"))?;
+/// Branch, config status and last commit for the git work tree containing `dir`,
+/// via the `git` CLI. None outside a work tree or when git is unavailable.
+fn probe_git(dir: &std::path::Path, config_path: &std::path::Path) -> Option<GitInfo> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    };
 
-    // Untrusted config — exit 1, empty stdout, error in stderr.
-    if !config_out.status.success() {
-        let stderr = String::from_utf8_lossy(&config_out.stderr);
-        if stderr.contains("not trusted") {
-            return Ok(DriftState::Untrusted);
+    // One status call yields both the branch header and the config file's state
+    let config = config_path.to_string_lossy().to_string();
+    let status = git(&[
+        "status",
+        "--porcelain=v2",
+        "--branch",
+        "--untracked-files=all",
+        "--",
+        &config,
+    ])?;
+    let mut branch = String::new();
+    let mut config_dirty = false;
+    for line in status.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            branch = head.to_string();
+        } else if !line.starts_with('#') {
+            config_dirty = true;
         }
-        return Ok(DriftState::NoConfig);
     }
 
-    let configs: Vec<serde_json::Value> =
-        serde_json::from_slice(&config_out.stdout).unwrap_or_default();
+    let commit_age_secs = git(&["log", "-1", "--format=%ct"])
+        .and_then(|ts| ts.trim().parse::<u64>().ok())
+        .and_then(|ts| age_secs(std::time::UNIX_EPOCH + std::time::Duration::from_secs(ts)));
 
-    // Global config lives at $XDG_CONFIG_HOME/mise/config.toml.
-    let global_config: Option<PathBuf> = dirs::config_dir().map(|d| d.join("mise/config.toml"));
+    Some(GitInfo {
+        branch,
+        config_dirty,
+        commit_age_secs,
+    })
+}
 
-    let local_config_paths: Vec<PathBuf> = configs
-        .iter()
-        .filter_map(|e| e["path"].as_str().map(PathBuf::from))
-        .filter(|p| {
-            global_config
-                .as_ref()
-                .map(|g| p != g)
-                .unwrap_or(true)
-        })
-        .collect();
+/// Health of one `tool = required` requirement against the installed versions and,
+/// when the config has a lockfile, its `locked` entries.
+pub fn tool_health(
+    tool: &str,
+    required: &str,
+    installed_map: &HashMap<&str, Vec<&str>>,
+    locked: Option<&[LockedTool]>,
+) -> ProjectToolHealth {
+    let versions = installed_map.get(tool).map(|v| v.as_slice()).unwrap_or(&[]);
+    // Check if any installed version satisfies the requirement.
+    // "latest" always satisfies. Exact match satisfies. Fuzzy prefix satisfies
+    // (e.g. required "3.12" is satisfied by installed "3.12.12").
+    let status = if versions.is_empty() {
+        ProjectHealthStatus::Missing
+    } else if versions.iter().any(|v| version_satisfies(v, required)) {
+        ProjectHealthStatus::Healthy
+    } else {
+        ProjectHealthStatus::Outdated
+    };
 
-    if local_config_paths.is_empty() {
-        return Ok(DriftState::NoConfig);
-    }
+    // A tool may be locked at several versions; prefer the one matching the requirement.
+    let entry = locked.and_then(|entries| {
+        let mut matching = entries.iter().filter(|e| e.tool == tool);
+        matching
+            .clone()
+            .find(|e| version_satisfies(&e.version, required))
+            .or_else(|| matching.next())
+    });
+    let lock = locked.map(|_| match entry {
+        None => LockState::Unlocked,
+        Some(e) if !version_satisfies(&e.version, required) => LockState::Stale,
+        Some(e) if !versions.contains(&e.version.as_str()) => LockState::Drift,
+        Some(_) => LockState::Locked,
+    });
+
+    // Display the locked version when it is installed, else the best-matching
+    // installed version (exact match first, else first available).
+    let installed = match (entry, lock) {
+        (Some(e), Some(LockState::Locked)) => e.version.clone(),
+        _ => versions
+            .iter()
+            .find(|v| version_satisfies(v, required))
+            .or_else(|| versions.first())
+            .map(|v| v.to_string())
+            .unwrap_or_default(),
+    };
 
-    // Step 2: Check tool installation state for local-config tools only.
-    // `mise ls --current --json` returns { "tool": [ { installed, source, ... } ] }
-    let ls_out = Command::new("mise")
-        .args(["ls", "--current", "--json"])
-        .current_dir(&cwd)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run mise ls: {e}"))?;
+    ProjectToolHealth {
+        tool: tool.to_string(),
+        required: required.to_string(),
+        installed,
+        status,
+        locked: entry.map(|e| e.version.clone()).unwrap_or_default(),
+        checksum: entry.map(platform_checksum).unwrap_or_default(),
+        lock,
+    }
+}
 
-    if !ls_out.status.success() {
-        let stderr = String::from_utf8_lossy(&ls_out.stderr);
-        if stderr.contains("not trusted") {
-            return Ok(DriftState::Untrusted);
-        }
-        return Err(String::from_utf8_lossy(&ls_out.stderr).into_owned());
+/// The lockfile mise keeps for a TOML config: `<stem>.lock` beside it (`mise.toml` →
+/// `mise.lock`, `mise.local.toml` → `mise.local.lock`), else `mise.lock` in the same
+/// directory. None for legacy version files or when no lockfile exists.
+pub fn lockfile_for(config_path: &std::path::Path) -> Option<PathBuf> {
+    if config_path.extension().is_none_or(|ext| ext != "toml") {
+        return None;
     }
+    let dir = config_path.parent()?;
+    let is_local = config_path
+        .file_name()
+        .is_some_and(|n| n.to_string_lossy().contains(".local."));
+    let fallback = if is_local { "mise.local.lock" } else { "mise.lock" };
+    [config_path.with_extension("lock"), dir.join(fallback)]
+        .into_iter()
+        .find(|p| p.is_file())
+}
 
-    let tools: serde_json::Map<String, serde_json::Value> =
-        serde_json::from_slice(&ls_out.stdout).unwrap_or_default();
+/// Read a lockfile's tool entries; unreadable or malformed files yield none.
+pub fn read_lockfile(path: &std::path::Path) -> Vec<LockedTool> {
+    std::fs::read_to_string(path)
+        .map(|text| parse_lockfile(&text))
+        .unwrap_or_default()
+}
 
-    for (_tool, versions) in &tools {
-        if let Some(arr) = versions.as_array() {
-            for entry in arr {
-                // Only flag tools sourced from a local config file.
-                let source_path = entry["source"]["path"]
-                    .as_str()
-                    .map(PathBuf::from);
-                let is_local = source_path
-                    .as_ref()
-                    .map(|p| local_config_paths.contains(p))
-                    .unwrap_or(false);
-
-                if is_local && entry["installed"].as_bool() == Some(false) {
-                    return Ok(DriftState::Missing);
+/// Parse the `[tools]` table of a `mise.lock`. Accepts a table per tool
+/// (`version` plus `[tools.x.checksums]` or `[tools.x.platforms.<p>] checksum`),
+/// an array of such tables for multi-version locks, and bare version strings.
+pub fn parse_lockfile(text: &str) -> Vec<LockedTool> {
+    let Ok(value) = toml::from_str::<toml::Value>(text) else {
+        return Vec::new();
+    };
+    let Some(tools) = value.get("tools").and_then(|t| t.as_table()) else {
+        return Vec::new();
+    };
+    let mut locked = Vec::new();
+    for (tool, entry) in tools {
+        let entries: Vec<&toml::Value> = match entry {
+            toml::Value::Array(arr) => arr.iter().collect(),
+            other => vec![other],
+        };
+        for entry in entries {
+            let mut item = LockedTool {
+                tool: tool.clone(),
+                ..Default::default()
+            };
+            match entry {
+                toml::Value::String(v) => item.version = v.clone(),
+                toml::Value::Table(t) => {
+                    item.version = t
+                        .get("version")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    if let Some(sums) = t.get("checksums").and_then(|c| c.as_table()) {
+                        item.checksums.extend(sums.iter().filter_map(|(asset, sum)| {
+                            Some((asset.clone(), sum.as_str()?.to_string()))
+                        }));
+                    }
+                    if let Some(platforms) = t.get("platforms").and_then(|p| p.as_table()) {
+                        item.checksums.extend(platforms.iter().filter_map(|(platform, info)| {
+                            let sum = info.get("checksum")?.as_str()?;
+                            Some((platform.clone(), sum.to_string()))
+                        }));
+                    }
                 }
+                _ => continue,
+            }
+            if !item.version.is_empty() {
+                locked.push(item);
+            }
+        }
+    }
+    locked
+}
+
+/// The checksum recorded for this machine's platform (`linux-x64`, `macos-arm64`, or an
+/// asset name containing both parts), else the first one recorded.
+fn platform_checksum(entry: &LockedTool) -> String {
+    let os: &[&str] = match std::env::consts::OS {
+        "macos" => &["macos", "darwin"],
+        other => &[other],
+    };
+    let arch: &[&str] = match std::env::consts::ARCH {
+        "x86_64" => &["x64", "x86_64", "amd64"],
+        "aarch64" => &["arm64", "aarch64"],
+        other => &[other],
+    };
+    entry
+        .checksums
+        .iter()
+        .find(|(key, _)| {
+            os.iter().any(|o| key.contains(o)) && arch.iter().any(|a| key.contains(a))
+        })
+        .or_else(|| entry.checksums.first())
+        .map(|(_, sum)| sum.clone())
+        .unwrap_or_default()
+}
+
+/// Lock rows for a config file with a lockfile: each `[tools]` requirement against its
+/// locked entry and the installed versions. Empty when the config has no lockfile.
+pub fn config_lock_rows(cfg: &ConfigFile, tools: &[InstalledTool]) -> Vec<ProjectToolHealth> {
+    if cfg.lockfile.is_none() {
+        return Vec::new();
+    }
+    let installed_map = installed_versions(tools);
+    parse_tool_requirements(&cfg.contents)
+        .unwrap_or_default()
+        .iter()
+        .map(|(tool, required)| tool_health(tool, required, &installed_map, Some(&cfg.locked)))
+        .collect()
+}
+
+/// Read the `[tools]` table of a mise config file as `(tool, version spec)` pairs.
+/// Array values yield their first element; inline tables yield their `version` key.
+/// Returns None if the file cannot be read or parsed.
+pub fn read_tool_requirements(config_path: &std::path::Path) -> Option<Vec<(String, String)>> {
+    let contents = std::fs::read_to_string(config_path).ok()?;
+    parse_tool_requirements(&contents)
+}
+
+/// [`read_tool_requirements`] for config text already in memory.
+pub fn parse_tool_requirements(contents: &str) -> Option<Vec<(String, String)>> {
+    let toml_val: toml::Value = toml::from_str(contents).ok()?;
+    let entries = toml_val
+        .get("tools")
+        .and_then(|t| t.as_table())
+        .map(|table| {
+            table
+                .iter()
+                .map(|(k, v)| {
+                    let version = match v {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Array(arr) => arr
+                            .first()
+                            .and_then(|x| x.as_str())
+                            .unwrap_or("?")
+                            .to_string(),
+                        toml::Value::Table(t) => t
+                            .get("version")
+                            .and_then(|x| x.as_str())
+                            .unwrap_or("?")
+                            .to_string(),
+                        other => other.to_string(),
+                    };
+                    (k.clone(), version)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(entries)
+}
+
+/// Requirements that would become Missing if `tool@version` were uninstalled: those
+/// this version currently satisfies and no other installed version does.
+///
+/// Checks scanned projects, the active toolset (configs resolved for the CWD) and the
+/// global config files mise reports (see `global_configs`).
+pub fn uninstall_impact(
+    tool: &str,
+    version: &str,
+    tools: &[InstalledTool],
+    projects: &[MiseProject],
+) -> Vec<ToolUsage> {
+    let others: Vec<&str> = tools
+        .iter()
+        .filter(|t| t.name == tool && t.installed && t.version != version)
+        .map(|t| t.version.as_str())
+        .collect();
+    let breaks = |required: &str| {
+        version_satisfies(version, required) && !others.iter().any(|v| version_satisfies(v, required))
+    };
+
+    let mut impacted: Vec<ToolUsage> = Vec::new();
+    let mut push = |usage: ToolUsage| {
+        if !impacted.iter().any(|u| u.config_path == usage.config_path) {
+            impacted.push(usage);
+        }
+    };
+
+    for project in projects {
+        for req in project.tools.iter().filter(|r| r.tool == tool && breaks(&r.required)) {
+            push(ToolUsage {
+                project: project.name.clone(),
+                config_path: project.config_path.clone(),
+                required: req.required.clone(),
+            });
+        }
+    }
+
+    for t in tools.iter().filter(|t| t.name == tool && t.active && !t.source.is_empty()) {
+        let required = if t.requested_version.is_empty() { &t.version } else { &t.requested_version };
+        if breaks(required) {
+            push(ToolUsage {
+                project: "active toolset".to_string(),
+                config_path: t.source.clone(),
+                required: required.clone(),
+            });
+        }
+    }
+
+    for global in global_configs() {
+        for (name, required) in read_tool_requirements(&global).unwrap_or_default() {
+            if name == tool && breaks(&required) {
+                push(ToolUsage {
+                    project: "global config".to_string(),
+                    config_path: global.to_string_lossy().to_string(),
+                    required,
+                });
             }
         }
     }
 
-    Ok(DriftState::Healthy)
+    impacted
+}
+
+/// Build the reverse "used by" index: `tool@version` → every scanned project and active
+/// config file whose requirement resolves to that installed version.
+///
+/// A requirement resolves to the newest installed version that satisfies it, which is
+/// the version mise itself would pick.
+pub fn build_usage_index(
+    tools: &[InstalledTool],
+    projects: &[MiseProject],
+) -> HashMap<String, Vec<ToolUsage>> {
+    let mut installed: HashMap<&str, Vec<&str>> = HashMap::new();
+    for tool in tools.iter().filter(|t| t.installed) {
+        installed.entry(tool.name.as_str()).or_default().push(tool.version.as_str());
+    }
+    for versions in installed.values_mut() {
+        versions.sort_by(|a, b| compare_versions(b, a));
+        versions.dedup();
+    }
+
+    let mut index: HashMap<String, Vec<ToolUsage>> = HashMap::new();
+    let mut add = |tool: &str, required: &str, usage: ToolUsage| {
+        let resolved = installed
+            .get(tool)
+            .and_then(|versions| versions.iter().find(|v| version_satisfies(v, required)));
+        if let Some(version) = resolved {
+            let entry = index.entry(format!("{tool}@{version}")).or_default();
+            if !entry.iter().any(|u| u.config_path == usage.config_path) {
+                entry.push(usage);
+            }
+        }
+    };
+
+    for project in projects {
+        for req in &project.tools {
+            add(&req.tool, &req.required, ToolUsage {
+                project: project.name.clone(),
+                config_path: project.config_path.clone(),
+                required: req.required.clone(),
+            });
+        }
+    }
+
+    // Active toolset: configs mise resolved for the CWD and global scope.
+    for tool in tools.iter().filter(|t| t.active && !t.source.is_empty()) {
+        let required = if tool.requested_version.is_empty() {
+            tool.version.as_str()
+        } else {
+            tool.requested_version.as_str()
+        };
+        let project = std::path::Path::new(&tool.source)
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| tool.source.clone());
+        add(&tool.name, required, ToolUsage {
+            project,
+            config_path: tool.source.clone(),
+            required: required.to_string(),
+        });
+    }
+
+    index
+}
+
+/// Check the health of the working directory's ([`work_dir`]) mise tool requirements.
+///
+/// Uses `mise config ls --json` + `mise ls --current --json` (proper tool-status APIs),
+/// then `mise ls --installed --json` and `mise outdated --json` for per-tool detail.
+///
+/// - `DriftState::NoConfig`  — no local .mise.toml applies; only global config (or nothing)
+/// - `DriftState::Untrusted` — a local .mise.toml exists but hasn't been trusted
+/// - `DriftState::Missing`   — a local-config tool is not installed at the requested version
+/// - `DriftState::Outdated`  — all installed, but newer versions matching the pins exist
+/// - `DriftState::Healthy`   — all local-config tools are installed and current
+pub async fn check_cwd_drift() -> Result<DriftReport, String> {
+    let cwd = work_dir();
+    let mut report = DriftReport {
+        dir: cwd.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    // Step 1: Detect whether a local (non-global) config applies to this directory.
+    // `mise config ls --json` returns an array of config entries ordered by precedence.
+    let config_out = Command::new("mise")
+        .args(["config", "ls", "--json"])
+        .current_dir(&cwd)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run mise config ls: {e}"))?;
+
+    // Untrusted config — exit 1, empty stdout, error in stderr.
+    if !config_out.status.success() {
+        let stderr = String::from_utf8_lossy(&config_out.stderr);
+        if stderr.contains("not trusted") {
+            report.state = DriftState::Untrusted;
+            report.untrusted = untrusted_configs(&cwd, &stderr);
+        } else {
+            report.state = DriftState::NoConfig;
+        }
+        return Ok(report);
+    }
+
+    let configs: Vec<serde_json::Value> =
+        serde_json::from_slice(&config_out.stdout).unwrap_or_default();
+
+    // Global config lives at $XDG_CONFIG_HOME/mise/config.toml.
+    let global_config: Option<PathBuf> = dirs::config_dir().map(|d| d.join("mise/config.toml"));
+
+    let local_config_paths: Vec<PathBuf> = configs
+        .iter()
+        .filter_map(|e| e["path"].as_str().map(PathBuf::from))
+        .filter(|p| {
+            global_config
+                .as_ref()
+                .map(|g| p != g)
+                .unwrap_or(true)
+        })
+        .collect();
+
+    if local_config_paths.is_empty() {
+        report.state = DriftState::NoConfig;
+        return Ok(report);
+    }
+
+    // Step 2: Check tool installation state for local-config tools only.
+    // `mise ls --current --json` returns { "tool": [ { installed, source, ... } ] }
+    let ls_out = Command::new("mise")
+        .args(["ls", "--current", "--json"])
+        .current_dir(&cwd)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run mise ls: {e}"))?;
+
+    if !ls_out.status.success() {
+        let stderr = String::from_utf8_lossy(&ls_out.stderr);
+        if stderr.contains("not trusted") {
+            report.state = DriftState::Untrusted;
+            report.untrusted = untrusted_configs(&cwd, &stderr);
+            return Ok(report);
+        }
+        return Err(String::from_utf8_lossy(&ls_out.stderr).into_owned());
+    }
+
+    let tools: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(&ls_out.stdout).unwrap_or_default();
+
+    // Step 3: Every installed version per tool, to tell "missing" from "wrong version",
+    // and what `mise outdated` reports for tools that are installed.
+    let installed: BTreeMap<String, Vec<InstalledToolVersion>> =
+        mise_json_in(&cwd, &["ls", "--installed", "--json"]).await.unwrap_or_default();
+    let outdated: BTreeMap<String, OutdatedEntry> =
+        mise_json_in(&cwd, &["outdated", "--json"]).await.unwrap_or_default();
+
+    for (tool, versions) in &tools {
+        let Some(arr) = versions.as_array() else {
+            continue;
+        };
+        for entry in arr {
+            // Only flag tools sourced from a local config file.
+            let source_path = entry["source"]["path"]
+                .as_str()
+                .map(PathBuf::from);
+            let Some(source) = source_path.filter(|p| local_config_paths.contains(p)) else {
+                continue;
+            };
+
+            let installed_versions: Vec<String> = installed
+                .get(tool)
+                .map(|vs| vs.iter().map(|v| v.version.clone()).collect())
+                .unwrap_or_default();
+            let mut latest = String::new();
+            let issue = if entry["installed"].as_bool() == Some(false) {
+                if installed_versions.is_empty() {
+                    DriftIssue::Missing
+                } else {
+                    DriftIssue::WrongVersion
+                }
+            } else if let Some(newer) = outdated
+                .get(tool)
+                .and_then(|o| o.latest.clone())
+                .filter(|l| entry["version"].as_str() != Some(l.as_str()))
+            {
+                latest = newer;
+                DriftIssue::Outdated
+            } else {
+                report.healthy += 1;
+                continue;
+            };
+
+            report.tools.push(DriftTool {
+                tool: tool.clone(),
+                requested: entry["requested_version"]
+                    .as_str()
+                    .or_else(|| entry["version"].as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                installed: installed_versions,
+                latest,
+                source: source.to_string_lossy().to_string(),
+                issue,
+            });
+        }
+    }
+
+    report.state = if report.has(DriftIssue::Missing) || report.has(DriftIssue::WrongVersion) {
+        DriftState::Missing
+    } else if report.has(DriftIssue::Outdated) {
+        DriftState::Outdated
+    } else {
+        DriftState::Healthy
+    };
+    Ok(report)
+}
+
+/// Run mise in `dir` and parse its JSON stdout; None if it fails or doesn't parse.
+async fn mise_json_in<T: serde::de::DeserializeOwned>(
+    dir: &std::path::Path,
+    args: &[&str],
+) -> Option<T> {
+    let output = Command::new("mise")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Config file names mise loads from each directory, relative to that directory.
+const LOCAL_CONFIG_NAMES: &[&str] = &[
+    "mise.toml",
+    ".mise.toml",
+    "mise.local.toml",
+    ".mise.local.toml",
+    ".config/mise.toml",
+    ".config/mise/config.toml",
+    "mise/config.toml",
+    ".mise/config.toml",
+];
+
+/// Config files behind a "not trusted" error for `dir`: the file paths named in
+/// mise's `stderr`, else every untrusted local config in `dir` and its ancestors.
+fn untrusted_configs(dir: &std::path::Path, stderr: &str) -> Vec<String> {
+    let mut paths: Vec<String> = stderr
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| matches!(c, '"' | '\'' | '`' | ',' | ':')))
+        .map(|w| w.trim_end_matches('.'))
+        .filter(|w| w.starts_with('/') && std::path::Path::new(w).is_file())
+        .map(str::to_string)
+        .collect();
+    if paths.is_empty() {
+        let trusted = read_trust_dir("trusted-configs");
+        let ignored = read_trust_dir("ignored-configs");
+        for ancestor in dir.ancestors() {
+            for name in LOCAL_CONFIG_NAMES {
+                let path = ancestor.join(name);
+                if !path.is_file() {
+                    continue;
+                }
+                let kind = classify_config(&path);
+                if kind != ConfigKind::Global
+                    && trust_state_for(&path, kind, &trusted, &ignored) == TrustState::Untrusted
+                {
+                    paths.push(path.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    paths.dedup();
+    paths
 }
 
 /// Run `mise install` in the specified project directory.
@@ -489,6 +1391,215 @@ pub async fn install_project_tools(path: &str) -> Result<String, String> {
     }
 }
 
+/// Run `mise lock` in the specified directory to rewrite its lockfile from the
+/// current requirements.
+pub async fn regenerate_lock(path: &str) -> Result<String, String> {
+    let output = Command::new("mise")
+        .args(["lock"])
+        .current_dir(path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run mise lock: {e}"))?;
+
+    if output.status.success() {
+        Ok(format!("Regenerated lockfile in {path}"))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        Err(format!("mise lock failed: {stderr}"))
+    }
+}
+
+/// Plan installing what every unhealthy project in `projects` is missing. Requirements
+/// resolve to their locked version when the lockfile has a matching one, so identical
+/// `tool@version` specs are installed once for every project sharing them.
+pub fn plan_fleet_install(projects: &[&MiseProject]) -> FleetPlan {
+    let mut targets = Vec::new();
+    let mut skipped = 0;
+    for proj in projects {
+        let specs: Vec<String> = proj
+            .tools
+            .iter()
+            .filter(|t| t.status != ProjectHealthStatus::Healthy)
+            .map(|t| {
+                let version = if !t.locked.is_empty() && version_satisfies(&t.locked, &t.required) {
+                    &t.locked
+                } else {
+                    &t.required
+                };
+                format!("{}@{version}", t.tool)
+            })
+            .collect();
+        if specs.is_empty() {
+            skipped += 1;
+            continue;
+        }
+        targets.push(FleetTarget {
+            name: proj.name.clone(),
+            path: proj.path.clone(),
+            specs,
+            error: None,
+        });
+    }
+    FleetPlan {
+        op: FleetOp::InstallMissing,
+        installs: shared_installs(&targets),
+        targets,
+        skipped,
+    }
+}
+
+/// Plan `mise upgrade` across `(name, path)` projects from each one's
+/// `mise upgrade --dry-run`, a few projects at a time.
+pub async fn plan_fleet_upgrade(projects: Vec<(String, String)>) -> FleetPlan {
+    use futures::stream::{self, StreamExt};
+
+    let planned: Vec<FleetTarget> = stream::iter(projects)
+        .map(|(name, path)| async move {
+            let output = Command::new("mise")
+                .args(["upgrade", "--dry-run"])
+                .current_dir(&path)
+                .output()
+                .await;
+            let (specs, error) = match output {
+                Ok(out) if out.status.success() => {
+                    // mise prints dry-run plans on stdout or stderr depending on version
+                    let text = format!(
+                        "{}\n{}",
+                        String::from_utf8_lossy(&out.stdout),
+                        String::from_utf8_lossy(&out.stderr)
+                    );
+                    let specs = text
+                        .lines()
+                        .filter_map(|l| l.split_once("Would install ").map(|(_, spec)| spec.trim().to_string()))
+                        .collect();
+                    (specs, None)
+                }
+                Ok(out) => (Vec::new(), Some(first_line(&String::from_utf8_lossy(&out.stderr)))),
+                Err(e) => (Vec::new(), Some(format!("Failed to run mise upgrade: {e}"))),
+            };
+            FleetTarget { name, path, specs, error }
+        })
+        .buffered(4)
+        .collect()
+        .await;
+
+    let (targets, idle): (Vec<FleetTarget>, Vec<FleetTarget>) = planned
+        .into_iter()
+        .partition(|t| !t.specs.is_empty() || t.error.is_some());
+    FleetPlan {
+        op: FleetOp::UpgradePins,
+        installs: shared_installs(&targets),
+        targets,
+        skipped: idle.len(),
+    }
+}
+
+/// Group the targets' specs into distinct installs, most shared first.
+fn shared_installs(targets: &[FleetTarget]) -> Vec<FleetInstall> {
+    let mut by_spec: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for target in targets {
+        for spec in &target.specs {
+            by_spec.entry(spec).or_default().push(target.name.clone());
+        }
+    }
+    let mut installs: Vec<FleetInstall> = by_spec
+        .into_iter()
+        .map(|(spec, projects)| {
+            // Versions never contain '@', but backend tool names can (`npm:@scope/pkg`)
+            let (tool, version) = spec.rsplit_once('@').unwrap_or((spec, ""));
+            FleetInstall {
+                tool: tool.to_string(),
+                version: version.to_string(),
+                projects,
+            }
+        })
+        .collect();
+    installs.sort_by(|a, b| b.projects.len().cmp(&a.projects.len()).then(a.tool.cmp(&b.tool)));
+    installs
+}
+
+/// Run a fleet plan and report per project. InstallMissing installs each distinct
+/// spec once; UpgradePins runs `mise upgrade` in each project in turn.
+pub async fn run_fleet(plan: FleetPlan) -> Vec<FleetResult> {
+    let mut results = Vec::new();
+    match plan.op {
+        FleetOp::InstallMissing => {
+            let mut failed: HashMap<String, String> = HashMap::new();
+            for install in &plan.installs {
+                let spec = format!("{}@{}", install.tool, install.version);
+                // Install from a project that needs it, so its settings and backends apply
+                let dir = plan
+                    .targets
+                    .iter()
+                    .find(|t| t.error.is_none() && t.specs.contains(&spec))
+                    .map(|t| t.path.as_str());
+                if let Err(e) = install_in_project(&spec, dir).await {
+                    failed.insert(spec, first_line(&e));
+                }
+            }
+            for target in plan.targets {
+                let failures: Vec<String> = target
+                    .specs
+                    .iter()
+                    .filter_map(|spec| failed.get(spec).map(|e| format!("{spec}: {e}")))
+                    .collect();
+                results.push(FleetResult {
+                    name: target.name,
+                    ok: failures.is_empty(),
+                    detail: if failures.is_empty() {
+                        format!("installed {}", target.specs.join(", "))
+                    } else {
+                        failures.join("; ")
+                    },
+                });
+            }
+        }
+        FleetOp::UpgradePins => {
+            for target in plan.targets {
+                let result = match target.error {
+                    Some(e) => Err(e),
+                    None => update_project_pins(&target.path).await.map_err(|e| first_line(&e)),
+                };
+                results.push(FleetResult {
+                    name: target.name,
+                    ok: result.is_ok(),
+                    detail: match result {
+                        Ok(_) => format!("upgraded {}", target.specs.join(", ")),
+                        Err(e) => e,
+                    },
+                });
+            }
+        }
+    }
+    results
+}
+
+/// First non-empty line of a (possibly multi-line) mise error.
+fn first_line(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Run `mise install <spec>` in a project directory, or the working directory if none.
+async fn install_in_project(spec: &str, dir: Option<&str>) -> Result<String, String> {
+    let output = Command::new("mise")
+        .args(["install", spec])
+        .current_dir(dir.map_or_else(work_dir, PathBuf::from))
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run mise install: {e}"))?;
+
+    if output.status.success() {
+        Ok(format!("Installed {spec}"))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        Err(format!("mise install failed: {stderr}"))
+    }
+}
+
 /// Run `mise upgrade` in the specified project directory to update outdated tool pins.
 pub async fn update_project_pins(path: &str) -> Result<String, String> {
     let output = Command::new("mise")
@@ -506,268 +1617,1093 @@ pub async fn update_project_pins(path: &str) -> Result<String, String> {
     }
 }
 
-/// Detect tools from filesystem indicators in `dir`, then cross-reference against
-/// `mise ls -J` to mark which tools are already installed.
+/// Detect tools from filesystem indicators in `dir` and each package directory
+/// below it, then cross-reference against `mise ls -J` to mark which tools are
+/// already installed.
 ///
-/// Filesystem mapping:
-///   package.json        → node  (version from .nvmrc, else "lts")
-///   Cargo.toml          → rust  (version "stable")
-///   pyproject.toml / requirements.txt → python (version from .python-version, else "latest")
-///   go.mod              → go    (version "latest")
-///   Gemfile             → ruby  (version from .ruby-version, else "latest")
-///   composer.json       → php   (version "latest")
-///   .tool-versions      → all tools (via migrate_legacy_pins, lowest priority)
+/// The detectors in `detect` read pins from `.nvmrc`, `package.json` engines and
+/// `packageManager`, `rust-toolchain.toml`, `go.mod`, `pyproject.toml`,
+/// `global.json`, `.sdkmanrc`, `pom.xml`/Gradle builds, `mix.exs` and similar;
+/// `.tool-versions` entries (via migrate_legacy_pins) have the lowest priority.
 ///
-/// Returns Vec<DetectedTool> sorted by name; `enabled = true`, `installed` from mise ls -J.
-pub async fn detect_project_tools(dir: &str) -> Vec<DetectedTool> {
-    use std::collections::HashMap;
-    use std::path::Path;
-
-    let base = Path::new(dir);
-
-    // Helper: read first non-empty, non-comment line
-    let read_first_line = |p: &Path| -> Option<String> {
-        std::fs::read_to_string(p).ok().and_then(|s| {
-            s.lines()
-                .map(|l| l.trim().to_string())
-                .find(|l| !l.is_empty() && !l.starts_with('#'))
+/// Returns the root package first, each with tools sorted by name.
+pub async fn detect_project_tools(dir: &str) -> Vec<WizardPackage> {
+    let root = dir.to_string();
+    let mut packages = tokio::task::spawn_blocking(move || {
+        crate::detect::detect_packages(std::path::Path::new(&root))
+    })
+    .await
+    .unwrap_or_default();
+
+    // Cross-reference with `mise ls -J` to mark already-installed tools.
+    // JSON structure: { "tool-name": [ { "version": "x.y.z", ... }, ... ] }
+    if let Ok(json) = run_mise(&["ls", "-J"]).await {
+        if let Ok(map) = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&json) {
+            for entry in packages.iter_mut().flat_map(|p| p.tools.iter_mut()) {
+                if let Some(versions) = map.get(&entry.name).and_then(|v| v.as_array()) {
+                    let wanted = entry.version.as_str();
+                    entry.installed = versions.iter().any(|v| {
+                        let installed_ver = v["version"].as_str().unwrap_or("");
+                        wanted == "latest" || wanted == "stable" || wanted == "lts"
+                            || installed_ver == wanted
+                            || installed_ver.starts_with(&format!("{wanted}."))
+                    });
+                }
+            }
+        }
+    }
+    packages
+}
+
+/// Parse `.tool-versions` in `dir` and return a DetectedTool per line.
+/// Format: `tool version` per line; `#` comments and blank lines ignored.
+pub fn migrate_legacy_pins(dir: &str) -> Vec<DetectedTool> {
+    let path = std::path::Path::new(dir).join(".tool-versions");
+    let Ok(contents) = std::fs::read_to_string(&path) else { return Vec::new() };
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.trim().starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.to_string();
+            let version = parts.next().unwrap_or("latest").to_string();
+            Some(DetectedTool { name, version, source: ".tool-versions".to_string(), enabled: true, ..Default::default() })
         })
+        .collect()
+}
+
+/// Config file the wizard writes in `dir`: the first existing shared config mise
+/// loads there (`mise.toml`, `.config/mise.toml`, ...), else a new `.mise.toml`.
+/// Local override files are skipped since they are usually not committed.
+pub fn wizard_config_path(dir: &std::path::Path) -> std::path::PathBuf {
+    LOCAL_CONFIG_NAMES
+        .iter()
+        .filter(|name| !name.contains(".local."))
+        .map(|name| dir.join(name))
+        .find(|p| p.exists())
+        .unwrap_or_else(|| dir.join(".mise.toml"))
+}
+
+/// Merge wizard tool pins and imported tasks into `existing` config text,
+/// leaving every other table, comment and tool untouched. A tool already pinned
+/// at the wizard's version, or given only a floating version (`latest`, `lts`) by
+/// the wizard, is kept; otherwise its first version is replaced in whatever shape
+/// (string, array, table) it already has. Tasks are only added
+/// when no task of that name exists. Returns the merged text and one entry per
+/// tool in the resulting `[tools]` table plus one per imported task.
+pub fn merge_wizard_config(
+    existing: &str,
+    tools: &[(String, String)],
+    tasks: &[DetectedTask],
+    config: &str,
+) -> Result<(String, Vec<WizardMergeEntry>), String> {
+    use toml_edit::{value, DocumentMut, Table};
+
+    let mut doc: DocumentMut = existing
+        .parse()
+        .map_err(|e| format!("Failed to parse {config}: {e}"))?;
+    let table = doc
+        .entry("tools")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(|| format!("[tools] in {config} is not a table"))?;
+
+    let entry = |name: &str, version: &str, merge: ToolMerge| WizardMergeEntry {
+        config: config.to_string(),
+        name: name.to_string(),
+        version: version.to_string(),
+        merge,
+    };
+    let mut entries = Vec::new();
+    for (name, version) in tools {
+        let version = if version.is_empty() { "latest" } else { version.as_str() };
+        let Some(item) = table.get(name).cloned() else {
+            table.insert(name, value(version));
+            entries.push(entry(name, version, ToolMerge::Added));
+            continue;
+        };
+        let mut row = tool_row_from_item(name, &item);
+        if row.versions.iter().any(|v| v == version) {
+            entries.push(entry(name, version, ToolMerge::Kept));
+            continue;
+        }
+        // A detection fallback never overrides a version the config already pins
+        if crate::detect::is_floating(version) {
+            entries.push(entry(name, &row.version_display(), ToolMerge::Kept));
+            continue;
+        }
+        let from = row.version_display();
+        match row.versions.first_mut() {
+            Some(first) => *first = version.to_string(),
+            None => row.versions.push(version.to_string()),
+        }
+        table.insert(name, tool_item_from_row(&row, Some(item)));
+        entries.push(entry(name, version, ToolMerge::Changed { from }));
+    }
+    for (name, item) in table.iter() {
+        if !tools.iter().any(|(n, _)| n == name) {
+            let version = tool_row_from_item(name, item).version_display();
+            entries.push(entry(name, &version, ToolMerge::Kept));
+        }
+    }
+
+    if !tasks.is_empty() {
+        let table = doc
+            .entry("tasks")
+            .or_insert_with(|| {
+                let mut t = Table::new();
+                t.set_implicit(true);
+                toml_edit::Item::Table(t)
+            })
+            .as_table_like_mut()
+            .ok_or_else(|| format!("[tasks] in {config} is not a table"))?;
+        for task in tasks {
+            let key = format!("tasks.{}", task.name);
+            if table.contains_key(&task.name) {
+                entries.push(entry(&key, &task.run, ToolMerge::Kept));
+                continue;
+            }
+            let mut t = Table::new();
+            if !task.description.is_empty() {
+                t.insert("description", value(&task.description));
+            }
+            t.insert("run", value(&task.run));
+            table.insert(&task.name, toml_edit::Item::Table(t));
+            entries.push(entry(&key, &task.run, ToolMerge::Added));
+        }
+    }
+    Ok((doc.to_string(), entries))
+}
+
+/// `[tools]` entries of a rendered bootstrap template as (name, first version) pairs.
+pub fn template_tools(template: &str) -> Vec<(String, String)> {
+    let Ok(doc) = template.parse::<toml_edit::DocumentMut>() else {
+        return Vec::new();
     };
+    let Some(tools) = doc.get("tools").and_then(|t| t.as_table_like()) else {
+        return Vec::new();
+    };
+    tools
+        .iter()
+        .map(|(name, item)| {
+            let row = tool_row_from_item(name, item);
+            let version = row.versions.first().cloned().unwrap_or_else(|| "latest".to_string());
+            (name.to_string(), version)
+        })
+        .collect()
+}
+
+/// Layer a rendered bootstrap template under `existing` config text: keys the
+/// template defines are added wherever the config does not already set them
+/// (tables are merged key by key), so an existing config always wins. Tools in
+/// `skip_tools` (toggled off in the wizard) are not taken from the template.
+pub fn apply_template(
+    existing: &str,
+    template: &str,
+    skip_tools: &[String],
+    config: &str,
+) -> Result<String, String> {
+    use toml_edit::{DocumentMut, Item, TableLike};
+
+    fn fill(target: &mut dyn TableLike, source: &dyn TableLike, skip: &[String]) {
+        for (key, item) in source.iter() {
+            if skip.iter().any(|s| s == key) {
+                continue;
+            }
+            match (target.get_mut(key), item.as_table_like()) {
+                (None, _) => {
+                    target.insert(key, item.clone());
+                }
+                (Some(existing), Some(sub)) => {
+                    if let Some(existing) = existing.as_table_like_mut() {
+                        fill(existing, sub, &[]);
+                    }
+                }
+                (Some(_), None) => {}
+            }
+        }
+    }
 
-    let nvmrc_version   = base.join(".nvmrc").exists().then(|| read_first_line(&base.join(".nvmrc"))).flatten();
-    let python_version  = base.join(".python-version").exists().then(|| read_first_line(&base.join(".python-version"))).flatten();
-    let ruby_version    = base.join(".ruby-version").exists().then(|| read_first_line(&base.join(".ruby-version"))).flatten();
+    let mut doc: DocumentMut = existing
+        .parse()
+        .map_err(|e| format!("Failed to parse {config}: {e}"))?;
+    let template: DocumentMut = template
+        .parse()
+        .map_err(|e| format!("Failed to parse template: {e}"))?;
+    if existing.trim().is_empty() {
+        // A fresh config starts as the template itself, comments and layout included
+        doc = template.clone();
+        if let Some(tools) = doc.get_mut("tools").and_then(Item::as_table_like_mut) {
+            for name in skip_tools {
+                tools.remove(name);
+            }
+        }
+        return Ok(doc.to_string());
+    }
+    for (key, item) in template.iter() {
+        let skip: &[String] = if key == "tools" { skip_tools } else { &[] };
+        match (doc.get_mut(key), item.as_table_like()) {
+            (None, _) => {
+                let mut item = item.clone();
+                if let Some(t) = item.as_table_like_mut() {
+                    for name in skip {
+                        t.remove(name);
+                    }
+                }
+                doc.insert(key, item);
+            }
+            (Some(existing), Some(sub)) => {
+                if let Some(existing) = existing.as_table_like_mut() {
+                    fill(existing, sub, skip);
+                }
+            }
+            (Some(_), None) => {}
+        }
+    }
+    Ok(doc.to_string())
+}
 
-    let mut tools: HashMap<String, DetectedTool> = HashMap::new();
+/// Write a wizard-generated file to `path` atomically (temp file + rename).
+pub async fn write_config_file(path: &std::path::Path, content: &str) -> Result<(), String> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    tokio::fs::write(&tmp_path, content)
+        .await
+        .map_err(|e| format!("Failed to write temp file: {e}"))?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .map_err(|e| format!("Failed to rename temp file: {e}"))?;
+    Ok(())
+}
 
-    // .tool-versions first (lowest priority)
-    if base.join(".tool-versions").exists() {
-        for t in migrate_legacy_pins(dir) {
-            tools.insert(t.name.clone(), t);
+/// Load EditorStates for all TOML config files known to mise.
+pub async fn fetch_editor_states() -> Vec<EditorState> {
+    let configs = fetch_config().await.unwrap_or_default();
+    let mut states = Vec::new();
+    for config in &configs {
+        if config.path.ends_with(".toml") {
+            if let Ok(state) = parse_config_for_editor(&config.path).await {
+                states.push(state);
+            }
+        }
+    }
+    states
+}
+
+/// Parse a .mise.toml file into EditorState using toml_edit for round-trip preservation.
+/// Returns an EditorState with tools from [tools], env vars from [env], tasks from [tasks].
+pub async fn parse_config_for_editor(path: &str) -> Result<EditorState, String> {
+    let contents = tokio::fs::read_to_string(path).await
+        .map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let mtime = tokio::fs::metadata(path).await.and_then(|m| m.modified()).ok();
+    parse_editor_contents(path, &contents, mtime)
+}
+
+/// Build an EditorState from already-read file contents.
+fn parse_editor_contents(
+    path: &str,
+    contents: &str,
+    mtime: Option<std::time::SystemTime>,
+) -> Result<EditorState, String> {
+    use toml_edit::DocumentMut;
+    let doc: DocumentMut = contents.parse::<DocumentMut>()
+        .map_err(|e| format!("Failed to parse TOML: {e}"))?;
+
+    let mut tools = Vec::new();
+    if let Some(table) = doc.get("tools").and_then(|v| v.as_table()) {
+        for (key, value) in table.iter() {
+            tools.push(tool_row_from_item(key, value));
+        }
+    }
+
+    let mut env_vars = Vec::new();
+    if let Some(table) = doc.get("env").and_then(|v| v.as_table()) {
+        for (key, value) in table.iter() {
+            if key == "_" {
+                env_vars.extend(env_directive_rows(value));
+                continue;
+            }
+            let val_str = match value.as_str() {
+                Some(s) => s.to_string(),
+                None => value.to_string().trim_matches('"').to_string(),
+            };
+            env_vars.push(EditorEnvRow {
+                key: key.to_string(),
+                value: val_str,
+                status: EditorRowStatus::Unchanged,
+                original_key: Some(key.to_string()),
+                ..Default::default()
+            });
+        }
+    }
+
+    let mut tasks = Vec::new();
+    if let Some(table) = doc.get("tasks").and_then(|v| v.as_table()) {
+        for (key, value) in table.iter() {
+            tasks.push(task_row_from_item(key, value));
+        }
+    }
+
+    Ok(EditorState {
+        file_path: path.to_string(),
+        tab: EditorTab::Tools,
+        tools,
+        env_vars,
+        tasks,
+        selected: 0,
+        editing: false,
+        edit_column: 0,
+        edit_buffer: String::new(),
+        raw_document: doc.to_string(),
+        dirty: false,
+        disk_mtime: mtime,
+        disk_hash: content_hash(contents),
+    })
+}
+
+fn content_hash(contents: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// True when the file behind `state` no longer matches what was loaded. The mtime
+/// is checked first; the content hash rules out touches that changed nothing.
+pub fn changed_on_disk(state: &EditorState) -> bool {
+    let path = std::path::Path::new(&state.file_path);
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if mtime.is_some() && mtime == state.disk_mtime {
+        return false;
+    }
+    match std::fs::read_to_string(path) {
+        Ok(contents) => content_hash(&contents) != state.disk_hash,
+        Err(_) => true,
+    }
+}
+
+/// Re-read a config from disk, discarding pending editor changes.
+pub fn reload_editor_state(state: &EditorState) -> Result<EditorState, String> {
+    let path = &state.file_path;
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    parse_editor_contents(path, &contents, mtime)
+}
+
+/// Three-way merge of external edits with pending editor changes.
+///
+/// The base is the document captured at load, "theirs" is the file on disk now and
+/// "ours" is the set of rows the user touched. Touched rows are merged field by
+/// field: a field keeps the editor's value only where the user changed it, so
+/// on-disk edits to the other fields survive. Returns the merged state plus the
+/// `section.key` entries where both sides changed the same field (or one side
+/// removed the entry), where the editor's version wins.
+pub fn merge_external_changes(state: &EditorState) -> Result<(EditorState, Vec<String>), String> {
+    let mut merged = reload_editor_state(state)?;
+    let base = parse_editor_contents(&state.file_path, &state.raw_document, None)?;
+
+    let mut conflicts = Vec::new();
+    merge_rows("tools", &mut merged.tools, &base.tools, &state.tools, &mut conflicts,
+        |r| r.original_name.as_ref(), |r| r.status, EditorToolRow::form_fields,
+        |r, f| r.apply_form_fields(f).is_ok());
+    merge_rows("env", &mut merged.env_vars, &base.env_vars, &state.env_vars, &mut conflicts,
+        |r| r.original_key.as_ref(), |r| r.status, EditorEnvRow::form_fields,
+        |r, f| { r.apply_form_fields(f); true });
+    merge_rows("tasks", &mut merged.tasks, &base.tasks, &state.tasks, &mut conflicts,
+        |r| r.original_name.as_ref(), |r| r.status, EditorTaskRow::form_fields,
+        |r, f| { r.apply_form_fields(f); true });
+    merged.dirty = state.dirty;
+    Ok((merged, conflicts))
+}
+
+/// Re-apply the touched rows of `pending` on top of the on-disk `rows`, merging
+/// modified rows field by field against their `base` version. `apply` returns
+/// false when the merged fields cannot be applied.
+#[allow(clippy::too_many_arguments)]
+fn merge_rows<R: Clone>(
+    section: &str,
+    rows: &mut Vec<R>,
+    base: &[R],
+    pending: &[R],
+    conflicts: &mut Vec<String>,
+    key_of: impl Fn(&R) -> Option<&String>,
+    status_of: impl Fn(&R) -> EditorRowStatus,
+    fields_of: impl Fn(&R) -> Vec<String>,
+    apply: impl Fn(&mut R, &[String]) -> bool,
+) {
+    let find = |list: &[R], key: Option<&String>| {
+        key.and_then(|k| list.iter().position(|r| key_of(r) == Some(k)))
+    };
+    for row in pending.iter().filter(|r| status_of(r) != EditorRowStatus::Unchanged) {
+        let key = key_of(row);
+        let before = find(base, key).map(|i| fields_of(&base[i]));
+        let existing = find(rows, key);
+        if let (Some(k), Some(before)) = (key, &before) {
+            let theirs_changed = existing.is_none_or(|i| fields_of(&rows[i]) != *before);
+            if theirs_changed && status_of(row) == EditorRowStatus::Deleted {
+                conflicts.push(format!("{section}.{k}"));
+            }
+        }
+        match (existing, status_of(row)) {
+            (Some(i), EditorRowStatus::Deleted) => {
+                rows.remove(i);
+            }
+            // Deleted here and already gone on disk
+            (None, EditorRowStatus::Deleted) => {}
+            (Some(i), _) => {
+                let Some(before) = before else {
+                    rows[i] = row.clone();
+                    continue;
+                };
+                let ours = fields_of(row);
+                let theirs = fields_of(&rows[i]);
+                let field = |f: &[String], n: usize| f.get(n).cloned().unwrap_or_default();
+                let mut clash = false;
+                let fields: Vec<String> = (0..ours.len())
+                    .map(|n| {
+                        let (b, o, t) = (field(&before, n), field(&ours, n), field(&theirs, n));
+                        if o != b {
+                            clash |= t != b && t != o;
+                            o
+                        } else {
+                            t
+                        }
+                    })
+                    .collect();
+                // Fields that no longer fit together (options for a version the
+                // other side removed) fall back to the editor's row
+                let mut merged = row.clone();
+                if !apply(&mut merged, &fields) {
+                    clash = true;
+                    merged = row.clone();
+                }
+                if clash {
+                    if let Some(k) = key {
+                        conflicts.push(format!("{section}.{k}"));
+                    }
+                }
+                rows[i] = merged;
+            }
+            // Added here, or modified here but removed on disk (our edit wins)
+            (None, _) => {
+                if let Some(k) = key.filter(|_| before.is_some()) {
+                    conflicts.push(format!("{section}.{k}"));
+                }
+                rows.push(row.clone());
+            }
+        }
+    }
+}
+
+/// Expand the `_` entry of `[env]` into one editor row per directive mise knows:
+/// `_.path`, `_.file`, `_.source` (a string, `{ path, ... }` table or an array of
+/// either) and `_.python.venv` (string or `{ path, create }`). Other directives are
+/// left untouched in the document.
+fn env_directive_rows(item: &toml_edit::Item) -> Vec<EditorEnvRow> {
+    let Some(t) = item.as_table_like() else {
+        return Vec::new();
+    };
+    let list = |v: &toml_edit::Item| -> String {
+        match v.as_array() {
+            Some(a) => a.iter().filter_map(directive_entry_path).collect::<Vec<_>>().join("\n"),
+            None => v.as_value().and_then(directive_entry_path).unwrap_or("").to_string(),
+        }
+    };
+    let row = |key: &str, kind: EnvRowKind, value: String, create_venv: bool| EditorEnvRow {
+        key: key.to_string(),
+        value,
+        kind,
+        create_venv,
+        status: EditorRowStatus::Unchanged,
+        original_key: Some(key.to_string()),
+    };
+
+    let mut rows = Vec::new();
+    for (name, kind) in [
+        ("path", EnvRowKind::Path),
+        ("file", EnvRowKind::File),
+        ("source", EnvRowKind::Source),
+    ] {
+        if let Some(v) = t.get(name) {
+            rows.push(row(&format!("_.{name}"), kind, list(v), false));
+        }
+    }
+    let venv = t
+        .get("python")
+        .and_then(|p| p.as_table_like())
+        .and_then(|p| p.get("venv"));
+    if let Some(venv) = venv {
+        let (path, create) = match venv.as_table_like() {
+            Some(v) => (
+                v.get("path").and_then(|p| p.as_str()).unwrap_or("").to_string(),
+                v.get("create").and_then(|c| c.as_bool()).unwrap_or(false),
+            ),
+            None => (venv.as_str().unwrap_or("").to_string(), false),
+        };
+        rows.push(row("_.python.venv", EnvRowKind::PythonVenv, path, create));
+    }
+    rows
+}
+
+/// The path of a directive list entry: a plain string or the `path` of a table
+/// like `{ path = ".env", redact = true }`.
+fn directive_entry_path(v: &toml_edit::Value) -> Option<&str> {
+    match v {
+        toml_edit::Value::InlineTable(t) => t.get("path").and_then(|p| p.as_str()),
+        other => other.as_str(),
+    }
+}
+
+/// Remove a plain variable or directive (by its editor key) from `[env]`,
+/// dropping the `_` / `_.python` tables if they end up empty.
+fn remove_env_entry(env: &mut toml_edit::Table, key: &str) {
+    let kind = EnvRowKind::from_key(key);
+    if kind == EnvRowKind::Var {
+        env.remove(key);
+        return;
+    }
+    let Some(underscore) = env.get_mut("_").and_then(|u| u.as_table_like_mut()) else {
+        return;
+    };
+    match kind {
+        EnvRowKind::PythonVenv => {
+            if let Some(python) = underscore.get_mut("python").and_then(|p| p.as_table_like_mut()) {
+                python.remove("venv");
+                if python.is_empty() {
+                    underscore.remove("python");
+                }
+            }
+        }
+        _ => {
+            underscore.remove(key.trim_start_matches("_."));
+        }
+    }
+    if underscore.is_empty() {
+        env.remove("_");
+    }
+}
+
+/// Write a variable or directive row into `[env]`. Directive lists keep their
+/// string-or-array shape and any `{ path, ... }` tables of entries that remain;
+/// venvs keep their table and its extra keys, with `create` set or removed.
+fn set_env_entry(env: &mut toml_edit::Table, row: &EditorEnvRow) {
+    use toml_edit::{value, Array, InlineTable, Item, Table, Value};
+
+    if row.kind == EnvRowKind::Var {
+        env.insert(&row.key, value(&row.value));
+        return;
+    }
+    let underscore = env.entry("_").or_insert_with(|| {
+        let mut t = Table::new();
+        t.set_dotted(true);
+        Item::Table(t)
+    });
+    let Some(underscore) = underscore.as_table_like_mut() else {
+        return;
+    };
+
+    if row.kind == EnvRowKind::PythonVenv {
+        let existing = underscore
+            .get_mut("python")
+            .and_then(|p| p.as_table_like_mut())
+            .and_then(|p| p.get_mut("venv"))
+            .and_then(|v| v.as_table_like_mut());
+        if let Some(venv) = existing {
+            venv.insert("path", value(&row.value));
+            if row.create_venv {
+                venv.insert("create", value(true));
+            } else {
+                venv.remove("create");
+            }
+            return;
+        }
+        let venv = if row.create_venv {
+            let mut t = InlineTable::new();
+            t.insert("path", row.value.as_str().into());
+            t.insert("create", true.into());
+            value(t)
+        } else {
+            value(&row.value)
+        };
+        match underscore.get_mut("python").and_then(|p| p.as_table_like_mut()) {
+            Some(python) => {
+                python.insert("venv", venv);
+            }
+            None => {
+                let mut python = InlineTable::new();
+                if let Ok(v) = venv.into_value() {
+                    python.insert("venv", v);
+                }
+                underscore.insert("python", value(python));
+            }
+        }
+        return;
+    }
+
+    let name = row.key.trim_start_matches("_.");
+    let previous: Vec<Value> = match underscore.get(name) {
+        Some(Item::Value(Value::Array(a))) => a.iter().cloned().collect(),
+        Some(Item::Value(v)) => vec![v.clone()],
+        _ => Vec::new(),
+    };
+    let was_array = underscore.get(name).is_some_and(|v| v.is_array());
+    // Reuse the previous entry for a path so its table options survive the rewrite
+    let entries: Vec<Value> = row
+        .list_entries()
+        .iter()
+        .map(|path| {
+            previous
+                .iter()
+                .find(|v| directive_entry_path(v) == Some(path.as_str()))
+                .cloned()
+                .unwrap_or_else(|| path.as_str().into())
+        })
+        .collect();
+    let new = if entries.len() == 1 && !was_array {
+        let mut single = entries.into_iter().next().unwrap_or_else(|| "".into());
+        single.decor_mut().clear();
+        value(single)
+    } else {
+        let mut array: Array = entries.into_iter().collect();
+        array.fmt();
+        value(array)
+    };
+    underscore.insert(name, new);
+}
+
+/// Variables a directive row makes available, for display in the Environment tab.
+/// `_.source` scripts are never run for this: they show as the scripts themselves.
+/// Dotenv keys are read separately by `dotenv_keys`.
+pub fn env_directive_outputs(row: &EditorEnvRow) -> Vec<String> {
+    match row.kind {
+        EnvRowKind::Var => vec![row.key.clone()],
+        EnvRowKind::Path => vec!["PATH".to_string()],
+        EnvRowKind::PythonVenv => vec!["VIRTUAL_ENV".to_string(), "PATH".to_string()],
+        EnvRowKind::Source => row
+            .list_entries()
+            .iter()
+            .map(|script| format!("exports of {script}"))
+            .collect(),
+        EnvRowKind::File => Vec::new(),
+    }
+}
+
+/// Keys defined by the dotenv `files` of a `_.file` directive, read relative to the
+/// config file's directory. Unreadable files are skipped.
+pub async fn dotenv_keys(config_path: &str, files: &[String]) -> Vec<String> {
+    let base = std::path::Path::new(config_path)
+        .parent()
+        .unwrap_or(std::path::Path::new("."))
+        .to_path_buf();
+    let mut vars: Vec<String> = Vec::new();
+    for file in files {
+        let Ok(contents) = tokio::fs::read_to_string(base.join(file)).await else {
+            continue;
+        };
+        let keys = contents
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter_map(|l| l.trim_start_matches("export ").split_once('='))
+            .map(|(k, _)| k.trim().to_string());
+        for key in keys {
+            if !vars.contains(&key) {
+                vars.push(key);
+            }
         }
     }
+    vars
+}
 
-    // Filesystem indicators (higher priority)
-    macro_rules! insert_tool {
-        ($name:expr, $version:expr, $source:expr) => {
-            tools.insert($name.to_string(), DetectedTool {
-                name: $name.to_string(),
-                version: $version,
-                source: $source.to_string(),
-                enabled: true,
-                installed: false, // populated below
-            });
-        };
+/// Build an editor row from a `[tools]` entry. Handles the shapes mise accepts:
+/// `"20"`, `["20", "18"]`, `{ version = "20", ... }` and arrays mixing strings
+/// and inline tables (options are taken from the first table).
+fn tool_row_from_item(name: &str, item: &toml_edit::Item) -> EditorToolRow {
+    use toml_edit::Value;
+
+    fn options_of(t: &dyn toml_edit::TableLike) -> Vec<(String, String)> {
+        t.iter()
+            .filter(|(k, _)| *k != "version")
+            .map(|(k, v)| (k.to_string(), toml_value_text(v)))
+            .collect()
     }
 
-    if base.join("package.json").exists() {
-        let (v, s) = nvmrc_version.as_deref().map(|v| (v.to_string(), ".nvmrc")).unwrap_or(("lts".to_string(), "package.json"));
-        insert_tool!("node", v, s);
-    }
-    if base.join("Cargo.toml").exists() {
-        insert_tool!("rust", "stable".to_string(), "Cargo.toml");
-    }
-    if base.join("pyproject.toml").exists() || base.join("requirements.txt").exists() {
-        let src = if base.join("pyproject.toml").exists() { "pyproject.toml" } else { "requirements.txt" };
-        let (v, s) = python_version.as_deref().map(|v| (v.to_string(), ".python-version")).unwrap_or(("latest".to_string(), src));
-        insert_tool!("python", v, s);
-    }
-    if base.join("go.mod").exists() {
-        insert_tool!("go", "latest".to_string(), "go.mod");
-    }
-    if base.join("Gemfile").exists() {
-        let (v, s) = ruby_version.as_deref().map(|v| (v.to_string(), ".ruby-version")).unwrap_or(("latest".to_string(), "Gemfile"));
-        insert_tool!("ruby", v, s);
+    let mut row = EditorToolRow {
+        name: name.to_string(),
+        original_name: Some(name.to_string()),
+        ..Default::default()
+    };
+    let push_value = |v: &Value, row: &mut EditorToolRow| {
+        let options = match v {
+            Value::String(s) => {
+                row.versions.push(s.value().clone());
+                Vec::new()
+            }
+            Value::InlineTable(t) => {
+                let ver = t.get("version").and_then(|v| v.as_str()).unwrap_or("");
+                row.versions.push(ver.to_string());
+                options_of(t)
+            }
+            other => {
+                row.versions.push(other.to_string().trim().to_string());
+                Vec::new()
+            }
+        };
+        row.options.push(options);
+    };
+
+    if let Some(t) = item.as_table_like() {
+        if let Some(ver) = t.get("version").and_then(|v| v.as_str()) {
+            row.versions.push(ver.to_string());
+        }
+        row.options = vec![options_of(t)];
+    } else if let Some(arr) = item.as_array() {
+        for v in arr.iter() {
+            push_value(v, &mut row);
+        }
+    } else if let Some(v) = item.as_value() {
+        push_value(v, &mut row);
     }
-    if base.join("composer.json").exists() {
-        insert_tool!("php", "latest".to_string(), "composer.json");
+    while row.options.last().is_some_and(Vec::is_empty) {
+        row.options.pop();
     }
+    row
+}
 
-    // Standalone legacy pin files (no matching indicator file)
-    if !tools.contains_key("node") {
-        if let Some(v) = &nvmrc_version { insert_tool!("node", v.clone(), ".nvmrc"); }
-    }
-    if !tools.contains_key("python") {
-        if let Some(v) = &python_version { insert_tool!("python", v.clone(), ".python-version"); }
+/// Editable text for a TOML value: strings unquoted, anything else in TOML syntax.
+fn toml_value_text(item: &toml_edit::Item) -> String {
+    item.as_str()
+        .map(String::from)
+        .unwrap_or_else(|| item.to_string().trim().to_string())
+}
+
+/// Parse option text back into a TOML value. Strings stay strings unless the key
+/// previously held a non-string value, or the text is a bare boolean, array or table.
+fn option_value(text: &str, previous: Option<&toml_edit::Item>) -> toml_edit::Value {
+    let was_string = previous.map(|p| p.is_str()).unwrap_or(true);
+    let looks_structured = text.starts_with('[') || text.starts_with('{');
+    if text == "true" || text == "false" || looks_structured || !was_string {
+        if let Ok(v) = text.parse::<toml_edit::Value>() {
+            return v;
+        }
     }
-    if !tools.contains_key("ruby") {
-        if let Some(v) = &ruby_version { insert_tool!("ruby", v.clone(), ".ruby-version"); }
+    text.into()
+}
+
+/// Turn an editor row back into a `[tools]` entry, keeping the existing shape
+/// (string, array, inline table or `[tools.x]` table) and any unchanged option
+/// values and formatting where possible.
+fn tool_item_from_row(row: &EditorToolRow, existing: Option<toml_edit::Item>) -> toml_edit::Item {
+    use toml_edit::{value, Array, Item, Value};
+
+    // Update a table in place: set version, then sync options. Returns true if
+    // keys were added or removed (inline tables then need reformatting).
+    fn sync_table(t: &mut dyn toml_edit::TableLike, version: Option<&str>, options: &[(String, String)]) -> bool {
+        let keys_before = t.len();
+        match version {
+            Some(v) => {
+                t.insert("version", value(v));
+            }
+            None => {
+                t.remove("version");
+            }
+        }
+        let stale: Vec<String> = t
+            .iter()
+            .map(|(k, _)| k.to_string())
+            .filter(|k| k != "version" && !options.iter().any(|(o, _)| o == k))
+            .collect();
+        for k in stale {
+            t.remove(&k);
+        }
+        for (k, text) in options {
+            let previous = t.get(k);
+            if previous.is_some_and(|p| toml_value_text(p) == *text) {
+                continue;
+            }
+            let v = option_value(text, previous);
+            t.insert(k, value(v));
+        }
+        t.len() != keys_before
     }
 
-    // Cross-reference with `mise ls -J` to mark already-installed tools.
-    // JSON structure: { "tool-name": [ { "version": "x.y.z", ... }, ... ] }
-    if let Ok(json) = run_mise(&["ls", "-J"]).await {
-        if let Ok(map) = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&json) {
-            for (tool_name, entry) in tools.iter_mut() {
-                if let Some(versions) = map.get(tool_name).and_then(|v| v.as_array()) {
-                    let wanted = entry.version.as_str();
-                    entry.installed = versions.iter().any(|v| {
-                        let installed_ver = v["version"].as_str().unwrap_or("");
-                        wanted == "latest" || wanted == "stable" || wanted == "lts"
-                            || installed_ver == wanted
-                            || installed_ver.starts_with(&format!("{wanted}."))
-                    });
+    let first = row.versions.first().map(String::as_str);
+
+    // Standard `[tools.x]` tables and inline tables keep their shape.
+    if let Some(mut item) = existing.clone().filter(|i| i.is_table_like()) {
+        if row.versions.len() <= 1 {
+            let reshaped = item
+                .as_table_like_mut()
+                .is_some_and(|t| sync_table(t, first, row.options_for(0)));
+            if reshaped {
+                if let Some(t) = item.as_inline_table_mut() {
+                    t.fmt();
                 }
             }
+            return item;
         }
     }
 
-    let mut result: Vec<DetectedTool> = tools.into_values().collect();
-    result.sort_by(|a, b| a.name.cmp(&b.name));
-    result
-}
+    let decor = existing
+        .as_ref()
+        .and_then(|i| i.as_value())
+        .map(|v| v.decor().clone());
+    let was_array = existing.as_ref().is_some_and(|i| i.is_array());
+
+    // Existing inline tables, to keep the formatting of each version's options
+    let existing_tables: Vec<&toml_edit::InlineTable> = match existing.as_ref() {
+        Some(i) if i.is_inline_table() => i.as_inline_table().into_iter().collect(),
+        Some(i) => i
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_inline_table()).collect())
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    // A version with options lives on an inline table; the rest stay plain strings
+    let entry = |i: usize, version: &str| -> Value {
+        let options = row.options_for(i);
+        if options.is_empty() {
+            return version.into();
+        }
+        let mut t = existing_tables
+            .iter()
+            .find(|t| t.get("version").and_then(|v| v.as_str()) == Some(version))
+            .or_else(|| existing_tables.get(i))
+            .map(|t| (*t).clone())
+            .unwrap_or_default();
+        if sync_table(&mut t, Some(version), options) {
+            t.fmt();
+        }
+        Value::InlineTable(t)
+    };
 
-/// Parse `.tool-versions` in `dir` and return a DetectedTool per line.
-/// Format: `tool version` per line; `#` comments and blank lines ignored.
-pub fn migrate_legacy_pins(dir: &str) -> Vec<DetectedTool> {
-    let path = std::path::Path::new(dir).join(".tool-versions");
-    let Ok(contents) = std::fs::read_to_string(&path) else { return Vec::new() };
-    contents
-        .lines()
-        .filter(|l| !l.trim().is_empty() && !l.trim().starts_with('#'))
-        .filter_map(|line| {
-            let mut parts = line.split_whitespace();
-            let name = parts.next()?.to_string();
-            let version = parts.next().unwrap_or("latest").to_string();
-            Some(DetectedTool { name, version, source: ".tool-versions".to_string(), enabled: true, installed: false })
-        })
-        .collect()
-}
+    let mut new_value: Value = if row.versions.len() > 1 || was_array {
+        row.versions
+            .iter()
+            .enumerate()
+            .map(|(i, v)| entry(i, v))
+            .collect::<Array>()
+            .into()
+    } else {
+        entry(0, first.unwrap_or("latest"))
+    };
 
-/// Write a .mise.toml file to `dir` with the given tool name/version pairs.
-/// Overwrites any existing .mise.toml. Uses atomic write (temp file + rename).
-pub async fn write_mise_toml(dir: &str, tools: &[(String, String)]) -> Result<(), String> {
-    use std::path::Path;
-    let path = Path::new(dir).join(".mise.toml");
-    let tmp_path = Path::new(dir).join(".mise.toml.tmp");
+    if let Some(decor) = decor {
+        *new_value.decor_mut() = decor;
+    }
+    Item::Value(new_value)
+}
 
-    let mut content = String::from("[tools]\n");
-    for (name, version) in tools {
-        if version.is_empty() || version == "latest" {
-            content.push_str(&format!("{name} = \"latest\"\n"));
-        } else {
-            content.push_str(&format!("{name} = \"{version}\"\n"));
+/// Build an editor row from a `[tasks]` entry: either a bare run string or a table
+/// (standard `[tasks.x]` or inline) with `run`, `description`, `depends`, etc.
+fn task_row_from_item(name: &str, item: &toml_edit::Item) -> EditorTaskRow {
+    let str_list = |v: Option<&toml_edit::Item>| -> Vec<String> {
+        match v {
+            Some(v) if v.is_str() => v.as_str().map(|s| vec![s.to_string()]).unwrap_or_default(),
+            Some(v) => v
+                .as_array()
+                .map(|a| a.iter().filter_map(|x| x.as_str()).map(String::from).collect())
+                .unwrap_or_default(),
+            None => Vec::new(),
         }
-    }
+    };
 
-    tokio::fs::write(&tmp_path, &content)
-        .await
-        .map_err(|e| format!("Failed to write temp file: {e}"))?;
-    tokio::fs::rename(&tmp_path, &path)
-        .await
-        .map_err(|e| format!("Failed to rename temp file: {e}"))?;
-    Ok(())
-}
+    let mut row = EditorTaskRow {
+        name: name.to_string(),
+        original_name: Some(name.to_string()),
+        ..Default::default()
+    };
+    if let Some(s) = item.as_str() {
+        row.command = s.to_string();
+        return row;
+    }
+    let Some(t) = item.as_table_like() else {
+        row.command = item.to_string().trim().trim_matches('"').to_string();
+        return row;
+    };
 
-/// Load EditorStates for all TOML config files known to mise.
-pub async fn fetch_editor_states() -> Vec<EditorState> {
-    let configs = fetch_config().await.unwrap_or_default();
-    let mut states = Vec::new();
-    for config in &configs {
-        if config.path.ends_with(".toml") {
-            if let Ok(state) = parse_config_for_editor(&config.path).await {
-                states.push(state);
-            }
+    match t.get("run") {
+        Some(run) if run.is_array() => {
+            row.run_is_array = true;
+            row.command = str_list(Some(run)).join("\n");
         }
+        Some(run) => row.command = run.as_str().unwrap_or("").to_string(),
+        None => {}
     }
-    states
+    row.description = t.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    row.dir = t.get("dir").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    row.depends = str_list(t.get("depends"));
+    row.sources = str_list(t.get("sources"));
+    if let Some(env) = t.get("env").and_then(|v| v.as_table_like()) {
+        row.env = env
+            .iter()
+            .map(|(k, v)| {
+                let val = v
+                    .as_str()
+                    .map(String::from)
+                    .unwrap_or_else(|| v.to_string().trim().to_string());
+                (k.to_string(), val)
+            })
+            .collect();
+    }
+    row
 }
 
-/// Parse a .mise.toml file into EditorState using toml_edit for round-trip preservation.
-/// Returns an EditorState with tools from [tools], env vars from [env], tasks from [tasks].
-pub async fn parse_config_for_editor(path: &str) -> Result<EditorState, String> {
-    use toml_edit::DocumentMut;
-    let contents = tokio::fs::read_to_string(path).await
-        .map_err(|e| format!("Failed to read {path}: {e}"))?;
-    let doc: DocumentMut = contents.parse::<DocumentMut>()
-        .map_err(|e| format!("Failed to parse TOML: {e}"))?;
+/// Turn an editor row back into a `[tasks]` entry. When `existing` is a table the
+/// edited keys are updated in place so formatting and any other keys are kept;
+/// keys whose value didn't change are left untouched, keeping their TOML type and
+/// shape (e.g. `depends = "build"` stays a string, `env` numbers stay numbers).
+fn task_item_from_row(row: &EditorTaskRow, existing: Option<toml_edit::Item>) -> toml_edit::Item {
+    use toml_edit::{value, Array, InlineTable, Item, Table};
 
-    let mut tools = Vec::new();
-    if let Some(table) = doc.get("tools").and_then(|v| v.as_table()) {
-        for (key, value) in table.iter() {
-            let version = match value.as_str() {
-                Some(s) => s.to_string(),
-                None => match value.as_array() {
-                    Some(arr) => arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .next()
-                        .unwrap_or("?")
-                        .to_string(),
-                    None => value.to_string().trim_matches('"').to_string(),
-                },
-            };
-            tools.push(EditorToolRow {
-                name: key.to_string(),
-                version,
-                status: EditorRowStatus::Unchanged,
-                original_name: Some(key.to_string()),
-            });
-        }
+    let existing_is_table = existing.as_ref().is_some_and(|i| i.is_table_like());
+    if row.is_simple() && !existing_is_table {
+        return value(&row.command);
     }
 
-    let mut env_vars = Vec::new();
-    if let Some(table) = doc.get("env").and_then(|v| v.as_table()) {
-        for (key, value) in table.iter() {
-            let val_str = match value.as_str() {
-                Some(s) => s.to_string(),
-                None => value.to_string().trim_matches('"').to_string(),
-            };
-            env_vars.push(EditorEnvRow {
-                key: key.to_string(),
-                value: val_str,
-                status: EditorRowStatus::Unchanged,
-                original_key: Some(key.to_string()),
-            });
-        }
-    }
+    let orig = existing
+        .as_ref()
+        .filter(|i| i.is_table_like())
+        .map(|i| task_row_from_item(&row.name, i));
+    let mut item = match existing {
+        Some(i) if i.is_table_like() => i,
+        _ => Item::Table(Table::new()),
+    };
+    let Some(t) = item.as_table_like_mut() else {
+        return value(&row.command);
+    };
+    let unchanged = |same: fn(&EditorTaskRow, &EditorTaskRow) -> bool| {
+        orig.as_ref().is_some_and(|o| same(o, row))
+    };
 
-    let mut tasks = Vec::new();
-    if let Some(table) = doc.get("tasks").and_then(|v| v.as_table()) {
-        for (key, value) in table.iter() {
-            let command = match value.as_str() {
-                Some(s) => s.to_string(),
-                None => match value.as_table() {
-                    Some(t) => t.get("run")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string(),
-                    None => value.to_string().trim_matches('"').to_string(),
-                },
-            };
-            tasks.push(EditorTaskRow {
-                name: key.to_string(),
-                command,
-                status: EditorRowStatus::Unchanged,
-                original_name: Some(key.to_string()),
-            });
+    // A list that was a single string stays one while it has a single entry
+    let list_item = |items: &[String], was: Option<&Item>| -> Item {
+        match items {
+            [one] if was.is_some_and(|w| w.is_str()) => value(one),
+            _ => value(items.iter().map(String::as_str).collect::<Array>()),
+        }
+    };
+    let set_or_remove = |t: &mut dyn toml_edit::TableLike, key: &str, new: Option<Item>| match new {
+        Some(v) => {
+            t.insert(key, v);
+        }
+        None => {
+            t.remove(key);
         }
+    };
+
+    if !unchanged(|o, r| o.command == r.command && o.run_is_array == r.run_is_array) {
+        let run = if row.run_is_array {
+            let lines: Vec<String> = row.command.lines().map(String::from).collect();
+            list_item(&lines, None)
+        } else {
+            value(&row.command)
+        };
+        set_or_remove(t, "run", Some(run));
+    }
+    if !unchanged(|o, r| o.description == r.description) {
+        set_or_remove(
+            t,
+            "description",
+            (!row.description.is_empty()).then(|| value(&row.description)),
+        );
+    }
+    if !unchanged(|o, r| o.depends == r.depends) {
+        let new = (!row.depends.is_empty()).then(|| list_item(&row.depends, t.get("depends")));
+        set_or_remove(t, "depends", new);
+    }
+    if !unchanged(|o, r| o.dir == r.dir) {
+        set_or_remove(t, "dir", (!row.dir.is_empty()).then(|| value(&row.dir)));
+    }
+    if !unchanged(|o, r| o.env == r.env) {
+        let old_env = t.get("env").and_then(|e| e.as_table_like());
+        let new = (!row.env.is_empty()).then(|| {
+            let mut env = InlineTable::new();
+            for (k, v) in &row.env {
+                env.insert(k, env_value(v, old_env.and_then(|e| e.get(k))));
+            }
+            value(env)
+        });
+        set_or_remove(t, "env", new);
     }
+    if !unchanged(|o, r| o.sources == r.sources) {
+        let new = (!row.sources.is_empty()).then(|| list_item(&row.sources, t.get("sources")));
+        set_or_remove(t, "sources", new);
+    }
+    item
+}
 
-    Ok(EditorState {
-        file_path: path.to_string(),
-        tab: EditorTab::Tools,
-        tools,
-        env_vars,
-        tasks,
-        selected: 0,
-        editing: false,
-        edit_column: 0,
-        edit_buffer: String::new(),
-        raw_document: doc.to_string(),
-        dirty: false,
-    })
+/// A task env value as TOML: the existing value when the text is unchanged,
+/// otherwise the existing value's type (integer, float, bool) when `text` still
+/// parses as it, else a string.
+fn env_value(text: &str, existing: Option<&toml_edit::Item>) -> toml_edit::Value {
+    use toml_edit::Value;
+    let Some(old) = existing.and_then(|i| i.as_value()) else {
+        return text.into();
+    };
+    let old_text = old
+        .as_str()
+        .map(String::from)
+        .unwrap_or_else(|| old.to_string().trim().to_string());
+    if old_text == text {
+        let mut kept = old.clone();
+        kept.decor_mut().clear();
+        return kept;
+    }
+    match old {
+        Value::Integer(_) => text.trim().parse::<i64>().map(Value::from).unwrap_or_else(|_| text.into()),
+        Value::Float(_) => text.trim().parse::<f64>().map(Value::from).unwrap_or_else(|_| text.into()),
+        Value::Boolean(_) => text.trim().parse::<bool>().map(Value::from).unwrap_or_else(|_| text.into()),
+        _ => text.into(),
+    }
 }
 
 /// Write editor changes back to the .mise.toml file using toml_edit for round-trip preservation.
 /// Applies all modifications (edits, adds, deletes) to the stored Document, then writes atomically.
 pub async fn write_editor_changes(state: &EditorState) -> Result<String, String> {
-    use toml_edit::{value, DocumentMut, Item, Table};
+    if changed_on_disk(state) {
+        return Err(format!(
+            "{} changed on disk since it was opened; press w again to merge or reload",
+            state.file_path
+        ));
+    }
+    let content = render_editor_changes(state)?;
+
+    // Atomic write: temp file + rename
+    let path = std::path::Path::new(&state.file_path);
+    let tmp_path = path.with_extension("toml.tmp");
+
+    tokio::fs::write(&tmp_path, &content).await
+        .map_err(|e| format!("Failed to write temp file: {e}"))?;
+    tokio::fs::rename(&tmp_path, path).await
+        .map_err(|e| format!("Failed to rename temp file: {e}"))?;
+
+    Ok(format!("Saved {}", state.file_path))
+}
+
+/// Apply editor changes to the stored Document and return the TOML that would be
+/// written, without touching disk (used for the diff preview and by the writer).
+pub fn render_editor_changes(state: &EditorState) -> Result<String, String> {
+    use toml_edit::{DocumentMut, Item, Table};
+
+    // Implicit so a section with nothing in it is not emitted as an empty header
+    let new_implicit_table = || {
+        let mut t = Table::new();
+        t.set_implicit(true);
+        Item::Table(t)
+    };
     let mut doc: DocumentMut = state.raw_document.parse::<DocumentMut>()
         .map_err(|e| format!("Failed to re-parse document: {e}"))?;
 
     // Apply tool changes
     {
-        let tools_table = doc.entry("tools").or_insert(Item::Table(Table::new()));
+        let tools_table = doc.entry("tools").or_insert(new_implicit_table());
         let table = tools_table.as_table_mut()
             .ok_or_else(|| "[tools] is not a table".to_string())?;
 
@@ -783,14 +2719,15 @@ pub async fn write_editor_changes(state: &EditorState) -> Result<String, String>
         for row in &state.tools {
             match row.status {
                 EditorRowStatus::Modified => {
-                    // Remove old key if renamed
-                    if let Some(ref orig) = row.original_name {
-                        if orig != &row.name { table.remove(orig); }
-                    }
-                    table.insert(&row.name, value(&row.version));
+                    // Start from the existing entry so its shape and options survive
+                    // Replacing an existing key in place keeps its position; renames move it
+                    let existing = row.original_name.as_ref().and_then(|orig| {
+                        if orig == &row.name { table.get(orig).cloned() } else { table.remove(orig) }
+                    });
+                    table.insert(&row.name, tool_item_from_row(row, existing));
                 }
                 EditorRowStatus::Added => {
-                    table.insert(&row.name, value(&row.version));
+                    table.insert(&row.name, tool_item_from_row(row, None));
                 }
                 _ => {}
             }
@@ -799,25 +2736,25 @@ pub async fn write_editor_changes(state: &EditorState) -> Result<String, String>
 
     // Apply env changes
     {
-        let env_table = doc.entry("env").or_insert(Item::Table(Table::new()));
+        let env_table = doc.entry("env").or_insert(new_implicit_table());
         let table = env_table.as_table_mut()
             .ok_or_else(|| "[env] is not a table".to_string())?;
 
         for row in &state.env_vars {
             if row.status == EditorRowStatus::Deleted {
-                if let Some(ref orig) = row.original_key { table.remove(orig); }
+                if let Some(ref orig) = row.original_key { remove_env_entry(table, orig); }
             }
         }
         for row in &state.env_vars {
             match row.status {
                 EditorRowStatus::Modified => {
                     if let Some(ref orig) = row.original_key {
-                        if orig != &row.key { table.remove(orig); }
+                        if orig != &row.key { remove_env_entry(table, orig); }
                     }
-                    table.insert(&row.key, value(&row.value));
+                    set_env_entry(table, row);
                 }
                 EditorRowStatus::Added => {
-                    table.insert(&row.key, value(&row.value));
+                    set_env_entry(table, row);
                 }
                 _ => {}
             }
@@ -826,7 +2763,7 @@ pub async fn write_editor_changes(state: &EditorState) -> Result<String, String>
 
     // Apply task changes
     {
-        let tasks_table = doc.entry("tasks").or_insert(Item::Table(Table::new()));
+        let tasks_table = doc.entry("tasks").or_insert(new_implicit_table());
         let table = tasks_table.as_table_mut()
             .ok_or_else(|| "[tasks] is not a table".to_string())?;
 
@@ -838,61 +2775,372 @@ pub async fn write_editor_changes(state: &EditorState) -> Result<String, String>
         for row in &state.tasks {
             match row.status {
                 EditorRowStatus::Modified => {
-                    if let Some(ref orig) = row.original_name {
-                        if orig != &row.name { table.remove(orig); }
-                    }
-                    table.insert(&row.name, value(&row.command));
+                    // Start from the existing definition so unedited keys survive
+                    // Replacing an existing key in place keeps its position; renames move it
+                    let existing = row.original_name.as_ref().and_then(|orig| {
+                        if orig == &row.name { table.get(orig).cloned() } else { table.remove(orig) }
+                    });
+                    table.insert(&row.name, task_item_from_row(row, existing));
                 }
                 EditorRowStatus::Added => {
-                    table.insert(&row.name, value(&row.command));
+                    table.insert(&row.name, task_item_from_row(row, None));
                 }
                 _ => {}
             }
         }
     }
 
-    // Atomic write: temp file + rename
-    let path = std::path::Path::new(&state.file_path);
-    let tmp_path = path.with_extension("toml.tmp");
-    let content = doc.to_string();
+    Ok(doc.to_string())
+}
 
-    tokio::fs::write(&tmp_path, &content).await
-        .map_err(|e| format!("Failed to write temp file: {e}"))?;
-    tokio::fs::rename(&tmp_path, path).await
-        .map_err(|e| format!("Failed to rename temp file: {e}"))?;
+/// Markers delimiting the misetui-managed section of AGENTS.md / CLAUDE.md.
+const AGENT_SECTION_BEGIN: &str = "<!-- misetui:begin (generated; edits inside this block are replaced) -->";
+const AGENT_SECTION_END: &str = "<!-- misetui:end -->";
+
+/// Render the misetui section for agent files from the final root config
+/// `content` (named `config_name`), listing its pinned tools and tasks plus any
+/// package directories that carry their own config.
+pub fn render_agent_section(config_name: &str, content: &str, packages: &[String]) -> String {
+    let doc = content.parse::<toml_edit::DocumentMut>().ok();
+    let tools: Vec<String> = doc
+        .as_ref()
+        .and_then(|d| d.get("tools")?.as_table_like().map(|t| {
+            t.iter()
+                .map(|(name, item)| format!("- {name} {}", tool_row_from_item(name, item).version_display()))
+                .collect()
+        }))
+        .unwrap_or_default();
+    let tasks: Vec<String> = doc
+        .as_ref()
+        .and_then(|d| d.get("tasks")?.as_table_like().map(|t| {
+            t.iter()
+                .map(|(name, item)| {
+                    let description = item
+                        .as_table_like()
+                        .and_then(|t| t.get("description")?.as_str().map(String::from))
+                        .unwrap_or_default();
+                    if description.is_empty() {
+                        format!("- `{name}`")
+                    } else {
+                        format!("- `{name}` — {description}")
+                    }
+                })
+                .collect()
+        }))
+        .unwrap_or_default();
 
-    Ok(format!("Saved {}", state.file_path))
+    let mut lines = vec![
+        AGENT_SECTION_BEGIN.to_string(),
+        "## mise".to_string(),
+        String::new(),
+        "This project uses [mise](https://mise.jdx.dev/) to manage tools and tasks.".to_string(),
+        format!("Run `mise install` first. Tool versions are pinned in `{config_name}`; do not deviate from them."),
+    ];
+    if !tools.is_empty() {
+        lines.extend([String::new(), "### Tools".to_string(), String::new()]);
+        lines.extend(tools);
+    }
+    lines.extend([String::new(), "### Tasks".to_string(), String::new()]);
+    if tasks.is_empty() {
+        lines.push("List tasks with `mise tasks ls` and run them with `mise run <task>`.".to_string());
+    } else {
+        lines.push("Run with `mise run <task>`:".to_string());
+        lines.push(String::new());
+        lines.extend(tasks);
+    }
+    if !packages.is_empty() {
+        lines.extend([String::new(), "### Packages".to_string(), String::new()]);
+        lines.push("These directories pin their own versions; run mise from inside them:".to_string());
+        lines.push(String::new());
+        lines.extend(packages.iter().map(|p| format!("- `{p}`")));
+    }
+    lines.push(AGENT_SECTION_END.to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Insert or replace the misetui section in an agent file. Text outside the
+/// markers is preserved; a file without markers gets the section appended, and
+/// a missing file starts with `title`.
+pub fn upsert_agent_section(existing: Option<&str>, title: &str, section: &str) -> String {
+    let Some(existing) = existing else {
+        return format!("# {title}\n\n{section}");
+    };
+    let begin = existing.find(AGENT_SECTION_BEGIN);
+    let end = existing.find(AGENT_SECTION_END).map(|i| i + AGENT_SECTION_END.len());
+    match (begin, end) {
+        (Some(b), Some(e)) if b < e => {
+            let rest = existing[e..].strip_prefix('\n').unwrap_or(&existing[e..]);
+            format!("{}{section}{rest}", &existing[..b])
+        }
+        _ => {
+            let sep = if existing.ends_with("\n\n") || existing.is_empty() {
+                ""
+            } else if existing.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            format!("{existing}{sep}{section}")
+        }
+    }
+}
+
+/// AGENTS.md and CLAUDE.md contents for `dir` with the misetui section
+/// inserted or updated; everything else in existing files is kept. Only a missing
+/// file starts fresh: one that exists but cannot be read is an error.
+pub fn agent_file_outputs(
+    dir: &str,
+    section: &str,
+) -> Result<Vec<(std::path::PathBuf, String)>, String> {
+    [("AGENTS.md", "Agent Instructions"), ("CLAUDE.md", "CLAUDE.md")]
+        .into_iter()
+        .map(|(file, title)| {
+            let path = std::path::Path::new(dir).join(file);
+            let existing = match std::fs::read_to_string(&path) {
+                Ok(contents) => Some(contents),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+            };
+            let content = upsert_agent_section(existing.as_deref(), title, section);
+            Ok((path, content))
+        })
+        .collect()
 }
 
-/// Write AGENTS.md and CLAUDE.md to `dir` with mise-specific agent instructions.
-/// Silently ignores write errors (non-critical optional feature — BOOT-07).
-pub async fn write_agent_files_for(dir: &str) -> Result<(), String> {
-    use std::path::Path;
-    let base = Path::new(dir);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let agents_content = "\
-# Agent Instructions
+    type Locked = (String, String, Vec<(String, String)>);
 
-This project uses [mise](https://mise.jdx.dev/) to manage tool versions.
-Tool versions are pinned in `.mise.toml` — always use these exact versions.
+    fn locked(text: &str) -> Vec<Locked> {
+        parse_lockfile(text)
+            .into_iter()
+            .map(|l| (l.tool, l.version, l.checksums))
+            .collect()
+    }
+
+    fn pair(a: &str, b: &str) -> (String, String) {
+        (a.to_string(), b.to_string())
+    }
 
-- `mise install` — install pinned tools
-- `mise run <task>` — run a task
-- `mise ls` — list installed tools
-";
+    #[test]
+    fn lockfile_table_with_checksums() {
+        let text = r#"
+[tools.node]
+version = "20.11.0"
+
+[tools.node.checksums]
+"node-v20.11.0-linux-x64.tar.gz" = "sha256:aaa"
+"node-v20.11.0-darwin-arm64.tar.gz" = "sha256:bbb"
+"#;
+        let tools = locked(text);
+        assert_eq!(tools.len(), 1);
+        let (tool, version, sums) = &tools[0];
+        assert_eq!((tool.as_str(), version.as_str()), ("node", "20.11.0"));
+        assert_eq!(sums.len(), 2);
+        assert!(sums.contains(&pair("node-v20.11.0-linux-x64.tar.gz", "sha256:aaa")));
+        assert!(sums.contains(&pair("node-v20.11.0-darwin-arm64.tar.gz", "sha256:bbb")));
+    }
 
-    let claude_content = "\
-# CLAUDE.md
+    #[test]
+    fn lockfile_platform_checksums() {
+        let text = r#"
+[tools.python]
+version = "3.12.7"
+
+[tools.python.platforms.linux-x64]
+checksum = "sha256:111"
+url = "https://example.com/python.tar.gz"
+
+[tools.python.platforms.macos-arm64]
+checksum = "sha256:222"
+"#;
+        let tools = locked(text);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].1, "3.12.7");
+        assert_eq!(
+            tools[0].2,
+            vec![pair("linux-x64", "sha256:111"), pair("macos-arm64", "sha256:222")]
+        );
+    }
 
-Uses mise for tool versions. Run `mise install` first.
-See `.mise.toml` for pinned versions — do not deviate from these.
-Run tasks with `mise run <task>`, list with `mise tasks ls`.
-";
+    #[test]
+    fn lockfile_array_form_locks_every_version() {
+        let text = r#"
+[[tools.node]]
+version = "20.11.0"
+checksums = { "node-20.tar.gz" = "sha256:aaa" }
+
+[[tools.node]]
+version = "18.19.0"
+"#;
+        let tools = locked(text);
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0], ("node".into(), "20.11.0".into(), vec![pair("node-20.tar.gz", "sha256:aaa")]));
+        assert_eq!(tools[1], ("node".into(), "18.19.0".into(), Vec::new()));
+    }
 
-    let agents_path = base.join("AGENTS.md");
-    let claude_path = base.join("CLAUDE.md");
+    #[test]
+    fn lockfile_bare_version_strings() {
+        let text = "[tools]\ngo = \"1.22.1\"\nnode = [\"20.11.0\", \"18.19.0\"]\n";
+        let tools = locked(text);
+        assert_eq!(
+            tools,
+            vec![
+                ("go".into(), "1.22.1".into(), Vec::new()),
+                ("node".into(), "20.11.0".into(), Vec::new()),
+                ("node".into(), "18.19.0".into(), Vec::new()),
+            ]
+        );
+    }
 
-    let _ = tokio::fs::write(&agents_path, agents_content).await;
-    let _ = tokio::fs::write(&claude_path, claude_content).await;
-    Ok(())
+    #[test]
+    fn lockfile_skips_entries_without_version() {
+        let text = "[tools.node]\nbackend = \"core:node\"\n\n[tools.go]\nversion = \"1.22.1\"\n";
+        let tools = locked(text);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].0, "go");
+    }
+
+    #[test]
+    fn lockfile_malformed_or_empty() {
+        assert!(parse_lockfile("not = [valid").is_empty());
+        assert!(parse_lockfile("").is_empty());
+        assert!(parse_lockfile("[settings]\nexperimental = true\n").is_empty());
+    }
+
+    fn merge(existing: &str, tools: &[(&str, &str)], tasks: &[DetectedTask]) -> (String, Vec<WizardMergeEntry>) {
+        let tools: Vec<(String, String)> =
+            tools.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect();
+        merge_wizard_config(existing, &tools, tasks, "mise.toml").unwrap()
+    }
+
+    fn merge_of<'a>(entries: &'a [WizardMergeEntry], name: &str) -> &'a ToolMerge {
+        &entries.iter().find(|e| e.name == name).unwrap().merge
+    }
+
+    #[test]
+    fn wizard_merge_adds_missing_tools() {
+        let (text, entries) = merge("", &[("node", "20"), ("go", "")], &[]);
+        let doc: toml::Value = toml::from_str(&text).unwrap();
+        assert_eq!(doc["tools"]["node"].as_str(), Some("20"));
+        assert_eq!(doc["tools"]["go"].as_str(), Some("latest"));
+        assert_eq!(merge_of(&entries, "node"), &ToolMerge::Added);
+        assert_eq!(merge_of(&entries, "go"), &ToolMerge::Added);
+    }
+
+    #[test]
+    fn wizard_merge_keeps_comments_and_other_tables() {
+        let existing = "# project config\n[env]\nFOO = \"bar\" # keep me\n\n[tools]\nnode = \"18\"\npython = \"3.12\"\n\n[settings]\nexperimental = true\n";
+        let (text, entries) = merge(existing, &[("node", "20")], &[]);
+        assert!(text.contains("# project config"));
+        assert!(text.contains("FOO = \"bar\" # keep me"));
+        assert!(text.contains("experimental = true"));
+        assert!(text.contains("python = \"3.12\""));
+        assert!(text.contains("node = \"20\""));
+        assert_eq!(merge_of(&entries, "node"), &ToolMerge::Changed { from: "18".into() });
+        assert_eq!(merge_of(&entries, "python"), &ToolMerge::Kept);
+    }
+
+    #[test]
+    fn wizard_merge_keeps_matching_and_floating_versions() {
+        let existing = "[tools]\nnode = \"20\"\npython = \"3.11\"\n";
+        let (text, entries) = merge(existing, &[("node", "20"), ("python", "latest")], &[]);
+        assert_eq!(text, existing);
+        assert_eq!(merge_of(&entries, "node"), &ToolMerge::Kept);
+        assert_eq!(merge_of(&entries, "python"), &ToolMerge::Kept);
+        let python = entries.iter().find(|e| e.name == "python").unwrap();
+        assert_eq!(python.version, "3.11");
+    }
+
+    #[test]
+    fn wizard_merge_replaces_first_version_in_existing_shape() {
+        let existing = "[tools]\nnode = [\"18\", \"16\"]\npython = { version = \"3.11\", virtualenv = \".venv\" }\n\n[tools.go]\nversion = \"1.21\"\n";
+        let (text, _) = merge(existing, &[("node", "20"), ("python", "3.12"), ("go", "1.22")], &[]);
+        let doc: toml::Value = toml::from_str(&text).unwrap();
+        let node: Vec<&str> = doc["tools"]["node"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert_eq!(node, ["20", "16"]);
+        assert_eq!(doc["tools"]["python"]["version"].as_str(), Some("3.12"));
+        assert_eq!(doc["tools"]["python"]["virtualenv"].as_str(), Some(".venv"));
+        assert_eq!(doc["tools"]["go"]["version"].as_str(), Some("1.22"));
+        assert!(text.contains("[tools.go]"));
+    }
+
+    #[test]
+    fn wizard_merge_adds_only_new_tasks() {
+        let task = |name: &str, run: &str| DetectedTask {
+            name: name.to_string(),
+            run: run.to_string(),
+            description: format!("{name} it"),
+            ..Default::default()
+        };
+        let existing = "[tasks.build]\nrun = \"cargo build --release\"\n";
+        let (text, entries) = merge(existing, &[], &[task("build", "make build"), task("test", "make test")]);
+        let doc: toml::Value = toml::from_str(&text).unwrap();
+        assert_eq!(doc["tasks"]["build"]["run"].as_str(), Some("cargo build --release"));
+        assert_eq!(doc["tasks"]["test"]["run"].as_str(), Some("make test"));
+        assert_eq!(doc["tasks"]["test"]["description"].as_str(), Some("test it"));
+        assert_eq!(merge_of(&entries, "tasks.build"), &ToolMerge::Kept);
+        assert_eq!(merge_of(&entries, "tasks.test"), &ToolMerge::Added);
+    }
+
+    #[test]
+    fn wizard_merge_rejects_invalid_config() {
+        assert!(merge_wizard_config("[tools\n", &[], &[], "mise.toml").is_err());
+        assert!(merge_wizard_config("tools = 1\n", &[], &[], "mise.toml").is_err());
+    }
+
+    fn agent_section(body: &str) -> String {
+        format!("{AGENT_SECTION_BEGIN}\n{body}\n{AGENT_SECTION_END}\n")
+    }
+
+    #[test]
+    fn agent_section_starts_missing_file_with_title() {
+        let section = agent_section("new");
+        assert_eq!(
+            upsert_agent_section(None, "Agent Instructions", &section),
+            format!("# Agent Instructions\n\n{section}")
+        );
+    }
+
+    #[test]
+    fn agent_section_replaces_between_markers() {
+        let existing = format!("# Notes\n\nHand-written intro.\n\n{}\nFooter stays.\n", agent_section("old\nlines"));
+        let section = agent_section("new");
+        let updated = upsert_agent_section(Some(&existing), "unused", &section);
+        assert_eq!(updated, format!("# Notes\n\nHand-written intro.\n\n{section}\nFooter stays.\n"));
+        assert!(!updated.contains("old"));
+        assert_eq!(updated.matches(AGENT_SECTION_BEGIN).count(), 1);
+    }
+
+    #[test]
+    fn agent_section_update_is_idempotent() {
+        let section = agent_section("same");
+        let once = upsert_agent_section(Some("# Notes\n"), "unused", &section);
+        let twice = upsert_agent_section(Some(&once), "unused", &section);
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn agent_section_appended_without_markers() {
+        let section = agent_section("new");
+        assert_eq!(upsert_agent_section(Some(""), "t", &section), section);
+        assert_eq!(upsert_agent_section(Some("text\n\n"), "t", &section), format!("text\n\n{section}"));
+        assert_eq!(upsert_agent_section(Some("text\n"), "t", &section), format!("text\n\n{section}"));
+        assert_eq!(upsert_agent_section(Some("text"), "t", &section), format!("text\n\n{section}"));
+    }
+
+    #[test]
+    fn agent_section_appended_when_markers_out_of_order() {
+        let existing = format!("{AGENT_SECTION_END}\nmiddle\n{AGENT_SECTION_BEGIN}\n");
+        let section = agent_section("new");
+        let updated = upsert_agent_section(Some(&existing), "t", &section);
+        assert_eq!(updated, format!("{existing}\n{section}"));
+    }
 }
@@ -1,3 +1,4 @@
+use crate::config::BootstrapTemplate;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 
@@ -80,6 +81,103 @@ pub struct ConfigFile {
     pub path: String,
     #[serde(default)]
     pub tools: Vec<String>,
+    /// 1-based position in `mise config ls` output (1 = highest precedence).
+    #[serde(skip)]
+    pub rank: usize,
+    #[serde(skip)]
+    pub kind: ConfigKind,
+    #[serde(skip)]
+    pub trust: TrustState,
+    /// File contents read at load time for the Config tab preview.
+    #[serde(skip)]
+    pub contents: String,
+    /// Lockfile next to this config, if one exists.
+    #[serde(skip)]
+    pub lockfile: Option<String>,
+    /// Entries parsed from `lockfile`.
+    #[serde(skip)]
+    pub locked: Vec<LockedTool>,
+    /// Requested vs locked vs installed per tool; rebuilt whenever configs or tools load.
+    #[serde(skip)]
+    pub lock_rows: Vec<ProjectToolHealth>,
+}
+
+/// What role a config file plays in mise's hierarchy, derived from its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigKind {
+    /// `~/.config/mise/config.toml`, `conf.d` fragments and system config.
+    Global,
+    /// `mise.toml`, `.mise.toml`, `.config/mise.toml` and friends.
+    #[default]
+    Project,
+    /// `mise.local.toml` / `.mise.local.toml` (usually git-ignored).
+    Local,
+    /// `mise.<env>.toml`, selected by `MISE_ENV`.
+    EnvSpecific,
+    /// Idiomatic version files (`.tool-versions`, `.nvmrc`, `.python-version`, ...).
+    Legacy,
+}
+
+impl ConfigKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConfigKind::Global => "global",
+            ConfigKind::Project => "project",
+            ConfigKind::Local => "local",
+            ConfigKind::EnvSpecific => "env",
+            ConfigKind::Legacy => "legacy",
+        }
+    }
+}
+
+/// Trust status of a config file as recorded in mise's state directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrustState {
+    Trusted,
+    #[default]
+    Untrusted,
+    Ignored,
+}
+
+impl TrustState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrustState::Trusted => "✓ trusted",
+            TrustState::Untrusted => "! untrusted",
+            TrustState::Ignored => "∅ ignored",
+        }
+    }
+}
+
+/// A config path known to mise's trust store (or discovered but not yet trusted).
+#[derive(Debug, Clone)]
+pub struct TrustEntry {
+    pub path: String,
+    pub state: TrustState,
+    /// False when a trust or ignore record points at a file that has since been deleted.
+    pub exists: bool,
+    /// The symlink in mise's state dir recording this entry, if any.
+    pub record: Option<std::path::PathBuf>,
+    /// Checked for a bulk trust operation.
+    pub selected: bool,
+}
+
+/// Bulk operation applied from the trust manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustOp {
+    Trust,
+    Untrust,
+    Ignore,
+}
+
+impl TrustOp {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            TrustOp::Trust => "Trusted",
+            TrustOp::Untrust => "Untrusted",
+            TrustOp::Ignore => "Ignored",
+        }
+    }
 }
 
 /// An outdated tool from `mise outdated -J`.
@@ -138,6 +236,9 @@ pub struct EnvVar {
     pub value: String,
     pub source: String,
     pub tool: String,
+    /// True for rows synthesized from an `[env]` directive (`_.path`, `_.file`, ...)
+    /// rather than reported by `mise env`. `tool` then lists the variables it produces.
+    pub directive: bool,
 }
 
 /// Deserializable entry for `mise env --json-extended` (per-variable object).
@@ -160,6 +261,7 @@ impl EnvVar {
                 value: entry.value.unwrap_or_default(),
                 source: entry.source.unwrap_or_default(),
                 tool: entry.tool.unwrap_or_default(),
+                directive: false,
             })
             .collect()
     }
@@ -202,11 +304,40 @@ impl MiseSetting {
     }
 }
 
-/// A prune candidate from `mise prune --dry-run`.
+/// A prune candidate from `mise prune --dry-run`, enriched with install metadata.
 #[derive(Debug, Clone)]
 pub struct PruneCandidate {
     pub tool: String,
     pub version: String,
+    /// Total size of the install directory in bytes (None if it could not be read).
+    pub size_bytes: Option<u64>,
+    /// Seconds since the install directory was last accessed (falls back to mtime).
+    pub last_used_secs: Option<u64>,
+    /// Human-readable explanation of why mise considers this version unused.
+    pub reason: String,
+    /// Position among all installed versions of this tool, newest first (0 = newest).
+    pub rank: usize,
+    /// Scanned projects that still require this version even though no tracked config does.
+    pub required_by: Vec<String>,
+    /// Whether this version is checked for removal.
+    pub selected: bool,
+}
+
+impl PruneCandidate {
+    /// Label used in status messages and CLI arguments (`tool@version`).
+    pub fn spec(&self) -> String {
+        if self.version.is_empty() {
+            self.tool.clone()
+        } else {
+            format!("{}@{}", self.tool, self.version)
+        }
+    }
+
+    /// Whether this version should be kept under the "keep latest N" policy or
+    /// because a scanned project still depends on it.
+    pub fn is_protected(&self, keep_latest: usize) -> bool {
+        self.rank < keep_latest || !self.required_by.is_empty()
+    }
 }
 
 /// Health state of the current working directory's tool requirements.
@@ -215,15 +346,86 @@ pub struct PruneCandidate {
 /// Missing = one or more not installed (covers exact-version pins too);
 /// NoConfig = no local .mise.toml applies to CWD (global config only);
 /// Untrusted = .mise.toml exists but has not been trusted with `mise trust`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum DriftState {
+    #[default]
     Checking,
     Healthy,
+    /// Every requirement is installed, but newer versions matching the pins exist.
+    Outdated,
     Missing,
     NoConfig,
     Untrusted,
 }
 
+/// Why a CWD requirement shows up in the drift breakdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftIssue {
+    /// No version of the tool is installed.
+    Missing,
+    /// Other versions are installed, but not the requested one.
+    WrongVersion,
+    /// Installed, but a newer version matching the requested prefix is available.
+    Outdated,
+}
+
+impl DriftIssue {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DriftIssue::Missing => "missing",
+            DriftIssue::WrongVersion => "wrong version",
+            DriftIssue::Outdated => "outdated",
+        }
+    }
+}
+
+/// One unhealthy requirement from a local config in the CWD.
+#[derive(Debug, Clone)]
+pub struct DriftTool {
+    pub tool: String,
+    /// Version spec from the config (e.g. "20", "latest").
+    pub requested: String,
+    /// Installed versions of the tool, newest last; empty when Missing.
+    pub installed: Vec<String>,
+    /// Newer version available; set for Outdated only.
+    pub latest: String,
+    /// Config file that declares the requirement.
+    pub source: String,
+    pub issue: DriftIssue,
+}
+
+/// Result of [`crate::mise::check_cwd_drift`]: the aggregate header state plus the
+/// per-tool and per-file detail behind it.
+#[derive(Debug, Clone, Default)]
+pub struct DriftReport {
+    pub state: DriftState,
+    /// Directory the check ran in.
+    pub dir: String,
+    /// Unhealthy requirements, in config order.
+    pub tools: Vec<DriftTool>,
+    /// Local requirements with no issues.
+    pub healthy: usize,
+    /// Config files mise refused to load because they are not trusted.
+    pub untrusted: Vec<String>,
+}
+
+impl DriftReport {
+    pub fn has(&self, issue: DriftIssue) -> bool {
+        self.tools.iter().any(|t| t.issue == issue)
+    }
+}
+
+/// A remediation offered by the drift breakdown popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftFix {
+    /// `mise install` in the drift directory (missing and wrong-version tools).
+    InstallMissing,
+    /// `mise trust` every untrusted config.
+    Trust,
+    /// `mise upgrade` in the drift directory (outdated tools).
+    UpgradePins,
+}
+
 /// Health status for a project or individual tool requirement.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProjectHealthStatus {
@@ -238,6 +440,26 @@ pub enum ProjectHealthStatus {
 }
 
 impl ProjectHealthStatus {
+    /// Sort key: the most urgent status first.
+    pub fn rank(&self) -> u8 {
+        match self {
+            ProjectHealthStatus::Missing => 0,
+            ProjectHealthStatus::Outdated => 1,
+            ProjectHealthStatus::NoConfig => 2,
+            ProjectHealthStatus::Healthy => 3,
+        }
+    }
+
+    /// Plain name, without the status glyph, for exported reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProjectHealthStatus::Healthy => "healthy",
+            ProjectHealthStatus::Outdated => "outdated",
+            ProjectHealthStatus::Missing => "missing",
+            ProjectHealthStatus::NoConfig => "no config",
+        }
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             ProjectHealthStatus::Healthy  => "● healthy",
@@ -258,6 +480,60 @@ pub struct ProjectToolHealth {
     /// Installed version string, or empty if not installed.
     pub installed: String,
     pub status: ProjectHealthStatus,
+    /// Version pinned in the config's lockfile, or empty if unlocked.
+    pub locked: String,
+    /// Locked checksum for this platform (e.g. `sha256:…`), or empty if none recorded.
+    pub checksum: String,
+    /// Lock status; None when the config has no lockfile.
+    pub lock: Option<LockState>,
+}
+
+/// One `[tools.<name>]` entry from a `mise.lock` file.
+#[derive(Debug, Clone, Default)]
+pub struct LockedTool {
+    pub tool: String,
+    pub version: String,
+    /// `(asset or platform, checksum)` pairs: `checksums` entries, then `platforms`.
+    pub checksums: Vec<(String, String)>,
+}
+
+/// How a requirement relates to its config's lockfile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockState {
+    /// Locked version satisfies the requirement and is installed.
+    Locked,
+    /// The lockfile has no entry for this tool.
+    Unlocked,
+    /// Locked version no longer satisfies the requirement; the lock needs regenerating.
+    Stale,
+    /// Locked version is not the one installed.
+    Drift,
+}
+
+impl LockState {
+    /// Plain name, without the status glyph, for exported reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LockState::Locked => "locked",
+            LockState::Unlocked => "unlocked",
+            LockState::Stale => "stale",
+            LockState::Drift => "drift",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LockState::Locked => "✓ locked",
+            LockState::Unlocked => "? unlocked",
+            LockState::Stale => "✗ stale",
+            LockState::Drift => "≠ drift",
+        }
+    }
+
+    /// True for states that break reproducibility (anything but Locked).
+    pub fn is_issue(&self) -> bool {
+        *self != LockState::Locked
+    }
 }
 
 /// A project discovered by scanning configured directories.
@@ -267,16 +543,135 @@ pub struct MiseProject {
     pub name: String,
     /// Absolute path to the project directory (parent of .mise.toml).
     pub path: String,
+    /// Absolute path to the project's .mise.toml.
+    pub config_path: String,
     /// Number of tools declared in .mise.toml.
     pub tool_count: usize,
     /// Aggregate health status (worst-case of all tool health statuses).
     pub health: ProjectHealthStatus,
     /// Per-tool health breakdown (populated during scan).
     pub tools: Vec<ProjectToolHealth>,
+    /// Absolute path to the project's lockfile, if one exists.
+    pub lockfile: Option<String>,
+    /// Seconds since the config file was last modified.
+    pub config_age_secs: Option<u64>,
+    /// Git metadata; None outside a git work tree or when git is unavailable.
+    pub git: Option<GitInfo>,
+    /// Group from misetui's config, if the project falls under one.
+    pub group: Option<String>,
+    /// Pinned to the top of the list.
+    pub favorite: bool,
+    pub tags: Vec<String>,
 }
 
-/// A tool detected from filesystem indicators or migrated from legacy pin files.
+impl MiseProject {
+    /// Copy group, favorite and tags from misetui's config.
+    pub fn apply_config(&mut self, config: &crate::config::MisetuiConfig) {
+        let path = std::path::Path::new(&self.path);
+        self.group = config.group_for(path);
+        self.favorite = config.is_favorite(path);
+        self.tags = config.tags_for(path);
+    }
+
+    /// Section in the grouped list: favorites, then groups by name, then the rest.
+    pub fn section(&self) -> (u8, Option<&str>) {
+        match (self.favorite, &self.group) {
+            (true, _) => (0, None),
+            (false, Some(group)) => (1, Some(group)),
+            (false, None) => (2, None),
+        }
+    }
+
+    /// Number of requirements whose lock entry is unlocked, stale or drifted.
+    pub fn lock_issues(&self) -> usize {
+        self.tools
+            .iter()
+            .filter(|t| t.lock.is_some_and(|l| l.is_issue()))
+            .count()
+    }
+}
+
+/// Git state of the work tree a scanned project lives in.
 #[derive(Debug, Clone)]
+pub struct GitInfo {
+    /// Current branch, or `(detached)`.
+    pub branch: String,
+    /// The mise config has uncommitted changes (modified, staged or untracked).
+    pub config_dirty: bool,
+    /// Seconds since the last commit on HEAD; None for a repo without commits.
+    pub commit_age_secs: Option<u64>,
+}
+
+/// A remediation run across every project in the (filtered) Projects list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FleetOp {
+    /// Install the tools unhealthy projects are missing.
+    InstallMissing,
+    /// `mise upgrade` in each project.
+    UpgradePins,
+}
+
+impl FleetOp {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FleetOp::InstallMissing => "Install missing tools",
+            FleetOp::UpgradePins => "Upgrade pins",
+        }
+    }
+}
+
+/// A tool version the fleet plan installs once for every project that needs it.
+#[derive(Debug, Clone)]
+pub struct FleetInstall {
+    pub tool: String,
+    pub version: String,
+    /// Names of the projects sharing this install.
+    pub projects: Vec<String>,
+}
+
+/// One project in a fleet plan.
+#[derive(Debug, Clone)]
+pub struct FleetTarget {
+    pub name: String,
+    pub path: String,
+    /// `tool@version` specs this project needs.
+    pub specs: Vec<String>,
+    /// Set when planning failed for this project; it is skipped on run.
+    pub error: Option<String>,
+}
+
+/// Consolidated preview of a fleet operation, shown before anything runs.
+#[derive(Debug, Clone)]
+pub struct FleetPlan {
+    pub op: FleetOp,
+    /// Distinct installs, most shared first.
+    pub installs: Vec<FleetInstall>,
+    pub targets: Vec<FleetTarget>,
+    /// Projects in the filtered set with nothing to do.
+    pub skipped: usize,
+}
+
+/// Outcome of a fleet operation for one project.
+#[derive(Debug, Clone)]
+pub struct FleetResult {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// One place that requires a specific installed tool version (reverse "used by" index).
+#[derive(Debug, Clone)]
+pub struct ToolUsage {
+    /// Project name for scanned projects, or the config file name for active configs.
+    pub project: String,
+    /// Config file that declares the requirement.
+    pub config_path: String,
+    /// Version spec as written in the config (e.g. "3.10", "latest").
+    pub required: String,
+}
+
+/// A tool detected from filesystem indicators or migrated from legacy pin files.
+#[derive(Debug, Clone, Default)]
 pub struct DetectedTool {
     /// Short tool name as used by mise (e.g. "node", "python", "rust", "go", "ruby", "php").
     pub name: String,
@@ -288,6 +683,58 @@ pub struct DetectedTool {
     pub enabled: bool,
     /// True if this tool version is already installed locally (from `mise ls -J` cross-reference).
     pub installed: bool,
+    /// Package directory relative to the wizard target whose config this tool goes
+    /// into; empty for the root config.
+    pub dir: String,
+}
+
+/// A task imported from a package script, Makefile target, justfile recipe,
+/// Cargo alias or pyproject script, offered as a `[tasks]` entry by the wizard.
+#[derive(Debug, Clone, Default)]
+pub struct DetectedTask {
+    /// Task name; prefixed with its source (`make:build`) when names collide.
+    pub name: String,
+    /// Command that runs the original script, e.g. `npm run build` or `make test`.
+    pub run: String,
+    pub description: String,
+    /// Source description for display (e.g. "package.json", "Makefile").
+    pub source: String,
+    /// Whether this task is toggled ON (will be written to the config).
+    pub enabled: bool,
+    /// Package directory relative to the wizard target; empty for the root.
+    pub dir: String,
+}
+
+/// How the wizard's merge treats one `[tools]` or `[tasks]` entry of the config it writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolMerge {
+    /// Not in the existing config; added.
+    Added,
+    /// Already present at a different version, which is replaced.
+    Changed { from: String },
+    /// Already present at this version, or not touched by the wizard.
+    Kept,
+}
+
+/// One line of the wizard's merge preview.
+#[derive(Debug, Clone)]
+pub struct WizardMergeEntry {
+    /// Config file path relative to the wizard target, e.g. `services/api/mise.toml`.
+    pub config: String,
+    /// Tool name, or `tasks.<name>` for an imported task.
+    pub name: String,
+    /// Version after the merge (the `run` command for tasks).
+    pub version: String,
+    pub merge: ToolMerge,
+}
+
+/// Tools detected in one directory of a (possibly monorepo) wizard target.
+#[derive(Debug, Clone)]
+pub struct WizardPackage {
+    /// Directory relative to the wizard target; empty for the target itself.
+    pub dir: String,
+    pub tools: Vec<DetectedTool>,
+    pub tasks: Vec<DetectedTask>,
 }
 
 /// Multi-step wizard state for BOOT-01 through BOOT-07.
@@ -299,14 +746,32 @@ pub struct WizardState {
     pub step: WizardStep,
     /// Tools detected/migrated — each with enabled flag for toggling in Review step.
     pub tools: Vec<DetectedTool>,
-    /// Which tool row is currently highlighted in Review step.
+    /// Tasks imported from existing scripts — toggled in the Review step after the tools.
+    pub tasks: Vec<DetectedTask>,
+    /// Which row is currently highlighted in Review step (tools, then tasks).
     pub selected: usize,
-    /// Generated .mise.toml content (populated when entering Preview step).
+    /// Generated or merged config content (populated when entering Preview step).
     pub preview_content: String,
-    /// If true, write AGENTS.md and CLAUDE.md alongside .mise.toml (BOOT-07).
+    /// If true, insert or update a mise section in AGENTS.md and CLAUDE.md (BOOT-07).
     pub write_agent_files: bool,
     /// Scroll offset for Preview step paragraph.
     pub preview_scroll: usize,
+    /// Per-directory detections; the root (`dir` empty) comes first.
+    pub packages: Vec<WizardPackage>,
+    /// Propose a root config plus per-package overrides instead of a single root config.
+    pub monorepo: bool,
+    /// What writing will do to each config's `[tools]` (populated with preview_content).
+    pub merge: Vec<WizardMergeEntry>,
+    /// Exact (path, content) of every config the Preview step shows; written as-is.
+    pub outputs: Vec<(std::path::PathBuf, String)>,
+    /// AGENTS.md / CLAUDE.md contents when write_agent_files is on; written as-is.
+    pub agent_outputs: Vec<(std::path::PathBuf, String)>,
+    /// Set when a config could not be generated; writing is blocked until resolved.
+    pub preview_error: Option<String>,
+    /// Templates from `~/.config/misetui/templates/`, loaded when the picker opens.
+    pub templates: Vec<BootstrapTemplate>,
+    /// Template layered under the root config, chosen on the Idle step.
+    pub template: Option<BootstrapTemplate>,
 }
 
 /// Steps in the Bootstrap Wizard flow.
@@ -325,6 +790,7 @@ pub enum WizardStep {
 }
 
 /// Which sub-tab is active inside the inline editor popup.
+#[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EditorTab {
     Tools,
@@ -333,9 +799,10 @@ pub enum EditorTab {
 }
 
 /// Change status for a row in the editor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum EditorRowStatus {
     /// Unchanged from the file on disk.
+    #[default]
     Unchanged,
     /// Modified by the user.
     Modified,
@@ -346,34 +813,317 @@ pub enum EditorRowStatus {
 }
 
 /// A single editable row in the editor's Tools sub-tab.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct EditorToolRow {
     pub name: String,
-    pub version: String,
+    /// Requested versions; more than one is written as a TOML array.
+    pub versions: Vec<String>,
+    /// Per-tool options (`postinstall`, `os`, ...) for each entry of `versions`, as
+    /// `(key, value)` with string values unquoted and other values in TOML syntax.
+    /// Entries past the end mean that version has no options.
+    pub options: Vec<Vec<(String, String)>>,
     pub status: EditorRowStatus,
     /// Original name (for rename tracking in toml_edit Document).
     pub original_name: Option<String>,
 }
 
+/// Form labels for the tool editor popup, in the order of `EditorToolRow::form_fields`.
+pub const TOOL_FORM_LABELS: [&str; 3] = ["Name", "Versions", "Options"];
+
+impl EditorToolRow {
+    /// Versions as shown in tables and the form ("20, 18").
+    pub fn version_display(&self) -> String {
+        self.versions.join(", ")
+    }
+
+    /// Options of the version at index `i`.
+    pub fn options_for(&self, i: usize) -> &[(String, String)] {
+        self.options.get(i).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Options as form text: one `key=value` per line (values may contain `,` and
+    /// `;`). Options of versions after the first follow a `[version]` line.
+    fn options_text(&self) -> String {
+        let mut lines = Vec::new();
+        for (i, opts) in self.options.iter().enumerate() {
+            if opts.is_empty() {
+                continue;
+            }
+            if i > 0 {
+                lines.push(format!("[{}]", self.versions.get(i).map_or("", String::as_str)));
+            }
+            lines.extend(opts.iter().map(|(k, v)| format!("{k}={v}")));
+        }
+        lines.join("\n")
+    }
+
+    /// Flatten into editable text fields: versions comma-separated, options as
+    /// described on `options_text`.
+    pub fn form_fields(&self) -> Vec<String> {
+        vec![self.name.clone(), self.version_display(), self.options_text()]
+    }
+
+    /// Apply form fields produced by `form_fields`. Options are only re-parsed when
+    /// their text changed; otherwise they follow their version. Returns true if
+    /// anything changed, or an error (leaving the row untouched) when an options
+    /// `[version]` header names none of the versions.
+    pub fn apply_form_fields(&mut self, fields: &[String]) -> Result<bool, String> {
+        let field = |i: usize| fields.get(i).map(|s| s.as_str()).unwrap_or("");
+        let before = self.form_fields();
+        let versions: Vec<String> = if field(1) != before[1] {
+            field(1)
+                .split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        } else {
+            self.versions.clone()
+        };
+
+        let mut parsed = None;
+        if field(2) != before[2] {
+            let mut options: Vec<Vec<(String, String)>> = vec![Vec::new(); versions.len().max(1)];
+            let mut current = 0;
+            for line in field(2).lines().map(str::trim).filter(|l| !l.is_empty()) {
+                if let Some(version) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                    let version = version.trim();
+                    current = versions
+                        .iter()
+                        .position(|v| v == version)
+                        .ok_or_else(|| format!("options header [{version}] matches no version"))?;
+                    continue;
+                }
+                let pair = match line.split_once('=') {
+                    Some((k, v)) => (k.trim().to_string(), v.trim().to_string()),
+                    None => (line.to_string(), String::new()),
+                };
+                options[current].push(pair);
+            }
+            parsed = Some(options);
+        }
+
+        self.name = field(0).trim().to_string();
+        if field(1) != before[1] {
+            // Keep options with their version, or its position when it was renamed
+            self.options = versions
+                .iter()
+                .enumerate()
+                .map(|(i, v)| match self.versions.iter().position(|old| old == v) {
+                    Some(old) => self.options_for(old).to_vec(),
+                    None => self.options_for(i).to_vec(),
+                })
+                .collect();
+            self.versions = versions;
+        }
+        if let Some(options) = parsed {
+            self.options = options;
+        }
+        while self.options.last().is_some_and(Vec::is_empty) {
+            self.options.pop();
+        }
+        Ok(before != self.form_fields())
+    }
+}
+
 /// A single editable row in the editor's Env sub-tab.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct EditorEnvRow {
+    /// Variable name, or the directive path (`_.path`, `_.python.venv`, ...).
     pub key: String,
+    /// Variable value; for list directives, one entry per line.
     pub value: String,
+    pub kind: EnvRowKind,
+    /// `_.python.venv` only: create the venv if it does not exist.
+    pub create_venv: bool,
     pub status: EditorRowStatus,
     pub original_key: Option<String>,
 }
 
+/// What an `[env]` editor row represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnvRowKind {
+    /// A plain `KEY = "value"` variable.
+    #[default]
+    Var,
+    /// `_.path`: directories prepended to PATH.
+    Path,
+    /// `_.file`: dotenv files to load.
+    File,
+    /// `_.source`: shell scripts whose exports are loaded.
+    Source,
+    /// `_.python.venv`: a Python virtualenv to activate.
+    PythonVenv,
+}
+
+impl EnvRowKind {
+    pub fn from_key(key: &str) -> Self {
+        match key.trim() {
+            "_.path" => EnvRowKind::Path,
+            "_.file" => EnvRowKind::File,
+            "_.source" => EnvRowKind::Source,
+            "_.python.venv" => EnvRowKind::PythonVenv,
+            _ => EnvRowKind::Var,
+        }
+    }
+
+    /// Form labels for the env editor popup, in the order of `EditorEnvRow::form_fields`.
+    pub fn form_labels(&self) -> &'static [&'static str] {
+        match self {
+            EnvRowKind::Var => &["Key", "Value"],
+            EnvRowKind::Path => &["Directive", "Paths"],
+            EnvRowKind::File => &["Directive", "Files"],
+            EnvRowKind::Source => &["Directive", "Scripts"],
+            EnvRowKind::PythonVenv => &["Directive", "Venv path", "Create"],
+        }
+    }
+
+    /// Whether the value is a list of entries (one per line in the form).
+    pub fn is_list(&self) -> bool {
+        matches!(self, EnvRowKind::Path | EnvRowKind::File | EnvRowKind::Source)
+    }
+}
+
+impl EditorEnvRow {
+    pub fn form_fields(&self) -> Vec<String> {
+        let mut fields = vec![self.key.clone(), self.value.clone()];
+        if self.kind == EnvRowKind::PythonVenv {
+            fields.push(self.create_venv.to_string());
+        }
+        fields
+    }
+
+    /// Apply form fields produced by `form_fields`; the row kind follows the key.
+    /// Returns true if anything changed.
+    pub fn apply_form_fields(&mut self, fields: &[String]) -> bool {
+        let field = |i: usize| fields.get(i).map(|s| s.trim()).unwrap_or("");
+        let before = (self.form_fields(), self.kind);
+        self.key = field(0).to_string();
+        self.kind = EnvRowKind::from_key(&self.key);
+        self.value = if self.kind.is_list() {
+            split_list(field(1)).join("\n")
+        } else {
+            fields.get(1).cloned().unwrap_or_default()
+        };
+        self.create_venv = matches!(field(2), "true" | "yes" | "y");
+        before != (self.form_fields(), self.kind)
+    }
+
+    /// Entries of a list directive's value.
+    pub fn list_entries(&self) -> Vec<String> {
+        split_list(&self.value)
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .lines()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
 /// A single editable row in the editor's Tasks sub-tab.
-#[derive(Debug, Clone)]
+///
+/// Only the fields below are edited; any other keys on the task table (`alias`,
+/// `outputs`, `hide`, ...) are left untouched on write.
+#[derive(Debug, Clone, Default)]
 pub struct EditorTaskRow {
     pub name: String,
+    /// The `run` script. Lines are separate commands when `run_is_array` is set.
     pub command: String,
+    /// True when `run` is an array of commands rather than a single string.
+    pub run_is_array: bool,
+    pub description: String,
+    pub depends: Vec<String>,
+    pub dir: String,
+    pub env: Vec<(String, String)>,
+    pub sources: Vec<String>,
     pub status: EditorRowStatus,
     pub original_name: Option<String>,
 }
 
+/// Form labels for the task editor popup, in the order of `EditorTaskRow::form_fields`.
+pub const TASK_FORM_LABELS: [&str; 7] =
+    ["Name", "Run", "Description", "Depends", "Dir", "Env", "Sources"];
+
+impl EditorTaskRow {
+    /// True when the task can be written as a bare `name = "run"` string.
+    pub fn is_simple(&self) -> bool {
+        !self.run_is_array
+            && self.description.is_empty()
+            && self.depends.is_empty()
+            && self.dir.is_empty()
+            && self.env.is_empty()
+            && self.sources.is_empty()
+    }
+
+    /// Flatten the task into editable text fields. Lists and env take one entry per
+    /// line (env as `KEY=value`), since globs like `*.{ts,tsx}` and env values
+    /// contain commas.
+    pub fn form_fields(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.command.clone(),
+            self.description.clone(),
+            self.depends.join("\n"),
+            self.dir.clone(),
+            self.env
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            self.sources.join("\n"),
+        ]
+    }
+
+    /// Apply form fields produced by `form_fields`. Only fields whose text changed
+    /// are re-parsed, so untouched lists keep their exact entries. Returns true if
+    /// anything changed.
+    pub fn apply_form_fields(&mut self, fields: &[String]) -> bool {
+        let field = |i: usize| fields.get(i).map(|s| s.as_str()).unwrap_or("");
+        let split_lines = |s: &str| -> Vec<String> {
+            s.lines()
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect()
+        };
+
+        let before = self.form_fields();
+        let changed = |i: usize| before.get(i).map(|s| s.as_str()).unwrap_or("") != field(i);
+        if changed(0) {
+            self.name = field(0).to_string();
+        }
+        if changed(1) {
+            self.command = field(1).to_string();
+        }
+        if changed(2) {
+            self.description = field(2).to_string();
+        }
+        if changed(3) {
+            self.depends = split_lines(field(3));
+        }
+        if changed(4) {
+            self.dir = field(4).to_string();
+        }
+        if changed(5) {
+            self.env = field(5)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| match line.split_once('=') {
+                    Some((k, v)) => (k.trim().to_string(), v.to_string()),
+                    None => (line.trim().to_string(), String::new()),
+                })
+                .collect();
+        }
+        if changed(6) {
+            self.sources = split_lines(field(6));
+        }
+        (0..TASK_FORM_LABELS.len()).any(changed)
+    }
+}
+
 /// Full state for a config file's editable content.
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct EditorState {
     /// Absolute path to the .mise.toml being edited.
@@ -400,4 +1150,8 @@ pub struct EditorState {
     pub raw_document: String,
     /// True if any row has been modified/added/deleted since open.
     pub dirty: bool,
+    /// Modification time of the file when `raw_document` was read.
+    pub disk_mtime: Option<std::time::SystemTime>,
+    /// Hash of the file contents when `raw_document` was read.
+    pub disk_hash: u64,
 }
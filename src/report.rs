@@ -0,0 +1,302 @@
+//! Project health reports exported from the Projects tab or `misetui --export`.
+//!
+//! A report covers each project's aggregate health, its per-tool rows and the
+//! health counts across all of them, rendered as Markdown, HTML or JSON.
+
+use crate::model::{MiseProject, ProjectHealthStatus, ProjectToolHealth};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [ReportFormat::Markdown, ReportFormat::Html, ReportFormat::Json];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "Markdown",
+            ReportFormat::Html => "HTML",
+            ReportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+            ReportFormat::Json => "json",
+        }
+    }
+
+    /// Parse a CLI format name (`md`, `markdown`, `html`, `json`).
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            "json" => Ok(ReportFormat::Json),
+            other => Err(format!("Unknown report format '{other}' (expected md, html or json)")),
+        }
+    }
+}
+
+/// Render a report of `projects` generated at `now` (seconds since the epoch).
+pub fn render(projects: &[&MiseProject], format: ReportFormat, now: u64) -> String {
+    match format {
+        ReportFormat::Markdown => render_markdown(projects, now),
+        ReportFormat::Html => render_html(projects, now),
+        ReportFormat::Json => render_json(projects, now),
+    }
+}
+
+/// Where the TUI saves reports: ~/.local/share/misetui/reports (the platform data dir).
+pub fn reports_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("misetui").join("reports"))
+}
+
+/// Write a report named `misetui-report-<date>.<ext>` into `dir`, creating it if
+/// needed; returns its path.
+pub fn write(projects: &[&MiseProject], format: ReportFormat, dir: &Path) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    let now = now_secs();
+    let path = dir.join(format!("misetui-report-{}.{}", utc_date(now), format.extension()));
+    std::fs::write(&path, render(projects, format, now))
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+pub fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// `YYYY-MM-DD` in UTC for a Unix timestamp.
+fn utc_date(secs: u64) -> String {
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// `(status, project count)` for each health status, most urgent first.
+fn health_counts(projects: &[&MiseProject]) -> Vec<(ProjectHealthStatus, usize)> {
+    [
+        ProjectHealthStatus::Missing,
+        ProjectHealthStatus::Outdated,
+        ProjectHealthStatus::NoConfig,
+        ProjectHealthStatus::Healthy,
+    ]
+    .into_iter()
+    .map(|status| {
+        let count = projects.iter().filter(|p| p.health == status).count();
+        (status, count)
+    })
+    .collect()
+}
+
+fn lock_name(row: &ProjectToolHealth) -> &'static str {
+    row.lock.map_or("", |l| l.name())
+}
+
+fn branch(project: &MiseProject) -> &str {
+    project.git.as_ref().map_or("", |g| g.branch.as_str())
+}
+
+fn render_markdown(projects: &[&MiseProject], now: u64) -> String {
+    // Pipes would end a table cell early
+    let cell = |s: &str| s.replace('|', "\\|");
+    let mut out = format!("# Toolchain health report\n\nGenerated {} for {} project(s).\n\n", utc_date(now), projects.len());
+
+    out.push_str("| Status | Projects |\n|---|---:|\n");
+    for (status, count) in health_counts(projects) {
+        out.push_str(&format!("| {} | {count} |\n", status.name()));
+    }
+
+    out.push_str("\n## Projects\n\n| Project | Health | Tools | Lock issues | Group | Branch | Path |\n|---|---|---:|---:|---|---|---|\n");
+    for p in projects {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | `{}` |\n",
+            cell(&p.name),
+            p.health.name(),
+            p.tool_count,
+            p.lock_issues(),
+            cell(p.group.as_deref().unwrap_or("")),
+            cell(branch(p)),
+            p.path,
+        ));
+    }
+
+    for p in projects {
+        out.push_str(&format!("\n### {}\n\n`{}` — {}", p.name, p.config_path, p.health.name()));
+        if !p.tags.is_empty() {
+            out.push_str(&format!(" — tags: {}", p.tags.join(", ")));
+        }
+        out.push_str("\n\n");
+        if p.tools.is_empty() {
+            out.push_str("No tools declared.\n");
+            continue;
+        }
+        out.push_str("| Tool | Required | Installed | Status | Locked | Lock |\n|---|---|---|---|---|---|\n");
+        for t in &p.tools {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                cell(&t.tool),
+                cell(&t.required),
+                cell(&t.installed),
+                t.status.name(),
+                cell(&t.locked),
+                lock_name(t),
+            ));
+        }
+    }
+    out
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(projects: &[&MiseProject], now: u64) -> String {
+    let e = html_escape;
+    let status_cell = |status: &ProjectHealthStatus| {
+        format!("<td class=\"{0}\">{0}</td>", status.name().replace(' ', "-"))
+    };
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Toolchain health report</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }\n\
+         table { border-collapse: collapse; margin: 0.5rem 0 1.5rem; }\n\
+         th, td { border: 1px solid #d0d7de; padding: 0.3rem 0.6rem; text-align: left; }\n\
+         th { background: #f6f8fa; }\n\
+         code { font-size: 0.9em; }\n\
+         .healthy { color: #1a7f37; } .outdated { color: #9a6700; }\n\
+         .missing { color: #cf222e; } .no-config { color: #656d76; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    out.push_str(&format!(
+        "<h1>Toolchain health report</h1>\n<p>Generated {} for {} project(s).</p>\n",
+        utc_date(now),
+        projects.len()
+    ));
+
+    out.push_str("<table>\n<tr><th>Status</th><th>Projects</th></tr>\n");
+    for (status, count) in health_counts(projects) {
+        out.push_str(&format!("<tr>{}<td>{count}</td></tr>\n", status_cell(&status)));
+    }
+    out.push_str("</table>\n");
+
+    out.push_str("<h2>Projects</h2>\n<table>\n<tr><th>Project</th><th>Health</th><th>Tools</th><th>Lock issues</th><th>Group</th><th>Branch</th><th>Path</th></tr>\n");
+    for p in projects {
+        out.push_str(&format!(
+            "<tr><td><a href=\"#{}\">{}</a></td>{}<td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><code>{}</code></td></tr>\n",
+            e(&p.path),
+            e(&p.name),
+            status_cell(&p.health),
+            p.tool_count,
+            p.lock_issues(),
+            e(p.group.as_deref().unwrap_or("")),
+            e(branch(p)),
+            e(&p.path),
+        ));
+    }
+    out.push_str("</table>\n");
+
+    for p in projects {
+        out.push_str(&format!(
+            "<h3 id=\"{}\">{}</h3>\n<p><code>{}</code>",
+            e(&p.path),
+            e(&p.name),
+            e(&p.config_path)
+        ));
+        if !p.tags.is_empty() {
+            out.push_str(&format!(" — tags: {}", e(&p.tags.join(", "))));
+        }
+        out.push_str("</p>\n");
+        if p.tools.is_empty() {
+            out.push_str("<p>No tools declared.</p>\n");
+            continue;
+        }
+        out.push_str("<table>\n<tr><th>Tool</th><th>Required</th><th>Installed</th><th>Status</th><th>Locked</th><th>Lock</th></tr>\n");
+        for t in &p.tools {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td>{}<td>{}</td><td>{}</td></tr>\n",
+                e(&t.tool),
+                e(&t.required),
+                e(&t.installed),
+                status_cell(&t.status),
+                e(&t.locked),
+                lock_name(t),
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_json(projects: &[&MiseProject], now: u64) -> String {
+    let summary: serde_json::Map<String, serde_json::Value> = health_counts(projects)
+        .into_iter()
+        .map(|(status, count)| (status.name().replace(' ', "_"), count.into()))
+        .collect();
+    let projects: Vec<serde_json::Value> = projects
+        .iter()
+        .map(|p| {
+            let tools: Vec<serde_json::Value> = p
+                .tools
+                .iter()
+                .map(|t| {
+                    serde_json::json!({
+                        "tool": t.tool,
+                        "required": t.required,
+                        "installed": t.installed,
+                        "status": t.status.name(),
+                        "locked": t.locked,
+                        "checksum": t.checksum,
+                        "lock": t.lock.map(|l| l.name()),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "name": p.name,
+                "path": p.path,
+                "config_path": p.config_path,
+                "health": p.health.name(),
+                "tool_count": p.tool_count,
+                "lockfile": p.lockfile,
+                "lock_issues": p.lock_issues(),
+                "group": p.group,
+                "favorite": p.favorite,
+                "tags": p.tags,
+                "git": p.git.as_ref().map(|g| serde_json::json!({
+                    "branch": g.branch,
+                    "config_dirty": g.config_dirty,
+                    "commit_age_secs": g.commit_age_secs,
+                })),
+                "config_age_secs": p.config_age_secs,
+                "tools": tools,
+            })
+        })
+        .collect();
+    let report = serde_json::json!({
+        "generated_at": now,
+        "date": utc_date(now),
+        "summary": summary,
+        "projects": projects,
+    });
+    serde_json::to_string_pretty(&report).unwrap_or_default() + "\n"
+}
@@ -1,8 +1,14 @@
+use super::highlight::highlight_toml_line;
+use super::projects::{lock_style, short_checksum};
 use crate::app::{App, LoadState};
+use crate::model::{ConfigFile, LockState, TrustState};
 use crate::theme;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table,
+};
 use ratatui::Frame;
 
 pub fn render(f: &mut Frame, area: Rect, app: &App) {
@@ -75,19 +81,37 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(content_area);
+
     let items: Vec<ListItem> = configs
         .iter()
         .map(|cfg| {
+            let trust_style = match cfg.trust {
+                TrustState::Trusted => Style::default().fg(theme::GREEN),
+                TrustState::Untrusted => Style::default().fg(theme::YELLOW),
+                TrustState::Ignored => theme::muted(),
+            };
             let tools_str = if cfg.tools.is_empty() {
                 String::new()
             } else {
                 format!("  [{}]", cfg.tools.join(", "))
             };
-            ListItem::new(Line::from(vec![
-                Span::styled("  ", theme::active_indicator()),
-                Span::styled(&cfg.path, theme::table_row()),
-                Span::styled(tools_str, theme::muted()),
-            ]))
+            ListItem::new(vec![
+                Line::from(vec![
+                    Span::styled(format!(" {:>2} ", cfg.rank), theme::muted()),
+                    Span::styled(&cfg.path, theme::table_row()),
+                ]),
+                Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(format!("{:<12}", cfg.trust.label()), trust_style),
+                    Span::styled(format!("{:<8}", cfg.kind.label()), theme::key_desc()),
+                    lock_summary(cfg),
+                    Span::styled(tools_str, theme::muted()),
+                ]),
+            ])
         })
         .collect();
 
@@ -97,5 +121,129 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
 
     let mut state = ListState::default();
     state.select(Some(app.config_selected));
-    f.render_stateful_widget(list, content_area, &mut state);
+    f.render_stateful_widget(list, panes[0], &mut state);
+
+    render_preview(f, panes[1], configs.get(app.config_selected).copied(), app.config_preview_scroll);
+}
+
+/// `lock ✓` when every requirement is locked and installed, `lock N!` otherwise.
+fn lock_summary(cfg: &ConfigFile) -> Span<'static> {
+    if cfg.lockfile.is_none() {
+        return Span::raw("");
+    }
+    let issues = cfg
+        .lock_rows
+        .iter()
+        .filter(|r| r.lock.is_some_and(|l| l.is_issue()))
+        .count();
+    if issues == 0 {
+        Span::styled("lock ✓ ", lock_style(LockState::Locked))
+    } else {
+        Span::styled(format!("lock {issues}! "), lock_style(LockState::Drift))
+    }
+}
+
+fn render_preview(f: &mut Frame, area: Rect, cfg: Option<&ConfigFile>, scroll: usize) {
+    // Configs with a lockfile get a requested/locked/installed table under the file
+    let area = match cfg {
+        Some(c) if c.lockfile.is_some() => {
+            let rows = c.lock_rows.len().max(1) as u16;
+            let panes = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(5), Constraint::Length(rows + 3)])
+                .split(area);
+            render_lock(f, panes[1], c);
+            panes[0]
+        }
+        _ => area,
+    };
+
+    let title = cfg
+        .and_then(|c| std::path::Path::new(&c.path).file_name())
+        .map(|n| format!(" {} ", n.to_string_lossy()))
+        .unwrap_or_else(|| " Preview ".to_string());
+    let block = Block::default()
+        .title(Span::styled(title, theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border())
+        .style(Style::default().bg(theme::BG));
+
+    let lines: Vec<Line> = match cfg {
+        Some(c) if !c.contents.is_empty() => {
+            c.contents.lines().map(highlight_toml_line).collect()
+        }
+        Some(_) => vec![Line::from(Span::styled(
+            "  (empty or unreadable)",
+            theme::muted(),
+        ))],
+        None => Vec::new(),
+    };
+
+    let preview = Paragraph::new(lines).block(block).scroll((scroll as u16, 0));
+    f.render_widget(preview, area);
+}
+
+fn render_lock(f: &mut Frame, area: Rect, cfg: &ConfigFile) {
+    let name = cfg
+        .lockfile
+        .as_deref()
+        .and_then(|l| std::path::Path::new(l).file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(Span::styled(format!(" {name} "), theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border())
+        .style(Style::default().bg(theme::BG));
+
+    if cfg.lock_rows.is_empty() {
+        let empty = Paragraph::new("  No [tools] requirements")
+            .style(theme::muted())
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Tool"),
+        Cell::from("Requested"),
+        Cell::from("Locked"),
+        Cell::from("Installed"),
+        Cell::from("Checksum"),
+        Cell::from("Lock"),
+    ])
+    .style(theme::table_header());
+
+    let rows: Vec<Row> = cfg
+        .lock_rows
+        .iter()
+        .map(|row| {
+            let lock = row.lock.unwrap_or(LockState::Unlocked);
+            let installed = if row.installed.is_empty() {
+                "—".to_string()
+            } else {
+                row.installed.clone()
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(row.tool.clone(), theme::table_row())),
+                Cell::from(Span::styled(row.required.clone(), theme::muted())),
+                Cell::from(Span::styled(row.locked.clone(), theme::table_row())),
+                Cell::from(Span::styled(installed, theme::table_row())),
+                Cell::from(Span::styled(short_checksum(&row.checksum), theme::muted())),
+                Cell::from(Span::styled(lock.label(), lock_style(lock))),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Min(10),
+        Constraint::Length(11),
+    ];
+    f.render_widget(Table::new(rows, widths).header(header).block(block), area);
 }
@@ -22,10 +22,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         Tab::Bootstrap => match app.wizard.step {
             WizardStep::Idle => {
                 hints.push(("Enter", "start"));
+                hints.push(("t", "template"));
             }
             WizardStep::Review => {
                 hints.push(("Space", "toggle"));
                 hints.push(("a", "agent files"));
+                if app.wizard.packages.len() > 1 {
+                    hints.push(("m", "monorepo"));
+                }
                 hints.push(("Enter", "preview"));
                 hints.push(("Esc", "cancel"));
             }
@@ -44,9 +48,11 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 hints.push(("a", "add"));
                 hints.push(("d", "delete"));
                 hints.push(("w", "write"));
+                hints.push(("z/Z", "undo/redo"));
             }
             hints.push(("v", "detail"));
             hints.push(("u", "update"));
+            hints.push(("x", "uninstall"));
         }
         Tab::Registry => {
             hints.push(("i", "install"));
@@ -62,10 +68,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 hints.push(("a", "add"));
                 hints.push(("d", "delete"));
                 hints.push(("w", "write"));
+                hints.push(("z/Z", "undo/redo"));
             }
         }
         Tab::Config => {
             hints.push(("t", "trust"));
+            hints.push(("T", "trust manager"));
+            hints.push(("L", "relock"));
+            hints.push(("PgUp/PgDn", "scroll preview"));
         }
         Tab::Environment => {
             if app.editor_states_loaded {
@@ -73,12 +83,19 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 hints.push(("a", "add"));
                 hints.push(("d", "delete"));
                 hints.push(("w", "write"));
+                hints.push(("z/Z", "undo/redo"));
             }
         }
         Tab::Settings | Tab::Doctor => {}
         Tab::Projects => {
             hints.push(("i", "install tools"));
             hints.push(("u", "upgrade pins"));
+            hints.push(("I/U", "fix all"));
+            hints.push(("L", "relock"));
+            hints.push(("C", "cd"));
+            hints.push(("f/#", "pin/tag"));
+            hints.push(("g", "group"));
+            hints.push(("E", "export"));
             hints.push(("Enter", "drill-down"));
         }
     }
@@ -0,0 +1,27 @@
+/// Format a byte count as a short human-readable size ("512 B", "1.4 MB").
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Format an age in seconds as a coarse relative label ("today", "3d ago", "2w ago").
+pub fn format_age(secs: u64) -> String {
+    const DAY: u64 = 86_400;
+    match secs / DAY {
+        0 => "today".to_string(),
+        d if d < 7 => format!("{d}d ago"),
+        d if d < 60 => format!("{}w ago", d / 7),
+        d if d < 730 => format!("{}mo ago", d / 30),
+        d => format!("{}y ago", d / 365),
+    }
+}
@@ -1,5 +1,5 @@
 use crate::app::App;
-use crate::model::DriftState;
+use crate::model::{DriftIssue, DriftReport, DriftState};
 use crate::theme;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
@@ -8,10 +8,21 @@ use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 use ratatui::Frame;
 
 pub fn render(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border_focused())
+        .style(ratatui::style::Style::default().bg(theme::BG));
+
+    let header = Paragraph::new(Line::from(title_spans(app))).block(block);
+    f.render_widget(header, area);
+}
+
+fn title_spans(app: &App) -> Vec<Span<'static>> {
     let tool_count = app.tools.len();
     let outdated = app.outdated_count();
 
-    let title_spans = vec![
+    vec![
         Span::styled(" misetui ", theme::title()),
         Span::raw("  "),
         Span::styled(format!("Tools: {tool_count}"), theme::header_stat()),
@@ -25,26 +36,47 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             },
         ),
         Span::raw("  "),
-        Span::styled(drift_label(app.drift_state), drift_style(app.drift_state)),
-    ];
+        Span::styled(format!("Dir: {}", short_dir(&app.work_dir)), theme::header_stat()),
+        Span::raw("  "),
+        Span::styled(drift_label(&app.drift), drift_style(app.drift.state)),
+    ]
+}
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(theme::border_focused())
-        .style(ratatui::style::Style::default().bg(theme::BG));
+/// The working directory with the home prefix shown as `~`.
+fn short_dir(dir: &std::path::Path) -> String {
+    match dirs::home_dir().and_then(|home| dir.strip_prefix(home).ok().map(|p| p.to_path_buf())) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.display()),
+        None => dir.display().to_string(),
+    }
+}
 
-    let header = Paragraph::new(Line::from(title_spans)).block(block);
-    f.render_widget(header, area);
+/// Screen columns covered by the CWD drift indicator (the last header span), offset
+/// by the header's left border; used to open the breakdown on click.
+pub fn drift_indicator_columns(app: &App) -> std::ops::Range<u16> {
+    let spans = title_spans(app);
+    let start = 1 + spans[..spans.len() - 1].iter().map(|s| s.width()).sum::<usize>();
+    let end = start + spans.last().map(|s| s.width()).unwrap_or(0);
+    start as u16..end as u16
 }
 
-fn drift_label(state: DriftState) -> &'static str {
-    match state {
-        DriftState::Checking   => " CWD: checking...",
-        DriftState::Healthy    => " CWD: healthy",
-        DriftState::Missing    => "! CWD: missing",
-        DriftState::NoConfig   => " CWD: no config",
-        DriftState::Untrusted  => "! CWD: untrusted",
+fn drift_label(report: &DriftReport) -> String {
+    let count = |issues: &[DriftIssue]| {
+        report.tools.iter().filter(|t| issues.contains(&t.issue)).count()
+    };
+    match report.state {
+        DriftState::Checking   => " CWD: checking...".to_string(),
+        DriftState::Healthy    => " CWD: healthy".to_string(),
+        DriftState::Outdated   => format!(
+            " CWD: {} outdated",
+            count(&[DriftIssue::Outdated])
+        ),
+        DriftState::Missing    => format!(
+            "! CWD: {} missing",
+            count(&[DriftIssue::Missing, DriftIssue::WrongVersion])
+        ),
+        DriftState::NoConfig   => " CWD: no config".to_string(),
+        DriftState::Untrusted  => "! CWD: untrusted".to_string(),
     }
 }
 
@@ -52,6 +84,7 @@ fn drift_style(state: DriftState) -> Style {
     match state {
         DriftState::Checking   => theme::muted(),
         DriftState::Healthy    => Style::default().fg(theme::GREEN),
+        DriftState::Outdated   => Style::default().fg(theme::YELLOW),
         DriftState::Missing    => theme::error(),
         DriftState::NoConfig   => theme::muted(),
         DriftState::Untrusted  => Style::default().fg(theme::YELLOW),
@@ -39,3 +39,113 @@ pub fn highlight_cached(text: &str, indices: &[usize], normal: Style) -> Line<'s
 
     Line::from(spans)
 }
+
+/// Syntax-highlight a single line of TOML for read-only previews: comments,
+/// `[table]` headers, keys, strings, numbers and booleans. This is a line-local
+/// tokenizer, so multi-line strings are only coloured on their opening line.
+pub fn highlight_toml_line(line: &str) -> Line<'static> {
+    let trimmed = line.trim_start();
+    let indent = line[..line.len() - trimmed.len()].to_string();
+
+    if trimmed.starts_with('#') {
+        return Line::from(Span::styled(line.to_owned(), theme::muted()));
+    }
+    if trimmed.starts_with('[') {
+        let (header, comment) = split_comment(trimmed);
+        return Line::from(vec![
+            Span::raw(indent),
+            Span::styled(header.to_owned(), theme::title()),
+            Span::styled(comment.to_owned(), theme::muted()),
+        ]);
+    }
+
+    let Some(eq) = find_unquoted(trimmed, '=') else {
+        return Line::from(Span::styled(line.to_owned(), theme::table_row()));
+    };
+    let (key, rest) = trimmed.split_at(eq);
+    let (value, comment) = split_comment(&rest[1..]);
+
+    let mut spans = vec![
+        Span::raw(indent),
+        Span::styled(key.to_owned(), Style::default().fg(theme::YELLOW)),
+        Span::styled("=".to_string(), theme::muted()),
+    ];
+    spans.extend(highlight_toml_value(value));
+    spans.push(Span::styled(comment.to_owned(), theme::muted()));
+    Line::from(spans)
+}
+
+fn highlight_toml_value(value: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut buf = String::new();
+    let mut chars = value.chars().peekable();
+
+    let flush = |buf: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !buf.is_empty() {
+            let style = scalar_style(buf.trim());
+            spans.push(Span::styled(std::mem::take(buf), style));
+        }
+    };
+
+    while let Some(ch) = chars.next() {
+        if ch == '"' || ch == '\'' {
+            flush(&mut buf, &mut spans);
+            let mut s = String::from(ch);
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                s.push(c);
+                if c == ch && !escaped {
+                    break;
+                }
+                escaped = ch == '"' && c == '\\' && !escaped;
+            }
+            spans.push(Span::styled(s, Style::default().fg(theme::GREEN)));
+        } else if matches!(ch, '[' | ']' | '{' | '}' | ',' | '=') {
+            flush(&mut buf, &mut spans);
+            spans.push(Span::styled(ch.to_string(), theme::muted()));
+        } else {
+            buf.push(ch);
+        }
+    }
+    flush(&mut buf, &mut spans);
+    spans
+}
+
+fn scalar_style(token: &str) -> Style {
+    let is_number = !token.is_empty()
+        && token
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | '_' | 'e' | 'E'));
+    if token == "true" || token == "false" || is_number {
+        Style::default().fg(theme::RED)
+    } else {
+        theme::table_row()
+    }
+}
+
+/// Split `s` at the first `#` that is not inside a string.
+fn split_comment(s: &str) -> (&str, &str) {
+    match find_unquoted(s, '#') {
+        Some(i) => s.split_at(i),
+        None => (s, ""),
+    }
+}
+
+fn find_unquoted(s: &str, needle: char) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, ch) in s.char_indices() {
+        match quote {
+            Some(q) => {
+                if ch == q && !escaped {
+                    quote = None;
+                }
+                escaped = q == '"' && ch == '\\' && !escaped;
+            }
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == needle => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
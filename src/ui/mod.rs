@@ -3,7 +3,8 @@ pub(super) mod highlight;
 mod doctor;
 mod environment;
 mod footer;
-mod header;
+pub(super) mod format;
+pub(crate) mod header;
 pub mod layout;
 mod outdated;
 mod popup;
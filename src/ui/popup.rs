@@ -1,9 +1,19 @@
+use super::format::{format_age, format_bytes};
 use crate::app::{App, Popup, Tab};
+use crate::config::BootstrapTemplate;
+use crate::report::ReportFormat;
+use crate::model::{
+    DriftIssue, DriftReport, DriftState, EnvRowKind, FleetOp, FleetPlan, FleetResult, PruneCandidate, ToolUsage, TrustEntry, TrustState,
+    TASK_FORM_LABELS, TOOL_FORM_LABELS,
+};
 use crate::theme;
+use crate::validate::{self, EditorProblem};
 use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
-    Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap,
+    Block, BorderType, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table,
+    TableState, Wrap,
 };
 use ratatui::Frame;
 
@@ -34,17 +44,64 @@ pub fn render(f: &mut Frame, app: &App) {
             action_on_confirm: _,
         } => render_confirm(f, message),
         Popup::Progress { message } => render_progress(f, message, app.spinner_char()),
+        Popup::UninstallGuard { tool, version, impacted, typed } => {
+            render_uninstall_guard(f, tool, version, impacted, typed)
+        }
         Popup::ToolDetail {
             tool_name,
             info,
+            used_by,
             scroll,
-        } => render_tool_detail(f, tool_name, info, *scroll),
+        } => render_tool_detail(f, tool_name, info, used_by, *scroll),
         Popup::Help => render_help(f),
+        Popup::DriftBreakdown => render_drift_breakdown(f, &app.drift),
+        Popup::FleetPlan { plan, scroll } => render_fleet_plan(f, plan, *scroll),
+        Popup::FleetResults { op, results, scroll } => {
+            render_fleet_results(f, *op, results, *scroll)
+        }
+        Popup::ChangeDir { input, error } => render_change_dir(f, input, error.as_deref()),
+        Popup::ProjectTags { path, input } => render_project_tags(f, path, input),
+        Popup::ExportReport { selected } => {
+            let dir = crate::report::reports_dir();
+            render_export_report(f, app.filtered_projects.len(), dir, *selected)
+        }
+        Popup::Prune { candidates, selected, keep_latest } => {
+            render_prune(f, candidates, *selected, *keep_latest)
+        }
+        Popup::TrustManager { entries, selected } => render_trust_manager(f, entries, *selected),
+        Popup::TemplatePicker { selected } => {
+            render_template_picker(f, &app.wizard.templates, *selected)
+        }
+        Popup::EditorDiff { diff, scroll } => render_editor_diff(f, diff, *scroll),
+        Popup::EditorConflict { paths } => render_editor_conflict(f, paths),
         Popup::ScanConfig { dirs, selected, adding, new_dir, max_depth } => {
             render_scan_config(f, dirs, *selected, *adding, new_dir, *max_depth)
         }
-        Popup::Editor { tab, field0, field1, active_field, .. } => {
-            render_editor(f, *tab, field0, field1, *active_field)
+        Popup::Editor { config_idx, row_idx, tab, fields, active_field, .. } => {
+            let problems = app
+                .editor_states
+                .get(*config_idx)
+                .map(|state| {
+                    validate::validate_fields(
+                        *tab,
+                        fields,
+                        state,
+                        *row_idx,
+                        &app.registry,
+                        &app.editor_remote_missing,
+                    )
+                })
+                .unwrap_or_default();
+            let labels: &[&str] = match tab {
+                Tab::Tools => &TOOL_FORM_LABELS,
+                Tab::Environment => {
+                    EnvRowKind::from_key(fields.first().map(|s| s.as_str()).unwrap_or(""))
+                        .form_labels()
+                }
+                Tab::Tasks => &TASK_FORM_LABELS,
+                _ => &["Field 1", "Field 2"],
+            };
+            render_editor(f, *tab, labels, fields, *active_field, &problems)
         }
     }
 }
@@ -167,6 +224,307 @@ fn render_confirm(f: &mut Frame, message: &str) {
     f.render_widget(paragraph, area);
 }
 
+fn render_uninstall_guard(
+    f: &mut Frame,
+    tool: &str,
+    version: &str,
+    impacted: &[ToolUsage],
+    typed: &str,
+) {
+    let height = (impacted.len() as u16 + 9).clamp(10, 24);
+    let area = centered_rect(76, height, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(format!(" Uninstall {tool}@{version} "), theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let mut lines = vec![
+        Line::default(),
+        Line::from(Span::styled(
+            format!("  {} config(s) would be left without a matching {tool}:", impacted.len()),
+            theme::error(),
+        )),
+    ];
+    for usage in impacted {
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<20} ", usage.project), theme::table_row()),
+            Span::styled(format!("{:<10} ", usage.required), theme::search_input()),
+            Span::styled(usage.config_path.clone(), theme::muted()),
+        ]));
+    }
+    let matches = typed.trim() == tool;
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        Span::styled(format!("  Type '{tool}' to confirm: "), theme::key_desc()),
+        Span::styled(typed.to_string(), if matches { theme::active_indicator() } else { theme::search_input() }),
+        Span::styled("█", theme::search_input()),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Enter", theme::key_hint()),
+        Span::styled(" uninstall  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" cancel", theme::key_desc()),
+    ]));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    f.render_widget(paragraph, area);
+}
+
+fn render_fleet_plan(f: &mut Frame, plan: &FleetPlan, scroll: usize) {
+    let body = plan.installs.len() + plan.targets.len() + 4;
+    let height = (body as u16 + 4).clamp(10, 32);
+    let area = centered_rect(90, height, f.area());
+    f.render_widget(Clear, area);
+
+    let title = format!(
+        " {} — {} project(s), {} up to date ",
+        plan.op.label(),
+        plan.targets.len(),
+        plan.skipped
+    );
+    let block = Block::default()
+        .title(Span::styled(title, theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!("  Installs ({}, each once)", plan.installs.len()),
+        theme::key_desc(),
+    ))];
+    if plan.installs.is_empty() {
+        lines.push(Line::from(Span::styled("    nothing to install", theme::muted())));
+    }
+    for install in &plan.installs {
+        let shared = if install.projects.len() > 1 {
+            format!("shared by {}: {}", install.projects.len(), install.projects.join(", "))
+        } else {
+            install.projects.join(", ")
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("    {:<28} ", format!("{}@{}", install.tool, install.version)),
+                theme::table_row(),
+            ),
+            Span::styled(shared, theme::muted()),
+        ]));
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("  Projects", theme::key_desc())));
+    for target in &plan.targets {
+        let detail = match &target.error {
+            Some(e) => Span::styled(format!("skipped: {e}"), theme::error()),
+            None => Span::styled(target.specs.join(", "), theme::muted()),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<24} ", target.name), theme::table_row()),
+            detail,
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), chunks[0]);
+
+    let hint = Line::from(vec![
+        Span::styled("  Enter", theme::key_hint()),
+        Span::styled(" run  ", theme::key_desc()),
+        Span::styled("j/k", theme::key_hint()),
+        Span::styled(" scroll  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" cancel", theme::key_desc()),
+    ]);
+    f.render_widget(Paragraph::new(hint), chunks[1]);
+}
+
+fn render_fleet_results(f: &mut Frame, op: FleetOp, results: &[FleetResult], scroll: usize) {
+    let height = (results.len() as u16 + 4).clamp(8, 32);
+    let area = centered_rect(90, height, f.area());
+    f.render_widget(Clear, area);
+
+    let failed = results.iter().filter(|r| !r.ok).count();
+    let title = format!(
+        " {} — {} ok, {failed} failed ",
+        op.label(),
+        results.len() - failed
+    );
+    let block = Block::default()
+        .title(Span::styled(title, theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let lines: Vec<Line> = results
+        .iter()
+        .map(|r| {
+            let (mark, style) = if r.ok {
+                ("✓", Style::default().fg(theme::GREEN))
+            } else {
+                ("✗", theme::error())
+            };
+            Line::from(vec![
+                Span::styled(format!("  {mark} "), style),
+                Span::styled(format!("{:<24} ", r.name), theme::table_row()),
+                Span::styled(r.detail.clone(), if r.ok { theme::muted() } else { theme::error() }),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), chunks[0]);
+
+    let hint = Line::from(vec![
+        Span::styled("  j/k", theme::key_hint()),
+        Span::styled(" scroll  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" close", theme::key_desc()),
+    ]);
+    f.render_widget(Paragraph::new(hint), chunks[1]);
+}
+
+fn render_change_dir(f: &mut Frame, input: &str, error: Option<&str>) {
+    let area = centered_rect(76, 7, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" Change directory ", theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let status = match error {
+        Some(e) => Line::from(Span::styled(format!("  {e}"), theme::error())),
+        None => Line::from(Span::styled(
+            "  Tools, tasks, env, config and drift will reflect this directory",
+            theme::muted(),
+        )),
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("  Path: ", theme::key_desc()),
+            Span::styled(input.to_string(), theme::search_input()),
+            Span::styled("█", theme::search_input()),
+        ]),
+        status,
+        Line::default(),
+        Line::from(vec![
+            Span::styled("  Enter", theme::key_hint()),
+            Span::styled(" switch  ", theme::key_desc()),
+            Span::styled("Esc", theme::key_hint()),
+            Span::styled(" cancel", theme::key_desc()),
+        ]),
+    ];
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_export_report(f: &mut Frame, count: usize, dir: Option<std::path::PathBuf>, selected: usize) {
+    let area = centered_rect(64, 10, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Export {count} project(s) "),
+            theme::title(),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = ReportFormat::ALL
+        .iter()
+        .map(|format| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {:<10}", format.label()), theme::table_row()),
+                Span::styled(format!("misetui-report-<date>.{}", format.extension()), theme::muted()),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(theme::table_selected());
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(list, chunks[0], &mut state);
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            match dir {
+                Some(dir) => format!("  Into {}", dir.display()),
+                None => "  No data directory to save into".to_string(),
+            },
+            theme::muted(),
+        )),
+        chunks[1],
+    );
+    let hint = Line::from(vec![
+        Span::styled("  Enter", theme::key_hint()),
+        Span::styled(" export  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" cancel", theme::key_desc()),
+    ]);
+    f.render_widget(Paragraph::new(hint), chunks[2]);
+}
+
+fn render_project_tags(f: &mut Frame, path: &str, input: &str) {
+    let area = centered_rect(76, 7, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" Project tags ", theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("  Tags: ", theme::key_desc()),
+            Span::styled(input.to_string(), theme::search_input()),
+            Span::styled("█", theme::search_input()),
+        ]),
+        Line::from(Span::styled(
+            format!("  Comma-separated, for {path}; search them with /#tag"),
+            theme::muted(),
+        )),
+        Line::default(),
+        Line::from(vec![
+            Span::styled("  Enter", theme::key_hint()),
+            Span::styled(" save  ", theme::key_desc()),
+            Span::styled("Esc", theme::key_hint()),
+            Span::styled(" cancel", theme::key_desc()),
+        ]),
+    ];
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn render_progress(f: &mut Frame, message: &str, spinner: char) {
     let area = centered_rect(44, 5, f.area());
     f.render_widget(Clear, area);
@@ -189,8 +547,14 @@ fn render_progress(f: &mut Frame, message: &str, spinner: char) {
     f.render_widget(paragraph, area);
 }
 
-fn render_tool_detail(f: &mut Frame, tool_name: &str, info: &str, scroll: usize) {
-    let area = centered_rect(60, 22, f.area());
+fn render_tool_detail(
+    f: &mut Frame,
+    tool_name: &str,
+    info: &str,
+    used_by: &[(String, Vec<ToolUsage>)],
+    scroll: usize,
+) {
+    let area = centered_rect(72, 26, f.area());
     f.render_widget(Clear, area);
 
     let block = Block::default()
@@ -203,9 +567,31 @@ fn render_tool_detail(f: &mut Frame, tool_name: &str, info: &str, scroll: usize)
         .border_style(theme::popup_border())
         .style(theme::popup_bg());
 
-    let lines: Vec<Line> = info
-        .lines()
-        .map(|l| {
+    let mut lines: Vec<Line> = vec![Line::from(Span::styled("  Used by", theme::title()))];
+    if used_by.is_empty() {
+        lines.push(Line::from(Span::styled("    (no installed versions)", theme::muted())));
+    }
+    for (version, usages) in used_by {
+        if usages.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {version:<14}"), theme::table_row()),
+                Span::styled("unused by scanned projects and active configs", theme::muted()),
+            ]));
+            continue;
+        }
+        for (i, usage) in usages.iter().enumerate() {
+            let version_col = if i == 0 { version.as_str() } else { "" };
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {version_col:<14}"), theme::table_row()),
+                Span::styled(format!("{:<20} ", usage.project), theme::active_indicator()),
+                Span::styled(format!("{} ", usage.required), theme::search_input()),
+                Span::styled(usage.config_path.clone(), theme::muted()),
+            ]));
+        }
+    }
+    lines.push(Line::default());
+
+    lines.extend(info.lines().map(|l| {
             // Highlight JSON keys
             if let Some(colon_pos) = l.find(':') {
                 let key = &l[..colon_pos];
@@ -217,8 +603,7 @@ fn render_tool_detail(f: &mut Frame, tool_name: &str, info: &str, scroll: usize)
             } else {
                 Line::from(Span::styled(format!("  {l}"), theme::table_row()))
             }
-        })
-        .collect();
+        }));
 
     let paragraph = Paragraph::new(lines)
         .block(block)
@@ -227,8 +612,476 @@ fn render_tool_detail(f: &mut Frame, tool_name: &str, info: &str, scroll: usize)
     f.render_widget(paragraph, area);
 }
 
+fn render_editor_conflict(f: &mut Frame, paths: &[String]) {
+    let area = centered_rect(72, paths.len() as u16 + 9, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" Changed on disk ", theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  These files were modified outside misetui after they were loaded:",
+            theme::table_row(),
+        )),
+    ];
+    lines.extend(
+        paths
+            .iter()
+            .map(|p| Line::from(Span::styled(format!("    {p}"), theme::search_input()))),
+    );
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Merge keeps their edits and re-applies yours on top.",
+        theme::muted(),
+    )));
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  m", theme::key_hint()),
+        Span::styled(" merge & review  ", theme::key_desc()),
+        Span::styled("r", theme::key_hint()),
+        Span::styled(" reload (discard mine)  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" cancel", theme::key_desc()),
+    ]));
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_editor_diff(f: &mut Frame, diff: &str, scroll: usize) {
+    let height = (diff.lines().count() as u16 + 4).clamp(10, 32);
+    let area = centered_rect(96, height, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" Review changes ", theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let lines: Vec<Line> = diff
+        .lines()
+        .map(|l| {
+            let style = if l.starts_with("+++") || l.starts_with("---") {
+                theme::title()
+            } else if l.starts_with("@@") {
+                theme::muted()
+            } else if l.starts_with('+') {
+                Style::default().fg(theme::GREEN)
+            } else if l.starts_with('-') {
+                Style::default().fg(theme::RED)
+            } else {
+                theme::table_row()
+            };
+            Line::from(Span::styled(format!(" {l}"), style))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), chunks[0]);
+
+    let hint = Line::from(vec![
+        Span::styled("  Enter", theme::key_hint()),
+        Span::styled(" write  ", theme::key_desc()),
+        Span::styled("j/k", theme::key_hint()),
+        Span::styled(" scroll  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" back", theme::key_desc()),
+    ]);
+    f.render_widget(Paragraph::new(hint), chunks[1]);
+}
+
+fn render_prune(f: &mut Frame, candidates: &[PruneCandidate], selected: usize, keep_latest: usize) {
+    let height = (candidates.len() as u16 + 8).clamp(12, 30);
+    let area = centered_rect(100, height, f.area());
+    f.render_widget(Clear, area);
+
+    let checked: Vec<&PruneCandidate> = candidates.iter().filter(|c| c.selected).collect();
+    let reclaim: u64 = checked.iter().filter_map(|c| c.size_bytes).sum();
+    let title = format!(
+        " Prune — {}/{} selected, {} ",
+        checked.len(),
+        candidates.len(),
+        format_bytes(reclaim)
+    );
+
+    let block = Block::default()
+        .title(Span::styled(title, theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // policy
+            Constraint::Min(1),    // table
+            Constraint::Length(1), // hints
+        ])
+        .split(inner);
+
+    let policy = if keep_latest == 0 {
+        "off".to_string()
+    } else {
+        format!("keep latest {keep_latest} per tool")
+    };
+    let policy_line = Line::from(vec![
+        Span::styled("  policy: ", theme::key_desc()),
+        Span::styled(policy, theme::title()),
+        Span::styled("   (-/+ to change)", theme::muted()),
+    ]);
+    f.render_widget(Paragraph::new(policy_line), chunks[0]);
+
+    let header = Row::new(vec![
+        Cell::from("   Version"),
+        Cell::from("Size"),
+        Cell::from("Last used"),
+        Cell::from("Reason"),
+    ])
+    .style(theme::table_header());
+
+    let rows: Vec<Row> = candidates
+        .iter()
+        .map(|c| {
+            let (check, check_style) = if c.selected {
+                ("[x]", theme::error())
+            } else {
+                ("[ ]", theme::muted())
+            };
+            let size = c.size_bytes.map(format_bytes).unwrap_or_else(|| "?".to_string());
+            let last_used = c.last_used_secs.map(format_age).unwrap_or_else(|| "?".to_string());
+            let reason = if !c.required_by.is_empty() {
+                Span::styled(
+                    format!("kept: required by {}", c.required_by.join(", ")),
+                    Style::default().fg(theme::YELLOW),
+                )
+            } else if c.rank < keep_latest {
+                Span::styled(
+                    format!("kept: within latest {keep_latest}"),
+                    Style::default().fg(theme::YELLOW),
+                )
+            } else {
+                Span::styled(c.reason.clone(), theme::muted())
+            };
+            Row::new(vec![
+                Cell::from(Line::from(vec![
+                    Span::styled(format!("{check} "), check_style),
+                    Span::styled(c.spec(), theme::table_row()),
+                ])),
+                Cell::from(Span::styled(size, theme::table_row())),
+                Cell::from(Span::styled(last_used, theme::muted())),
+                Cell::from(reason),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(30),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Min(20),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(theme::table_selected());
+    let mut state = TableState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(table, chunks[1], &mut state);
+
+    let hint = Line::from(vec![
+        Span::styled("  Space", theme::key_hint()),
+        Span::styled(" toggle  ", theme::key_desc()),
+        Span::styled("a", theme::key_hint()),
+        Span::styled(" all  ", theme::key_desc()),
+        Span::styled("-/+", theme::key_hint()),
+        Span::styled(" keep latest  ", theme::key_desc()),
+        Span::styled("Enter", theme::key_hint()),
+        Span::styled(" uninstall selected  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" cancel", theme::key_desc()),
+    ]);
+    f.render_widget(Paragraph::new(hint), chunks[2]);
+}
+
+fn render_trust_manager(f: &mut Frame, entries: &[TrustEntry], selected: usize) {
+    let height = (entries.len() as u16 + 6).clamp(10, 30);
+    let area = centered_rect(100, height, f.area());
+    f.render_widget(Clear, area);
+
+    let count = |state: TrustState| entries.iter().filter(|e| e.state == state).count();
+    let stale = entries.iter().filter(|e| !e.exists).count();
+    let title = format!(
+        " Trust — {} trusted, {} untrusted, {} ignored, {} missing ",
+        count(TrustState::Trusted),
+        count(TrustState::Untrusted),
+        count(TrustState::Ignored),
+        stale
+    );
+
+    let block = Block::default()
+        .title(Span::styled(title, theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let header = Row::new(vec![Cell::from("   State"), Cell::from("Path"), Cell::from("")])
+        .style(theme::table_header());
+
+    let rows: Vec<Row> = entries
+        .iter()
+        .map(|e| {
+            let (check, check_style) = if e.selected {
+                ("[x]", theme::error())
+            } else {
+                ("[ ]", theme::muted())
+            };
+            let state_style = match e.state {
+                TrustState::Trusted => Style::default().fg(theme::GREEN),
+                TrustState::Untrusted => Style::default().fg(theme::YELLOW),
+                TrustState::Ignored => theme::muted(),
+            };
+            let note = if e.exists {
+                Span::raw("")
+            } else {
+                Span::styled("✗ file missing", theme::error())
+            };
+            Row::new(vec![
+                Cell::from(Line::from(vec![
+                    Span::styled(format!("{check} "), check_style),
+                    Span::styled(e.state.label(), state_style),
+                ])),
+                Cell::from(Span::styled(e.path.clone(), theme::table_row())),
+                Cell::from(note),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(16),
+        Constraint::Min(30),
+        Constraint::Length(15),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(theme::table_selected());
+    let mut state = TableState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(table, chunks[0], &mut state);
+
+    let hint = Line::from(vec![
+        Span::styled("  Space", theme::key_hint()),
+        Span::styled(" toggle  ", theme::key_desc()),
+        Span::styled("a", theme::key_hint()),
+        Span::styled(" all  ", theme::key_desc()),
+        Span::styled("t", theme::key_hint()),
+        Span::styled(" trust  ", theme::key_desc()),
+        Span::styled("u", theme::key_hint()),
+        Span::styled(" untrust  ", theme::key_desc()),
+        Span::styled("i", theme::key_hint()),
+        Span::styled(" ignore  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" close", theme::key_desc()),
+    ]);
+    f.render_widget(Paragraph::new(hint), chunks[1]);
+}
+
+fn render_template_picker(f: &mut Frame, templates: &[BootstrapTemplate], selected: usize) {
+    let height = (templates.len() as u16 + 5).clamp(8, 24);
+    let area = centered_rect(70, height, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" Bootstrap template ", theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::styled("  (none)", theme::table_row()),
+        Span::styled("  detection only", theme::muted()),
+    ]))];
+    items.extend(templates.iter().map(|t| {
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("  {:<20}", t.name), theme::table_row()),
+            Span::styled(t.description.clone(), theme::muted()),
+        ]))
+    }));
+    let list = List::new(items).highlight_style(theme::table_selected());
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(list, chunks[0], &mut state);
+
+    let hint = Line::from(vec![
+        Span::styled("  Enter", theme::key_hint()),
+        Span::styled(" choose  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" cancel", theme::key_desc()),
+    ]);
+    f.render_widget(Paragraph::new(hint), chunks[1]);
+}
+
+fn render_drift_breakdown(f: &mut Frame, report: &DriftReport) {
+    let body_rows = report.tools.len().max(1) + report.untrusted.len() * 2;
+    let height = (body_rows as u16 + 7).clamp(9, 28);
+    let area = centered_rect(84, height, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(format!(" CWD drift — {} ", report.dir), theme::title()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::popup_border())
+        .style(theme::popup_bg());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let untrusted_height = if report.untrusted.is_empty() {
+        0
+    } else {
+        report.untrusted.len() as u16 + 2
+    };
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(untrusted_height),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    if !report.untrusted.is_empty() {
+        let mut lines = vec![Line::from(Span::styled(
+            "  Untrusted — mise ignores these files until they are trusted:",
+            Style::default().fg(theme::YELLOW),
+        ))];
+        lines.extend(
+            report
+                .untrusted
+                .iter()
+                .map(|p| Line::from(Span::styled(format!("    {p}"), theme::table_row()))),
+        );
+        f.render_widget(Paragraph::new(lines), chunks[0]);
+    }
+
+    if report.tools.is_empty() {
+        let msg = match report.state {
+            DriftState::NoConfig => "  No local mise config applies here".to_string(),
+            DriftState::Untrusted => String::new(),
+            _ => format!(
+                "  All {} local requirement(s) are installed and current",
+                report.healthy
+            ),
+        };
+        f.render_widget(Paragraph::new(msg).style(theme::muted()), chunks[1]);
+    } else {
+        let header = Row::new(vec![
+            Cell::from("  Tool"),
+            Cell::from("Requested"),
+            Cell::from("Installed"),
+            Cell::from("Issue"),
+            Cell::from("Source"),
+        ])
+        .style(theme::table_header());
+
+        let rows: Vec<Row> = report
+            .tools
+            .iter()
+            .map(|t| {
+                let (issue, style) = match t.issue {
+                    DriftIssue::Missing => (t.issue.label().to_string(), theme::error()),
+                    DriftIssue::WrongVersion => {
+                        (t.issue.label().to_string(), Style::default().fg(theme::YELLOW))
+                    }
+                    DriftIssue::Outdated => (
+                        format!("{} → {}", t.issue.label(), t.latest),
+                        Style::default().fg(theme::YELLOW),
+                    ),
+                };
+                let installed = if t.installed.is_empty() {
+                    "—".to_string()
+                } else {
+                    t.installed.join(", ")
+                };
+                let source = std::path::Path::new(&t.source)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| t.source.clone());
+                Row::new(vec![
+                    Cell::from(Span::styled(format!("  {}", t.tool), theme::table_row())),
+                    Cell::from(Span::styled(t.requested.clone(), theme::muted())),
+                    Cell::from(Span::styled(installed, theme::table_row())),
+                    Cell::from(Span::styled(issue, style)),
+                    Cell::from(Span::styled(source, theme::muted())),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(16),
+            Constraint::Length(11),
+            Constraint::Min(14),
+            Constraint::Length(22),
+            Constraint::Length(16),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header), chunks[1]);
+    }
+
+    // Only offer the fixes that apply to this report
+    let mut hint = vec![Span::raw("  ")];
+    let mut push = |key: &'static str, desc: &'static str| {
+        hint.push(Span::styled(key, theme::key_hint()));
+        hint.push(Span::styled(format!(" {desc}  "), theme::key_desc()));
+    };
+    if report.has(DriftIssue::Missing) || report.has(DriftIssue::WrongVersion) {
+        push("i", "install missing");
+    }
+    if !report.untrusted.is_empty() {
+        push("t", "trust");
+    }
+    if report.has(DriftIssue::Outdated) {
+        push("u", "upgrade pins");
+    }
+    push("Esc", "close");
+    f.render_widget(Paragraph::new(Line::from(hint)), chunks[2]);
+}
+
 fn render_help(f: &mut Frame) {
-    let area = centered_rect(54, 26, f.area());
+    let area = centered_rect(54, 36, f.area());
     f.render_widget(Clear, area);
 
     let block = Block::default()
@@ -245,24 +1098,34 @@ fn render_help(f: &mut Frame) {
         "    h/l ←/→     Focus sidebar/content",
         "    Tab          Next tab",
         "    Shift+Tab    Previous tab",
-        "    PgUp/PgDn    Scroll by 10",
+        "    PgUp/PgDn    Scroll by 10 (Config: file preview)",
         "    Mouse scroll Navigate lists",
         "",
         "  Actions",
         "    /            Search (all tabs)",
         "    i            Install (Registry)",
         "    u            Update/Upgrade (Tools/Outdated)",
-        "    U            Use global (Registry) / Upgrade all",
+        "    U            Use global / Upgrade all (+Projects)",
         "    Enter        Edit inline / Drill-down",
         "    v            Detail (Tools)",
         "    a            Add to config",
         "    d            Delete from config",
-        "    w            Write config changes",
+        "    x            Uninstall version (Tools)",
+        "    w            Review diff & write config changes",
+        "    z/Z          Undo/redo config edits",
         "    r            Refresh all data",
-        "    p            Prune unused versions",
+        "    p            Prune unused versions (select)",
         "    t            Trust config (Config)",
+        "    T            Trust manager (trust/untrust/ignore)",
         "    s            Cycle sort column/order",
         "    c            Edit scan config (Projects)",
+        "    f / #        Pin project / edit its tags",
+        "    g            Cycle project group filter",
+        "    E            Export health report (Projects)",
+        "    D            CWD drift breakdown & fixes",
+        "    C            Change working directory",
+        "    I            Install missing (all Projects)",
+        "    L            Regenerate lock (Projects/Config)",
         "    Esc          Cancel / Close popup",
         "    q            Quit",
         "    ?            This help",
@@ -292,7 +1155,7 @@ fn render_scan_config(
     max_depth: usize,
 ) {
     // Height: 3 (border+title+depth) + dirs.len() + 1 (add row) + 2 (hints) + padding
-    let height = (dirs.len() as u16 + 8).max(12).min(28);
+    let height = (dirs.len() as u16 + 8).clamp(12, 28);
     let area = centered_rect(58, height, f.area());
     f.render_widget(Clear, area);
 
@@ -379,8 +1242,79 @@ fn render_scan_config(
     f.render_widget(Paragraph::new(hint), chunks[5]);
 }
 
-fn render_editor(f: &mut Frame, tab: Tab, field0: &str, field1: &str, active_field: usize) {
-    let area = centered_rect(48, 10, f.area());
+fn render_editor(
+    f: &mut Frame,
+    tab: Tab,
+    labels: &[&str],
+    fields: &[String],
+    active_field: usize,
+    problems: &[EditorProblem],
+) {
+    let label_width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    // One line per field, except multi-line values which take one line each.
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    for (i, (label, value)) in labels.iter().zip(fields).enumerate() {
+        let style = if i == active_field { theme::search_input() } else { theme::table_row() };
+        let cursor = if i == active_field { "\u{2588}" } else { "" };
+        let mut value_lines: Vec<&str> = value.split('\n').collect();
+        let last = value_lines.pop().unwrap_or("");
+        let mut prefix = format!("  {label:>label_width$}: ");
+        for l in value_lines {
+            lines.push(Line::from(vec![
+                Span::styled(prefix.clone(), theme::key_desc()),
+                Span::styled(l.to_string(), style),
+            ]));
+            prefix = " ".repeat(prefix.chars().count());
+        }
+        lines.push(Line::from(vec![
+            Span::styled(prefix, theme::key_desc()),
+            Span::styled(last.to_string(), style),
+            Span::styled(cursor, style),
+        ]));
+    }
+    lines.push(Line::from(""));
+    for p in problems {
+        let (icon, style) = if p.blocking {
+            ("✗", theme::error())
+        } else {
+            ("⚠", Style::default().fg(theme::YELLOW))
+        };
+        lines.push(Line::from(Span::styled(format!("  {icon} {}", p.message), style)));
+    }
+    if !problems.is_empty() {
+        lines.push(Line::from(""));
+    }
+
+    let mut hint = vec![
+        Span::styled("  Tab", theme::key_hint()),
+        Span::styled(" next field  ", theme::key_desc()),
+    ];
+    let env_list = tab == Tab::Environment
+        && fields.first().is_some_and(|k| EnvRowKind::from_key(k).is_list());
+    if matches!(tab, Tab::Tasks | Tab::Tools) || env_list {
+        hint.push(Span::styled("Ctrl+N", theme::key_hint()));
+        hint.push(Span::styled(" new line  ", theme::key_desc()));
+    }
+    hint.extend([
+        Span::styled("Enter", theme::key_hint()),
+        Span::styled(" confirm  ", theme::key_desc()),
+        Span::styled("Esc", theme::key_hint()),
+        Span::styled(" cancel", theme::key_desc()),
+    ]);
+    lines.push(Line::from(hint));
+    let note = match tab {
+        Tab::Tasks => Some("  Lists are comma-separated; env is KEY=value, ..."),
+        Tab::Tools => Some("  Versions comma-separated; options key=value; ..."),
+        Tab::Environment => Some("  Directives: _.path, _.file, _.source (comma lists), _.python.venv"),
+        _ => None,
+    };
+    if let Some(note) = note {
+        lines.push(Line::from(Span::styled(note, theme::muted())));
+    }
+
+    let width = if tab == Tab::Environment { 48 } else { 72 };
+    let area = centered_rect(width, lines.len() as u16 + 2, f.area());
     f.render_widget(Clear, area);
 
     let title = match tab {
@@ -397,56 +1331,5 @@ fn render_editor(f: &mut Frame, tab: Tab, field0: &str, field1: &str, active_fie
         .border_style(theme::popup_border())
         .style(theme::popup_bg());
 
-    let inner = block.inner(area);
-    f.render_widget(block, area);
-
-    let (label0, label1) = match tab {
-        Tab::Tools => ("Name", "Version"),
-        Tab::Environment => ("Key", "Value"),
-        Tab::Tasks => ("Name", "Command"),
-        _ => ("Field 1", "Field 2"),
-    };
-
-    let chunks = Layout::default()
-        .direction(ratatui::layout::Direction::Vertical)
-        .constraints([
-            Constraint::Length(1), // blank
-            Constraint::Length(1), // field0
-            Constraint::Length(1), // blank
-            Constraint::Length(1), // field1
-            Constraint::Length(1), // blank
-            Constraint::Length(1), // hints
-        ])
-        .split(inner);
-
-    // Field 0
-    let f0_style = if active_field == 0 { theme::search_input() } else { theme::table_row() };
-    let f0_cursor = if active_field == 0 { "\u{2588}" } else { "" };
-    let field0_line = Line::from(vec![
-        Span::styled(format!("  {label0}: "), theme::key_desc()),
-        Span::styled(field0, f0_style),
-        Span::styled(f0_cursor, f0_style),
-    ]);
-    f.render_widget(Paragraph::new(field0_line), chunks[1]);
-
-    // Field 1
-    let f1_style = if active_field == 1 { theme::search_input() } else { theme::table_row() };
-    let f1_cursor = if active_field == 1 { "\u{2588}" } else { "" };
-    let field1_line = Line::from(vec![
-        Span::styled(format!("  {label1}: "), theme::key_desc()),
-        Span::styled(field1, f1_style),
-        Span::styled(f1_cursor, f1_style),
-    ]);
-    f.render_widget(Paragraph::new(field1_line), chunks[3]);
-
-    // Hints
-    let hint = Line::from(vec![
-        Span::styled("  Tab", theme::key_hint()),
-        Span::styled(" switch  ", theme::key_desc()),
-        Span::styled("Enter", theme::key_hint()),
-        Span::styled(" confirm  ", theme::key_desc()),
-        Span::styled("Esc", theme::key_hint()),
-        Span::styled(" cancel", theme::key_desc()),
-    ]);
-    f.render_widget(Paragraph::new(hint), chunks[5]);
+    f.render_widget(Paragraph::new(lines).block(block), area);
 }
@@ -1,6 +1,7 @@
+use super::format::format_age;
 use super::highlight::highlight_cached;
 use crate::app::{App, LoadState};
-use crate::model::ProjectHealthStatus;
+use crate::model::{LockState, MiseProject, ProjectHealthStatus};
 use crate::theme;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
@@ -25,6 +26,50 @@ fn health_style(status: &ProjectHealthStatus) -> Style {
     }
 }
 
+pub(super) fn lock_style(lock: LockState) -> Style {
+    match lock {
+        LockState::Locked => Style::default().fg(theme::GREEN),
+        LockState::Unlocked | LockState::Drift => Style::default().fg(theme::YELLOW),
+        LockState::Stale => Style::default().fg(theme::RED),
+    }
+}
+
+/// `sha256:0123abcd…` — algorithm plus the first 8 hex digits.
+pub(super) fn short_checksum(sum: &str) -> String {
+    match sum.split_once(':') {
+        Some((algo, hex)) if hex.len() > 8 => format!("{algo}:{}…", &hex[..8]),
+        _ => sum.to_string(),
+    }
+}
+
+/// Lock column for the project list: no lockfile, all locked, or the issue count.
+fn lock_summary(proj: &MiseProject) -> Span<'static> {
+    if proj.lockfile.is_none() {
+        return Span::styled("—", theme::muted());
+    }
+    match proj.lock_issues() {
+        0 => Span::styled(LockState::Locked.label(), lock_style(LockState::Locked)),
+        1 => Span::styled("1 issue", lock_style(LockState::Drift)),
+        n => Span::styled(format!("{n} issues"), lock_style(LockState::Drift)),
+    }
+}
+
+/// Whether the mise config has uncommitted changes; "—" outside a git repo.
+fn git_status(proj: &MiseProject) -> Span<'static> {
+    match &proj.git {
+        None => Span::styled("—", theme::muted()),
+        Some(g) if g.config_dirty => Span::styled("modified", Style::default().fg(theme::YELLOW)),
+        Some(_) => Span::styled("clean", theme::muted()),
+    }
+}
+
+fn age_cell(secs: Option<u64>) -> Span<'static> {
+    match secs {
+        Some(s) => Span::styled(format_age(s), theme::table_row()),
+        None => Span::styled("—", theme::muted()),
+    }
+}
+
 fn render_list(f: &mut Frame, area: Rect, app: &App) {
     let chunks = if app.search_active {
         Layout::default()
@@ -57,11 +102,17 @@ fn render_list(f: &mut Frame, area: Rect, app: &App) {
     let content_area = chunks[1];
     let count = app.filtered_projects.len();
     let total = app.projects.len();
-    let title = if app.search_active && !app.search_query.is_empty() {
-        format!(" Projects ({count}/{total}) ")
-    } else {
-        format!(" Projects ({total}) ")
+    let scanning = app.projects_state == LoadState::Loading;
+    let mut title = match &app.projects_group_filter {
+        Some(group) => format!(" Projects [{group}] ({count}/{total}) "),
+        None if app.search_active && !app.search_query.is_empty() => {
+            format!(" Projects ({count}/{total}) ")
+        }
+        None => format!(" Projects ({total}) "),
     };
+    if scanning && total > 0 {
+        title.push_str(&format!("{} scanning… ", app.spinner_char()));
+    }
 
     let block = Block::default()
         .title(Span::styled(title, theme::title()))
@@ -70,7 +121,7 @@ fn render_list(f: &mut Frame, area: Rect, app: &App) {
         .border_style(theme::border_focused())
         .style(Style::default().bg(theme::BG));
 
-    if app.projects_state == LoadState::Loading {
+    if scanning && total == 0 {
         let spinner = app.spinner_char();
         let loading = Paragraph::new(format!("  {spinner} Scanning projects..."))
             .style(theme::muted())
@@ -82,6 +133,8 @@ fn render_list(f: &mut Frame, area: Rect, app: &App) {
     if app.filtered_projects.is_empty() {
         let msg = if app.search_active && !app.search_query.is_empty() {
             "  No matching projects"
+        } else if app.projects_group_filter.is_some() && total > 0 {
+            "  No projects in this group — press g to switch"
         } else {
             "  No projects found — check ~/.config/misetui/config.toml scan_dirs"
         };
@@ -91,42 +144,92 @@ fn render_list(f: &mut Frame, area: Rect, app: &App) {
     }
 
     let header = Row::new(vec![
-        Cell::from("Name"),
-        Cell::from("Path"),
-        Cell::from("Tools"),
-        Cell::from("Health"),
+        Cell::from(format!("Name{}", app.sort_indicator(0))),
+        Cell::from(format!("Path{}", app.sort_indicator(1))),
+        Cell::from(format!("Tools{}", app.sort_indicator(2))),
+        Cell::from(format!("Health{}", app.sort_indicator(3))),
+        Cell::from(format!("Lock{}", app.sort_indicator(4))),
+        Cell::from(format!("Branch{}", app.sort_indicator(5))),
+        Cell::from(format!("Config{}", app.sort_indicator(6))),
+        Cell::from(format!("Commit{}", app.sort_indicator(7))),
+        Cell::from(format!("Changed{}", app.sort_indicator(8))),
     ])
     .style(theme::table_header());
 
-    let rows: Vec<Row> = app
-        .filtered_projects
-        .iter()
-        .enumerate()
-        .map(|(i, &idx)| {
-            let proj = &app.projects[idx];
-            let name_hl = app.projects_hl.get(i).map(|v| v.as_slice()).unwrap_or(&[]);
-
-            // Truncate path to last 3 components for readability
-            let path_display = {
-                let parts: Vec<&str> = proj.path.split('/').collect();
-                let shown = &parts[parts.len().saturating_sub(3)..];
-                format!("…/{}", shown.join("/"))
+    // Section headers (favorites, then each group) only when something is pinned or grouped
+    let sectioned = (!app.search_active || app.search_query.is_empty())
+        && app
+            .filtered_projects
+            .iter()
+            .any(|&idx| app.projects[idx].section() != (2, None));
+
+    let mut rows: Vec<Row> = Vec::new();
+    let mut visual_selected = app.projects_selected;
+    let mut section = None;
+    for (i, &idx) in app.filtered_projects.iter().enumerate() {
+        let proj = &app.projects[idx];
+        if sectioned && section != Some(proj.section()) {
+            section = Some(proj.section());
+            let label = match proj.section() {
+                (0, _) => "★ favorites",
+                (_, Some(group)) => group,
+                _ => "ungrouped",
             };
+            rows.push(
+                Row::new(vec![Cell::from(Span::styled(
+                    format!("\u{2500}\u{2500} {label} \u{2500}\u{2500}"),
+                    theme::muted(),
+                ))])
+                .style(Style::default().bg(theme::BG)),
+            );
+            if i <= app.projects_selected {
+                visual_selected += 1;
+            }
+        }
+        let name_hl = app.projects_hl.get(i).map(|v| v.as_slice()).unwrap_or(&[]);
 
-            Row::new(vec![
-                Cell::from(highlight_cached(&proj.name, name_hl, theme::table_row())),
-                Cell::from(Span::styled(path_display, theme::muted())),
-                Cell::from(Span::styled(proj.tool_count.to_string(), theme::table_row())),
-                Cell::from(Span::styled(proj.health.label(), health_style(&proj.health))),
-            ])
-        })
-        .collect();
+        // Truncate path to last 3 components for readability
+        let path_display = {
+            let parts: Vec<&str> = proj.path.split('/').collect();
+            let shown = &parts[parts.len().saturating_sub(3)..];
+            format!("…/{}", shown.join("/"))
+        };
+
+        let mut name = Vec::new();
+        if proj.favorite {
+            name.push(Span::styled("★ ", Style::default().fg(theme::YELLOW)));
+        }
+        name.extend(highlight_cached(&proj.name, name_hl, theme::table_row()).spans);
+        for tag in &proj.tags {
+            name.push(Span::styled(format!(" #{tag}"), theme::muted()));
+        }
+
+        rows.push(Row::new(vec![
+            Cell::from(Line::from(name)),
+            Cell::from(Span::styled(path_display, theme::muted())),
+            Cell::from(Span::styled(proj.tool_count.to_string(), theme::table_row())),
+            Cell::from(Span::styled(proj.health.label(), health_style(&proj.health))),
+            Cell::from(lock_summary(proj)),
+            Cell::from(Span::styled(
+                proj.git.as_ref().map_or("—", |g| g.branch.as_str()).to_string(),
+                theme::muted(),
+            )),
+            Cell::from(git_status(proj)),
+            Cell::from(age_cell(proj.git.as_ref().and_then(|g| g.commit_age_secs))),
+            Cell::from(age_cell(proj.config_age_secs)),
+        ]));
+    }
 
     let widths = [
-        Constraint::Length(20),
+        Constraint::Length(28),
         Constraint::Min(20),
         Constraint::Length(6),
         Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(16),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
     ];
 
     let table = Table::new(rows, widths)
@@ -135,7 +238,7 @@ fn render_list(f: &mut Frame, area: Rect, app: &App) {
         .row_highlight_style(theme::table_selected());
 
     let mut state = TableState::default();
-    state.select(Some(app.projects_selected));
+    state.select(Some(visual_selected));
     f.render_stateful_widget(table, content_area, &mut state);
 }
 
@@ -146,7 +249,10 @@ fn render_drill_down(f: &mut Frame, area: Rect, app: &App) {
     };
     let proj = &app.projects[idx];
 
-    let title = format!(" {} — Tool Health ", proj.name);
+    let title = match proj.lockfile.as_deref().and_then(|l| std::path::Path::new(l).file_name()) {
+        Some(lock) => format!(" {} — Tool Health · {} ", proj.name, lock.to_string_lossy()),
+        None => format!(" {} — Tool Health ", proj.name),
+    };
     let block = Block::default()
         .title(Span::styled(title, theme::title()))
         .borders(Borders::ALL)
@@ -161,13 +267,18 @@ fn render_drill_down(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let header = Row::new(vec![
+    // Lock columns only appear when the project has a lockfile
+    let locked = proj.lockfile.is_some();
+    let mut header_cells = vec![
         Cell::from("Tool"),
         Cell::from("Required"),
         Cell::from("Installed"),
         Cell::from("Status"),
-    ])
-    .style(theme::table_header());
+    ];
+    if locked {
+        header_cells.extend([Cell::from("Locked"), Cell::from("Checksum"), Cell::from("Lock")]);
+    }
+    let header = Row::new(header_cells).style(theme::table_header());
 
     let rows: Vec<Row> = proj
         .tools
@@ -178,7 +289,7 @@ fn render_drill_down(f: &mut Frame, area: Rect, app: &App) {
             } else {
                 &tool_health.installed
             };
-            Row::new(vec![
+            let mut cells = vec![
                 Cell::from(Span::styled(tool_health.tool.clone(), theme::table_row())),
                 Cell::from(Span::styled(tool_health.required.clone(), theme::muted())),
                 Cell::from(Span::styled(installed_text.to_owned(), theme::table_row())),
@@ -186,16 +297,27 @@ fn render_drill_down(f: &mut Frame, area: Rect, app: &App) {
                     tool_health.status.label(),
                     health_style(&tool_health.status),
                 )),
-            ])
+            ];
+            if let Some(lock) = tool_health.lock {
+                cells.extend([
+                    Cell::from(Span::styled(tool_health.locked.clone(), theme::table_row())),
+                    Cell::from(Span::styled(short_checksum(&tool_health.checksum), theme::muted())),
+                    Cell::from(Span::styled(lock.label(), lock_style(lock))),
+                ]);
+            }
+            Row::new(cells)
         })
         .collect();
 
-    let widths = [
+    let mut widths = vec![
         Constraint::Length(16),
         Constraint::Length(14),
         Constraint::Length(14),
         Constraint::Length(12),
     ];
+    if locked {
+        widths.extend([Constraint::Length(14), Constraint::Length(18), Constraint::Length(12)]);
+    }
 
     let table = Table::new(rows, widths)
         .header(header)
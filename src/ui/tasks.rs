@@ -167,7 +167,12 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     Span::styled("+ ", style),
                     Span::styled(&row.name, style),
                 ]));
-                let cmd_cell = Cell::from(Span::styled(&row.command, style));
+                let summary = if row.description.is_empty() {
+                    row.command.lines().next().unwrap_or("")
+                } else {
+                    &row.description
+                };
+                let cmd_cell = Cell::from(Span::styled(summary, style));
                 all_rows.push(Row::new(vec![
                     name_cell, cmd_cell,
                     Cell::from(Span::styled(source.rsplit('/').next().unwrap_or(source), theme::muted())),
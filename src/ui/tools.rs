@@ -1,6 +1,6 @@
 use super::highlight::highlight_cached;
 use crate::app::{App, LoadState};
-use crate::model::EditorRowStatus;
+use crate::model::{EditorRowStatus, InstalledTool};
 use crate::theme;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -29,6 +29,14 @@ fn status_style(status: EditorRowStatus) -> Style {
     }
 }
 
+/// Number of scanned projects / active configs whose requirement resolves to this version.
+fn used_by_cell<'a>(app: &App, tool: &InstalledTool) -> Cell<'a> {
+    match app.tool_usage(&tool.name, &tool.version).len() {
+        0 => Cell::from(Span::styled("—", theme::muted())),
+        n => Cell::from(Span::styled(n.to_string(), theme::table_row())),
+    }
+}
+
 pub fn render(f: &mut Frame, area: Rect, app: &App) {
     let chunks = if app.search_active {
         Layout::default()
@@ -103,13 +111,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         Cell::from(format!("Name{}", app.sort_indicator(0))),
         Cell::from(format!("Version{}", app.sort_indicator(1))),
         Cell::from(format!("Status{}", app.sort_indicator(2))),
-        Cell::from(format!("Source{}", app.sort_indicator(3))),
+        Cell::from(format!("Used by{}", app.sort_indicator(3))),
+        Cell::from(format!("Source{}", app.sort_indicator(4))),
     ])
     .style(theme::table_header());
 
     // Group tools by source for section headers when NOT searching
     if !app.search_active && app.editor_states_loaded {
-        let mut groups: BTreeMap<&str, Vec<(usize, &crate::model::InstalledTool)>> = BTreeMap::new();
+        let mut groups: BTreeMap<&str, Vec<(usize, &InstalledTool)>> = BTreeMap::new();
         for (i, tool) in tools.iter().enumerate() {
             let key = if tool.source.is_empty() { "(runtime)" } else { &tool.source };
             groups.entry(key).or_default().push((i, tool));
@@ -129,6 +138,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     Cell::from(""),
                     Cell::from(""),
                     Cell::from(""),
+                    Cell::from(""),
                 ])
                 .style(Style::default().bg(theme::BG))
             );
@@ -172,6 +182,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 let source_short = tool.source.rsplit('/').next().unwrap_or(&tool.source);
                 all_rows.push(Row::new(vec![
                     name_cell, version_cell, status,
+                    used_by_cell(app, tool),
                     Cell::from(Span::styled(source_short, theme::muted())),
                 ]));
                 visual_idx += 1;
@@ -185,10 +196,11 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     Span::styled("+ ", style),
                     Span::styled(&row.name, style),
                 ]));
-                let ver_cell = Cell::from(Span::styled(&row.version, style));
+                let ver_cell = Cell::from(Span::styled(row.version_display(), style));
                 all_rows.push(Row::new(vec![
                     name_cell, ver_cell,
                     Cell::from(Span::styled("+ new", style)),
+                    Cell::from(""),
                     Cell::from(Span::styled(source.rsplit('/').next().unwrap_or(source), theme::muted())),
                 ]));
                 visual_idx += 1;
@@ -201,6 +213,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             Constraint::Length(18),
             Constraint::Length(22),
             Constraint::Length(12),
+            Constraint::Length(9),
             Constraint::Min(10),
         ];
 
@@ -242,6 +255,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     Cell::from(highlight_cached(&tool.name, name_hl, theme::table_row())),
                     version_cell,
                     status,
+                    used_by_cell(app, tool),
                     Cell::from(Span::styled(source_short, theme::muted())),
                 ])
             })
@@ -251,6 +265,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             Constraint::Length(16),
             Constraint::Length(22),
             Constraint::Length(12),
+            Constraint::Length(9),
             Constraint::Min(10),
         ];
 
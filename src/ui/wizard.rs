@@ -1,5 +1,5 @@
 use crate::app::App;
-use crate::model::WizardStep;
+use crate::model::{DetectedTask, DetectedTool, ToolMerge, WizardStep};
 use crate::theme;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Style};
@@ -15,7 +15,7 @@ fn success_style() -> Style {
 /// Top-level render entry point — renders in the tab content area.
 pub fn render(f: &mut Frame, area: Rect, app: &App) {
     match app.wizard.step {
-        WizardStep::Idle => render_idle(f, area, &app.wizard.target_dir),
+        WizardStep::Idle => render_idle(f, area, app),
         WizardStep::Detecting => render_detecting(f, area, &app.wizard.target_dir, app.spinner_char()),
         WizardStep::Review => render_review(f, area, app),
         WizardStep::Preview => render_preview(f, area, app),
@@ -24,7 +24,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
 }
 
 /// Idle state: welcome screen — "Press Enter to detect project tools"
-fn render_idle(f: &mut Frame, area: Rect, target: &str) {
+fn render_idle(f: &mut Frame, area: Rect, app: &App) {
+    let target = app.wizard.target_dir.as_str();
     let block = Block::default()
         .title(Span::styled(" ⚡ Bootstrap Wizard ", theme::title()))
         .borders(Borders::ALL)
@@ -37,11 +38,15 @@ fn render_idle(f: &mut Frame, area: Rect, target: &str) {
 
     let short_target = shorten_path(target, 50);
 
-    // Check if .mise.toml exists
-    let mise_toml_path = std::path::Path::new(target).join(".mise.toml");
-    let exists_line = if mise_toml_path.exists() {
+    // Check for an existing config to merge into
+    let config_path = crate::mise::wizard_config_path(std::path::Path::new(target));
+    let exists_line = if config_path.exists() {
+        let name = config_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
         Line::from(Span::styled(
-            "  .mise.toml already exists — will be overwritten",
+            format!("  {name} already exists — detected tools will be merged into it"),
             Style::default().fg(Color::Rgb(230, 180, 60)),
         ))
     } else {
@@ -51,6 +56,18 @@ fn render_idle(f: &mut Frame, area: Rect, target: &str) {
         ))
     };
 
+    let template_line = match &app.wizard.template {
+        Some(t) => Line::from(vec![
+            Span::styled("  Template: ", theme::muted()),
+            Span::styled(t.name.as_str(), theme::table_row()),
+            Span::styled("  (t to change)", theme::muted()),
+        ]),
+        None => Line::from(Span::styled(
+            "  No template — press t to start from one in ~/.config/misetui/templates",
+            theme::muted(),
+        )),
+    };
+
     let text = vec![
         Line::default(),
         Line::from(Span::styled(
@@ -64,6 +81,7 @@ fn render_idle(f: &mut Frame, area: Rect, target: &str) {
         ]),
         Line::default(),
         exists_line,
+        template_line,
         Line::default(),
         Line::default(),
         Line::from(Span::styled(
@@ -79,11 +97,11 @@ fn render_idle(f: &mut Frame, area: Rect, target: &str) {
             theme::muted(),
         )),
         Line::from(Span::styled(
-            "    3. Let you review and toggle detected tools",
+            "    3. Let you review and toggle detected tools (per package in monorepos)",
             theme::muted(),
         )),
         Line::from(Span::styled(
-            "    4. Generate .mise.toml and run mise install",
+            "    4. Generate or merge into .mise.toml and run mise install",
             theme::muted(),
         )),
         Line::default(),
@@ -165,42 +183,57 @@ fn render_review(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(Paragraph::new(header), chunks[0]);
 
     // Tool list
-    if wizard.tools.is_empty() {
+    if wizard.tools.is_empty() && wizard.tasks.is_empty() {
         let empty = Paragraph::new(Span::styled(
             "  No tools detected — toggle to add manually (feature coming in Phase 4)",
             theme::muted(),
         ));
         f.render_widget(empty, chunks[1]);
     } else {
-        let items: Vec<ListItem> = wizard
-            .tools
-            .iter()
-            .map(|t| {
-                let check = if t.enabled { "✓" } else { "○" };
-                let check_style = if t.enabled {
-                    success_style()
-                } else {
-                    theme::muted()
-                };
-                let version_display = if t.version.is_empty() {
-                    "latest"
-                } else {
-                    t.version.as_str()
-                };
-                let inst_indicator = if t.installed { "↓" } else { " " };
-                ListItem::new(Line::from(vec![
-                    Span::styled(format!("  {check} "), check_style),
-                    Span::styled(format!("{:<16}", t.name), theme::table_row()),
-                    Span::styled(format!("{:<16}", version_display), theme::muted()),
-                    Span::styled(format!("  {inst_indicator} "), theme::muted()),
-                    Span::styled(t.source.as_str(), theme::muted()),
-                ]))
-            })
-            .collect();
+        // Monorepo proposals render as a tree of config files; the selection
+        // indexes tools, so track which display row each tool lands on.
+        let mut items: Vec<ListItem> = Vec::new();
+        let mut selected_row = wizard.selected;
+        if wizard.monorepo {
+            for (i, t) in wizard.tools.iter().enumerate() {
+                if i == 0 || wizard.tools[i - 1].dir != t.dir {
+                    let path = if t.dir.is_empty() {
+                        ".mise.toml (root, shared)".to_string()
+                    } else {
+                        format!("{}/.mise.toml", t.dir)
+                    };
+                    items.push(ListItem::new(Line::from(vec![
+                        Span::styled("  ▾ ", theme::muted()),
+                        Span::styled(path, theme::table_header()),
+                    ])));
+                }
+                let last = wizard.tools.get(i + 1).is_none_or(|n| n.dir != t.dir);
+                if i == wizard.selected {
+                    selected_row = items.len();
+                }
+                items.push(tool_item(t, if last { "    └ " } else { "    ├ " }));
+            }
+        } else {
+            items.extend(wizard.tools.iter().map(|t| tool_item(t, "  ")));
+        }
+
+        if !wizard.tasks.is_empty() {
+            items.push(ListItem::new(Line::default()));
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!("  Tasks imported from existing scripts ({})", wizard.tasks.len()),
+                theme::table_header(),
+            ))));
+            for (i, task) in wizard.tasks.iter().enumerate() {
+                if wizard.selected == wizard.tools.len() + i {
+                    selected_row = items.len();
+                }
+                items.push(task_item(task));
+            }
+        }
 
         let list = List::new(items).highlight_style(theme::table_selected());
         let mut state = ListState::default();
-        state.select(Some(wizard.selected));
+        state.select(Some(selected_row));
         f.render_stateful_widget(list, chunks[1], &mut state);
     }
 
@@ -211,21 +244,75 @@ fn render_review(f: &mut Frame, area: Rect, app: &App) {
     } else {
         theme::muted()
     };
-    let agent_toggle = Line::from(vec![
+    let mut agent_toggle = Line::from(vec![
         Span::styled(format!("  {agent_check} "), agent_style),
-        Span::styled("Write AGENTS.md + CLAUDE.md", theme::table_row()),
+        Span::styled("Add a mise section to AGENTS.md + CLAUDE.md", theme::table_row()),
         Span::styled("  (press a to toggle)", theme::muted()),
     ]);
+    if wizard.packages.len() > 1 {
+        let mode = if wizard.monorepo {
+            format!("monorepo: {} packages", wizard.packages.len() - 1)
+        } else {
+            "single root config".to_string()
+        };
+        agent_toggle.spans.push(Span::styled(format!("   {mode} (press m to switch)"), theme::muted()));
+    }
     f.render_widget(Paragraph::new(agent_toggle), chunks[3]);
 }
 
-/// Preview step: scrollable generated .mise.toml content.
+/// One tool row in the Review list; `prefix` carries the tree indentation.
+fn tool_item<'a>(t: &'a DetectedTool, prefix: &str) -> ListItem<'a> {
+    let check = if t.enabled { "✓" } else { "○" };
+    let check_style = if t.enabled {
+        success_style()
+    } else {
+        theme::muted()
+    };
+    let version_display = if t.version.is_empty() {
+        "latest"
+    } else {
+        t.version.as_str()
+    };
+    let inst_indicator = if t.installed { "↓" } else { " " };
+    ListItem::new(Line::from(vec![
+        Span::styled(prefix.to_string(), theme::muted()),
+        Span::styled(format!("{check} "), check_style),
+        Span::styled(format!("{:<16}", t.name), theme::table_row()),
+        Span::styled(format!("{:<16}", version_display), theme::muted()),
+        Span::styled(format!("  {inst_indicator} "), theme::muted()),
+        Span::styled(t.source.as_str(), theme::muted()),
+    ]))
+}
+
+/// One imported task row in the Review list.
+fn task_item(t: &DetectedTask) -> ListItem<'_> {
+    let check = if t.enabled { "✓" } else { "○" };
+    let check_style = if t.enabled {
+        success_style()
+    } else {
+        theme::muted()
+    };
+    let source = if t.dir.is_empty() {
+        t.source.clone()
+    } else {
+        format!("{}/{}", t.dir, t.source)
+    };
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("  {check} "), check_style),
+        Span::styled(format!("{:<16}", t.name), theme::table_row()),
+        Span::styled(format!("{:<24}", t.run), theme::muted()),
+        Span::styled(source, theme::muted()),
+    ]))
+}
+
+/// Preview step: merge summary (when configs already exist) above the
+/// scrollable generated config content.
 fn render_preview(f: &mut Frame, area: Rect, app: &App) {
     let wizard = &app.wizard;
 
     let block = Block::default()
         .title(Span::styled(
-            " ⚡ Bootstrap Wizard — Preview .mise.toml ",
+            " ⚡ Bootstrap Wizard — Preview ",
             theme::title(),
         ))
         .borders(Borders::ALL)
@@ -236,6 +323,44 @@ fn render_preview(f: &mut Frame, area: Rect, app: &App) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    // Fresh configs are all additions; the summary only matters when merging
+    let merging = wizard.merge.iter().any(|m| m.merge != ToolMerge::Added);
+    let summary_height = if merging {
+        (wizard.merge.len() as u16 + 2).min(inner.height / 2)
+    } else {
+        0
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(summary_height), Constraint::Min(1)])
+        .split(inner);
+
+    if merging {
+        let mut summary = vec![Line::from(Span::styled(
+            "  Merging into existing config — other tables and comments are kept",
+            theme::table_header(),
+        ))];
+        summary.extend(wizard.merge.iter().map(|m| {
+            let (mark, style, detail) = match &m.merge {
+                ToolMerge::Added => ("+", success_style(), "added".to_string()),
+                ToolMerge::Changed { from } => (
+                    "~",
+                    Style::default().fg(theme::YELLOW),
+                    format!("was {from}"),
+                ),
+                ToolMerge::Kept => ("=", theme::muted(), "kept".to_string()),
+            };
+            Line::from(vec![
+                Span::styled(format!("  {mark} "), style),
+                Span::styled(format!("{:<16}", m.name), theme::table_row()),
+                Span::styled(format!("{:<16}", m.version), style),
+                Span::styled(format!("{detail:<16}"), theme::muted()),
+                Span::styled(m.config.as_str(), theme::muted()),
+            ])
+        }));
+        f.render_widget(Paragraph::new(summary), chunks[0]);
+    }
+
     let lines: Vec<Line> = wizard
         .preview_content
         .lines()
@@ -245,7 +370,7 @@ fn render_preview(f: &mut Frame, area: Rect, app: &App) {
     let paragraph = Paragraph::new(lines)
         .wrap(Wrap { trim: false })
         .scroll((wizard.preview_scroll as u16, 0));
-    f.render_widget(paragraph, inner);
+    f.render_widget(paragraph, chunks[1]);
 }
 
 /// Writing step: spinner + message while mise install runs.
@@ -0,0 +1,363 @@
+//! Validation of inline editor changes against the registry and mise's version grammar.
+
+use crate::app::Tab;
+use crate::model::{
+    EditorEnvRow, EditorRowStatus, EditorState, EditorTaskRow, EditorToolRow, EnvRowKind,
+    RegistryEntry,
+};
+use std::collections::HashSet;
+
+/// Backends mise ships with; any other `backend:` prefix only gets a warning, since
+/// plugins and newer mise releases add their own.
+const KNOWN_BACKENDS: [&str; 16] = [
+    "aqua", "asdf", "cargo", "conda", "core", "dotnet", "gem", "github", "gitlab", "go", "http",
+    "npm", "pipx", "spm", "ubi", "vfox",
+];
+
+/// Version prefixes mise understands in front of a version string.
+const VERSION_PREFIXES: [&str; 4] = ["ref:", "path:", "prefix:", "sub-"];
+
+/// A problem found in an editor row.
+#[derive(Debug, Clone)]
+pub struct EditorProblem {
+    /// `section.key` the problem belongs to, e.g. `tools.nodjs`.
+    pub location: String,
+    pub message: String,
+    /// Blocking problems prevent `EditorWrite`; the rest are warnings.
+    pub blocking: bool,
+}
+
+impl EditorProblem {
+    fn error(location: String, message: String) -> Self {
+        Self {
+            location,
+            message,
+            blocking: true,
+        }
+    }
+
+    fn warning(location: String, message: String) -> Self {
+        Self {
+            location,
+            message,
+            blocking: false,
+        }
+    }
+}
+
+/// Validate every pending row of `state`. `remote_missing` holds `tool@version`
+/// specs that `mise ls-remote` reported no match for.
+pub fn validate_state(
+    state: &EditorState,
+    registry: &[RegistryEntry],
+    remote_missing: &HashSet<String>,
+) -> Vec<EditorProblem> {
+    let mut problems = Vec::new();
+    let live = |s: EditorRowStatus| s != EditorRowStatus::Deleted;
+    let pending =
+        |s: EditorRowStatus| matches!(s, EditorRowStatus::Added | EditorRowStatus::Modified);
+
+    let tool_names: Vec<&str> = state
+        .tools
+        .iter()
+        .filter(|r| live(r.status))
+        .map(|r| r.name.as_str())
+        .collect();
+    for row in state.tools.iter().filter(|r| pending(r.status)) {
+        problems.extend(validate_tool(row, registry, remote_missing));
+        problems.extend(duplicate("tools", &row.name, &tool_names));
+    }
+
+    let env_keys: Vec<&str> = state
+        .env_vars
+        .iter()
+        .filter(|r| live(r.status))
+        .map(|r| r.key.as_str())
+        .collect();
+    for row in state.env_vars.iter().filter(|r| pending(r.status)) {
+        problems.extend(validate_env_row(row));
+        problems.extend(duplicate("env", &row.key, &env_keys));
+    }
+
+    let task_names: Vec<&str> = state
+        .tasks
+        .iter()
+        .filter(|r| live(r.status))
+        .map(|r| r.name.as_str())
+        .collect();
+    for row in state.tasks.iter().filter(|r| pending(r.status)) {
+        problems.extend(validate_task(row));
+        problems.extend(duplicate("tasks", &row.name, &task_names));
+    }
+    problems
+}
+
+/// Validate the editor popup's form fields for row `row_idx` before they are applied.
+pub fn validate_fields(
+    tab: Tab,
+    fields: &[String],
+    state: &EditorState,
+    row_idx: usize,
+    registry: &[RegistryEntry],
+    remote_missing: &HashSet<String>,
+) -> Vec<EditorProblem> {
+    let others = |names: Vec<(usize, &str, EditorRowStatus)>| -> Vec<String> {
+        names
+            .into_iter()
+            .filter(|(i, _, s)| *i != row_idx && *s != EditorRowStatus::Deleted)
+            .map(|(_, n, _)| n.to_string())
+            .collect()
+    };
+    let name = fields.first().map(|s| s.trim()).unwrap_or("");
+    let mut problems = match tab {
+        Tab::Tools => {
+            let mut row = EditorToolRow::default();
+            match row.apply_form_fields(fields) {
+                Ok(_) => validate_tool(&row, registry, remote_missing),
+                Err(e) => vec![EditorProblem::error(format!("tools.{name}"), e)],
+            }
+        }
+        Tab::Environment => {
+            let mut row = EditorEnvRow::default();
+            row.apply_form_fields(fields);
+            validate_env_row(&row).into_iter().collect()
+        }
+        Tab::Tasks => {
+            let mut row = EditorTaskRow::default();
+            row.apply_form_fields(fields);
+            validate_task(&row)
+        }
+        _ => Vec::new(),
+    };
+    let (section, existing) = match tab {
+        Tab::Tools => (
+            "tools",
+            others(
+                state
+                    .tools
+                    .iter()
+                    .enumerate()
+                    .map(|(i, r)| (i, r.name.as_str(), r.status))
+                    .collect(),
+            ),
+        ),
+        Tab::Environment => (
+            "env",
+            others(
+                state
+                    .env_vars
+                    .iter()
+                    .enumerate()
+                    .map(|(i, r)| (i, r.key.as_str(), r.status))
+                    .collect(),
+            ),
+        ),
+        Tab::Tasks => (
+            "tasks",
+            others(
+                state
+                    .tasks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, r)| (i, r.name.as_str(), r.status))
+                    .collect(),
+            ),
+        ),
+        _ => return problems,
+    };
+    if existing.iter().any(|n| n == name) {
+        problems.push(EditorProblem::error(
+            format!("{section}.{name}"),
+            format!("duplicate key '{name}'"),
+        ));
+    }
+    problems
+}
+
+fn duplicate(section: &str, name: &str, names: &[&str]) -> Option<EditorProblem> {
+    (names.iter().filter(|n| **n == name).count() > 1).then(|| {
+        EditorProblem::error(
+            format!("{section}.{name}"),
+            format!("duplicate key '{name}'"),
+        )
+    })
+}
+
+fn validate_tool(
+    row: &EditorToolRow,
+    registry: &[RegistryEntry],
+    remote_missing: &HashSet<String>,
+) -> Vec<EditorProblem> {
+    let location = format!("tools.{}", row.name);
+    let mut problems = Vec::new();
+    problems.extend(tool_name_problem(&row.name, registry, &location));
+    if row.versions.is_empty() {
+        problems.push(EditorProblem::error(
+            location.clone(),
+            "no version given".to_string(),
+        ));
+    }
+    for version in &row.versions {
+        if let Some(msg) = version_spec_problem(version) {
+            problems.push(EditorProblem::error(location.clone(), msg));
+        } else if remote_missing.contains(&format!("{}@{version}", row.name)) {
+            problems.push(EditorProblem::warning(
+                location.clone(),
+                format!("no remote version matches '{version}'"),
+            ));
+        }
+    }
+    for (key, _) in row.options.iter().flatten() {
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            problems.push(EditorProblem::error(
+                location.clone(),
+                format!("invalid option key '{key}'"),
+            ));
+        }
+    }
+    problems
+}
+
+/// Check a `[tools]` key: either a registry short name/alias or `backend:name`.
+/// Only a structurally broken key blocks writes; an unknown tool or backend may be
+/// a plugin or a backend newer than this list, so it is a warning.
+fn tool_name_problem(
+    name: &str,
+    registry: &[RegistryEntry],
+    location: &str,
+) -> Option<EditorProblem> {
+    if name.is_empty() {
+        return Some(EditorProblem::error(location.to_string(), "tool name is empty".to_string()));
+    }
+    if let Some((backend, rest)) = name.split_once(':') {
+        if rest.is_empty() {
+            return Some(EditorProblem::error(
+                location.to_string(),
+                format!("missing tool after '{backend}:'"),
+            ));
+        }
+        return (!KNOWN_BACKENDS.contains(&backend)).then(|| {
+            let message = match closest(backend, KNOWN_BACKENDS.iter().copied()) {
+                Some(s) => format!("unknown backend '{backend}' (did you mean '{s}'?)"),
+                None => format!("unknown backend '{backend}'"),
+            };
+            EditorProblem::warning(location.to_string(), message)
+        });
+    }
+    // Registry not loaded yet — nothing to check against
+    if registry.is_empty() {
+        return None;
+    }
+    let known = registry
+        .iter()
+        .any(|e| e.short == name || e.aliases.iter().any(|a| a == name));
+    if known {
+        return None;
+    }
+    let message = match closest(name, registry.iter().map(|e| e.short.as_str())) {
+        Some(s) => format!("unknown tool '{name}' (did you mean '{s}'?)"),
+        None => format!("unknown tool '{name}'"),
+    };
+    Some(EditorProblem::warning(location.to_string(), message))
+}
+
+/// The candidate within edit distance 2 of `name`, for "did you mean" hints.
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|c| (edit_distance(name, c), c))
+        .filter(|(d, _)| *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+/// Check a single version string against the forms mise accepts: `latest`,
+/// `lts`, `system`, prefixed forms (`ref:`, `path:`, `prefix:`, `sub-N:`) and
+/// plain version strings like `20`, `3.12.7` or `1.0.0-rc.1`.
+pub fn version_spec_problem(spec: &str) -> Option<String> {
+    if spec.is_empty() {
+        return Some("version is empty".to_string());
+    }
+    if VERSION_PREFIXES.iter().any(|p| spec.starts_with(p)) {
+        return (!spec.contains(':') || spec.ends_with(':'))
+            .then(|| format!("'{spec}' is missing a value after the prefix"));
+    }
+    if spec.starts_with(['>', '<', '=', '~', '^']) {
+        return Some(format!(
+            "'{spec}': mise does not support range operators; use a prefix like \"1.6\""
+        ));
+    }
+    let valid = spec
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphanumeric())
+        && spec
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+'));
+    (!valid).then(|| format!("invalid version '{spec}'"))
+}
+
+/// Plain variables need a valid name; directives (`_.path`, `_.file`, ...) need a value.
+fn validate_env_row(row: &EditorEnvRow) -> Option<EditorProblem> {
+    if row.kind == EnvRowKind::Var {
+        return validate_env_key(&row.key);
+    }
+    row.list_entries().is_empty().then(|| {
+        EditorProblem::error(
+            format!("env.{}", row.key),
+            format!("directive '{}' has no value", row.key),
+        )
+    })
+}
+
+fn validate_env_key(key: &str) -> Option<EditorProblem> {
+    let valid = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    (!valid).then(|| {
+        EditorProblem::error(
+            format!("env.{key}"),
+            format!("invalid environment variable name '{key}'"),
+        )
+    })
+}
+
+fn validate_task(row: &EditorTaskRow) -> Vec<EditorProblem> {
+    let location = format!("tasks.{}", row.name);
+    let mut problems = Vec::new();
+    if row.name.is_empty() || row.name.contains(char::is_whitespace) {
+        problems.push(EditorProblem::error(
+            location.clone(),
+            format!("invalid task name '{}'", row.name),
+        ));
+    }
+    if row.command.trim().is_empty() && row.depends.is_empty() {
+        problems.push(EditorProblem::error(
+            location.clone(),
+            "task has no run or depends".to_string(),
+        ));
+    }
+    for (key, _) in &row.env {
+        if let Some(mut p) = validate_env_key(key) {
+            p.location = location.clone();
+            problems.push(p);
+        }
+    }
+    problems
+}
+
+/// Levenshtein distance, used for "did you mean" suggestions.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur.push((prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}